@@ -1,29 +1,211 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 
-use quote::quote;
-use syn::ItemStruct;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse::ParseStream, spanned::Spanned, Fields, FnArg, ItemFn, ItemStruct, Member, Meta,
+    NestedMeta, Pat, ReturnType, Token, Type,
+};
+
+mod view;
+
+/// Options of `#[compose(...)]` field attributes.
+const FIELD_OPTIONS: &[&str] = &["key", "skip_eq", "ptr_eq"];
+
+/// Generate caller key path expression, layer the `#[compose(key)]` field value on top of caller
+/// location if any.
+fn key_path(item_struct: &ItemStruct) -> syn::Result<TokenStream2> {
+    let mut keys = vec![];
+
+    let fields = match &item_struct.fields {
+        Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect::<Vec<_>>(),
+        Fields::Unit => vec![],
+    };
+
+    for (index, field) in fields.into_iter().enumerate() {
+        let options = compose_options(&field.attrs, FIELD_OPTIONS)?;
+
+        if let Some(option) = options.into_iter().find(|option| option == "key") {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+
+            keys.push((option, member));
+        }
+    }
+
+    if keys.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            &keys[1].0,
+            "only one field can be marked as #[compose(key)]",
+        ));
+    }
+
+    let caller = quote! {
+        let caller: agoraui_compose::keypath::KeyPath = std::panic::Location::caller().into();
+    };
+
+    if let Some((_, member)) = keys.pop() {
+        Ok(quote! {
+            #caller
+            let caller = caller.join(agoraui_compose::keypath::ToKeyPath::to_key_path(&self.#member));
+        })
+    } else {
+        Ok(caller)
+    }
+}
+
+/// Parse the options of `#[compose(...)]` attributes in `attrs`, each must be one of `known`.
+fn compose_options(attrs: &[syn::Attribute], known: &[&str]) -> syn::Result<Vec<syn::Ident>> {
+    let mut options = vec![];
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("compose")) {
+        let Meta::List(list) = attr.parse_meta()? else {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "expect #[compose(option, ...)] attribute",
+            ));
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path))
+                    if path
+                        .get_ident()
+                        .map(|ident| known.contains(&ident.to_string().as_str()))
+                        .unwrap_or(false) =>
+                {
+                    options.push(path.get_ident().unwrap().clone());
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        format!("unknown compose option, expect one of {}", known.join(", ")),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+/// Generate the `AnyEq` impl of `item_struct`, configurations of another type are never equal.
+///
+/// Compares by `PartialEq` of the struct, or field by field if some fields are marked
+/// `#[compose(skip_eq)]` or `#[compose(ptr_eq)]`, so only the compared fields must be `PartialEq`.
+/// With `#[compose(always_rebuild)]` it is never equal, the element is updated whenever its parent rebuilds.
+fn any_eq(item_struct: &ItemStruct) -> syn::Result<TokenStream2> {
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+
+    let name = &item_struct.ident;
+
+    let always_rebuild = !compose_options(&item_struct.attrs, &["always_rebuild"])?.is_empty();
+
+    let mut marked = false;
+    let mut comparisons = vec![];
+
+    for (index, field) in item_struct.fields.iter().enumerate() {
+        let options = compose_options(&field.attrs, FIELD_OPTIONS)?
+            .into_iter()
+            .filter(|option| option != "key")
+            .collect::<Vec<_>>();
+
+        if options.len() > 1 {
+            return Err(syn::Error::new_spanned(
+                &options[1],
+                "only one of skip_eq and ptr_eq can be set on a field",
+            ));
+        }
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+
+        match options.first() {
+            Some(option) if option == "skip_eq" => marked = true,
+            Some(_) => {
+                marked = true;
+
+                comparisons.push(quote! {
+                    agoraui_compose::view::PtrEq::ptr_eq(&self.#member, &other.#member)
+                });
+            }
+            None => comparisons.push(quote! {
+                std::cmp::PartialEq::eq(&self.#member, &other.#member)
+            }),
+        }
+    }
+
+    let eq = if always_rebuild {
+        quote! {
+            let _ = other;
+            false
+        }
+    } else if !marked {
+        quote! {
+            match other.downcast_ref::<#name #ty_generics>() {
+                Some(other) => self == other,
+                None => false,
+            }
+        }
+    } else {
+        if comparisons.is_empty() {
+            comparisons.push(quote! { true });
+        }
+
+        quote! {
+            match other.downcast_ref::<#name #ty_generics>() {
+                Some(other) => #(#comparisons)&&*,
+                None => false,
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics agoraui_compose::view::AnyEq for #name #ty_generics #where_clause {
+            fn eq(&self, other: &dyn std::any::Any) -> bool {
+                #eq
+            }
+        }
+    })
+}
 
-#[proc_macro_derive(Stateless)]
+#[proc_macro_derive(Stateless, attributes(compose))]
 pub fn derive_composite(item: TokenStream) -> TokenStream {
     let item_struct = syn::parse_macro_input!(item as ItemStruct);
 
+    stateless(&item_struct)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Generate the stateless configuration impls of `item_struct`, which has a `build` method.
+fn stateless(item_struct: &ItemStruct) -> syn::Result<TokenStream2> {
     let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
 
     let name = &item_struct.ident;
 
-    quote! {
+    let key_path = key_path(item_struct)?;
+
+    let any_eq = any_eq(item_struct)?;
+
+    Ok(quote! {
 
         impl #impl_generics agoraui_compose::view::StatelessConfiguration for #name #ty_generics #where_clause {
             fn framework_build(&self, element: &agoraui_compose::element::StatelessElement) -> agoraui_compose::view::View {
-                self.build(element).into_view()
+                agoraui_compose::view::IntoView::into_view(self.build(element))
             }
         }
 
         impl #impl_generics agoraui_compose::view::IntoView for #name #ty_generics #where_clause {
             #[track_caller]
             fn into_view(self) ->  agoraui_compose::view::View {
-                let caller: agoraui_compose::keypath::KeyPath = std::panic::Location::caller().into();
+                #key_path
                 agoraui_compose::view::View::Stateless((caller,self).into())
             }
         }
@@ -34,17 +216,212 @@ pub fn derive_composite(item: TokenStream) -> TokenStream {
             }
         }
 
-        impl #impl_generics agoraui_compose::view::AnyEq for #name #ty_generics #where_clause {
-            fn eq(&self, other: &dyn std::any::Any) -> bool {
-                self == other.downcast_ref::<#name #ty_generics>().unwrap()
+        #any_eq
+
+    })
+}
+
+/// Turn a function into a stateless view, e.g.
+///
+/// ```ignore
+/// #[component]
+/// fn Greeting(name: &String, #[compose(key)] id: u32, cx: &impl BuildContext) -> impl IntoView {
+///     ...
+/// }
+///
+/// Greeting { name: "world".to_owned(), id: 1 }.into_view()
+/// ```
+///
+/// Every parameter becomes a field of the props struct named after the function, props must be
+/// `Debug + Clone + PartialEq`. A `&T` parameter is a field of type `T` borrowed into the body,
+/// an owned parameter is cloned into the body when built.
+/// The `&impl BuildContext` parameter, if any, is not a prop and is bound to the element.
+///
+/// `#[compose(always_rebuild)]` on the function and `#[compose(skip_eq)]` or `#[compose(ptr_eq)]`
+/// on parameters apply to the props struct like on derived views, such props need not be `PartialEq`.
+#[proc_macro_attribute]
+pub fn component(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            TokenStream2::from(attr).span(),
+            "#[component] takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let item_fn = syn::parse_macro_input!(item as ItemFn);
+
+    component_struct(item_fn)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Returns true for `&impl BuildContext`, the trait path may be qualified.
+fn is_build_context(reference: &syn::TypeReference) -> bool {
+    let Type::ImplTrait(impl_trait) = &*reference.elem else {
+        return false;
+    };
+
+    reference.mutability.is_none()
+        && impl_trait.bounds.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => bound
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "BuildContext")
+                .unwrap_or(false),
+            _ => false,
+        })
+}
+
+fn component_struct(item_fn: ItemFn) -> syn::Result<TokenStream2> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = item_fn;
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "#[component] function can't be async",
+        ));
+    }
+
+    let mut fields = vec![];
+    let mut context = None;
+
+    for input in &sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[component] function can't take self",
+            ));
+        };
+
+        let mut by_ref = false;
+        let mut ty = arg.ty.clone();
+
+        if let Type::Reference(reference) = &*arg.ty {
+            if is_build_context(reference) {
+                if context.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "#[component] function takes at most one build context reference",
+                    ));
+                }
+
+                context = Some(arg.pat.clone());
+
+                continue;
             }
+
+            if reference.mutability.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &arg.ty,
+                    "#[component] props can't be mutable references",
+                ));
+            }
+
+            // Field of the referenced type, borrowed into the body instead of cloned.
+            by_ref = true;
+            ty = reference.elem.clone();
         }
 
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "expect identifier for #[component] prop",
+            ));
+        };
+
+        fields.push((
+            arg.attrs.clone(),
+            pat.ident.clone(),
+            arg.pat.clone(),
+            ty,
+            by_ref,
+        ));
     }
-    .into()
+
+    let name = &sig.ident;
+    let generics = &sig.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let output = match &sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+
+    let field_defs = fields.iter().map(|(attrs, ident, _, ty, _)| {
+        quote! { #(#attrs)* #vis #ident: #ty }
+    });
+
+    let (compose_attrs, attrs): (Vec<_>, Vec<_>) = attrs
+        .into_iter()
+        .partition(|attr| attr.path.is_ident("compose"));
+
+    // Struct with `#[compose(...)]` attributes for the key path and `AnyEq`, which are not emitted.
+    let item_struct: ItemStruct = syn::parse2(quote! {
+        #(#compose_attrs)*
+        #vis struct #name #generics #where_clause { #(#field_defs,)* }
+    })?;
+
+    let stateless = stateless(&item_struct)?;
+
+    let field_defs = fields.iter().map(|(attrs, ident, _, ty, _)| {
+        let attrs = attrs.iter().filter(|attr| !attr.path.is_ident("compose"));
+
+        quote! { #(#attrs)* #vis #ident: #ty }
+    });
+
+    let props = fields.iter().map(|(_, ident, pat, _, by_ref)| {
+        if *by_ref {
+            quote! { let #pat = &self.#ident; }
+        } else {
+            quote! { let #pat = std::clone::Clone::clone(&self.#ident); }
+        }
+    });
+
+    let context = context.map(|pat| quote! { let #pat = element; });
+
+    // `AnyEq` compares field by field with `#[compose(...)]` options other than `key`,
+    // props need not be `PartialEq`.
+    let mut compare_fields = !compose_attrs.is_empty();
+
+    for (attrs, ..) in &fields {
+        compare_fields |= compose_options(attrs, FIELD_OPTIONS)?
+            .iter()
+            .any(|option| option != "key");
+    }
+
+    let partial_eq = if compare_fields {
+        quote! {}
+    } else {
+        quote! { #[derive(PartialEq)] }
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #[derive(Debug, Clone)]
+        #partial_eq
+        #vis struct #name #generics #where_clause { #(#field_defs,)* }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            fn build(&self, element: &agoraui_compose::element::StatelessElement) -> #output {
+                #(#props)*
+                #context
+                #block
+            }
+        }
+
+        #stateless
+    })
 }
 
-#[proc_macro_derive(Render)]
+#[proc_macro_derive(Render, attributes(render, compose))]
 pub fn derive_render_object(item: TokenStream) -> TokenStream {
     let item_struct = syn::parse_macro_input!(item as ItemStruct);
 
@@ -52,6 +429,34 @@ pub fn derive_render_object(item: TokenStream) -> TokenStream {
 
     let name = &item_struct.ident;
 
+    let key_path = match key_path(&item_struct) {
+        Ok(key_path) => key_path,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let any_eq = match any_eq(&item_struct) {
+        Ok(any_eq) => any_eq,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let hooks = match hooks(&item_struct, "render", RENDER_HOOKS) {
+        Ok(hooks) => hooks,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let hooks = hooks.iter().map(|hook| {
+        let framework_hook = format_ident!("framework_{}", hook);
+
+        quote! {
+            fn #framework_hook(
+                &self,
+                render_object: &mut dyn agoraui_compose::render::RenderObjectLifecycle,
+            ) -> agoraui_compose::render::RenderObjectUpdate {
+                self.#hook(render_object)
+            }
+        }
+    });
+
     quote! {
 
         impl #impl_generics agoraui_compose::view::RenderObjectConfiguration for #name #ty_generics #where_clause {
@@ -59,15 +464,17 @@ pub fn derive_render_object(item: TokenStream) -> TokenStream {
                 self.create_render_object().into()
             }
 
-            fn framework_render_object_children(&self) -> Vec<View> {
+            fn framework_render_object_children(&self) -> Vec<agoraui_compose::view::View> {
                 self.render_object_children()
             }
+
+            #(#hooks)*
         }
 
         impl #impl_generics agoraui_compose::view::IntoView for #name #ty_generics #where_clause {
             #[track_caller]
             fn into_view(self) ->  agoraui_compose::view::View {
-                let caller: agoraui_compose::keypath::KeyPath = std::panic::Location::caller().into();
+                #key_path
                 agoraui_compose::view::View::RenderObject((caller,self).into())
             }
         }
@@ -78,17 +485,65 @@ pub fn derive_render_object(item: TokenStream) -> TokenStream {
             }
         }
 
-        impl #impl_generics agoraui_compose::view::AnyEq for #name #ty_generics #where_clause {
-            fn eq(&self, other: &dyn std::any::Any) -> bool {
-                self == other.downcast_ref::<#name #ty_generics>().unwrap()
-            }
-        }
+        #any_eq
 
     }
     .into()
 }
 
-#[proc_macro_derive(State)]
+/// State lifecycle hooks can be forwarded to inherent methods by `#[state(...)]` attribute.
+const STATE_HOOKS: &[&str] = &[
+    "init_state",
+    "did_update_config",
+    "did_change_dependencies",
+    "activate",
+    "deactivate",
+    "dispose",
+];
+
+/// Render object hooks can be forwarded to inherent methods by `#[render(...)]` attribute.
+const RENDER_HOOKS: &[&str] = &["update_render_object"];
+
+/// Parse hook list of attribute `name`, e.g. `#[state(init_state, dispose)]`.
+fn hooks(item_struct: &ItemStruct, name: &str, known: &[&str]) -> syn::Result<Vec<syn::Ident>> {
+    let mut hooks = vec![];
+
+    for attr in item_struct
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident(name))
+    {
+        let Meta::List(list) = attr.parse_meta()? else {
+            return Err(syn::Error::new_spanned(
+                attr,
+                format!("expect #[{}(hook, ...)] attribute", name),
+            ));
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path))
+                    if path
+                        .get_ident()
+                        .map(|ident| known.contains(&ident.to_string().as_str()))
+                        .unwrap_or(false) =>
+                {
+                    hooks.push(path.get_ident().unwrap().clone());
+                }
+                nested => {
+                    return Err(syn::Error::new_spanned(
+                        nested,
+                        format!("unknown {} hook, expect one of {}", name, known.join(", ")),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(hooks)
+}
+
+#[proc_macro_derive(State, attributes(state))]
 pub fn derive_state(item: TokenStream) -> TokenStream {
     let item_struct = syn::parse_macro_input!(item as ItemStruct);
 
@@ -96,18 +551,112 @@ pub fn derive_state(item: TokenStream) -> TokenStream {
 
     let name = &item_struct.ident;
 
+    let hooks = match hooks(&item_struct, "state", STATE_HOOKS) {
+        Ok(hooks) => hooks,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let hooks = hooks.iter().map(|hook| {
+        let framework_hook = format_ident!("framework_{}", hook);
+
+        if hook == "did_update_config" {
+            quote! {
+                fn #framework_hook(
+                    &mut self,
+                    element: &agoraui_compose::element::StatefulElement,
+                    old: &agoraui_compose::view::Configuration<dyn agoraui_compose::view::StatefulConfiguration>,
+                ) {
+                    self.#hook(element, old)
+                }
+            }
+        } else {
+            quote! {
+                fn #framework_hook(&mut self, element: &agoraui_compose::element::StatefulElement) {
+                    self.#hook(element)
+                }
+            }
+        }
+    });
+
     quote! {
 
         impl #impl_generics agoraui_compose::view::State for #name #ty_generics #where_clause {
-            fn framework_build(&self, element: element: &agoraui_compose::element::StatefulElement) -> agoraui_compose::View {
-                self.build(element).into_view()
+            fn framework_build(&self, element: &agoraui_compose::element::StatefulElement) -> agoraui_compose::view::View {
+                agoraui_compose::view::IntoView::into_view(self.build(element))
             }
+
+            #(#hooks)*
         }
     }
     .into()
 }
 
-#[proc_macro_derive(Stateful)]
+/// Parsed `#[stateful(state = MyState, init = path)]` attribute.
+struct StatefulAttr {
+    state: syn::Type,
+    /// Function creating the state from the configuration, `Default` if `None`.
+    init: Option<syn::ExprPath>,
+}
+
+/// Parse `#[stateful(state = MyState, init = path)]` attribute, returns `None` if absent.
+fn stateful_attr(item_struct: &ItemStruct) -> syn::Result<Option<StatefulAttr>> {
+    let mut attrs = item_struct
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("stateful"));
+
+    let Some(attr) = attrs.next() else {
+        return Ok(None);
+    };
+
+    if let Some(attr) = attrs.next() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "only one #[stateful(...)] attribute is allowed",
+        ));
+    }
+
+    attr.parse_args_with(|input: ParseStream| {
+        let (mut state, mut init) = (None, None);
+
+        while !input.is_empty() {
+            let ident: syn::Ident = input.parse()?;
+
+            input.parse::<Token![=]>()?;
+
+            if ident == "state" && state.is_none() {
+                state = Some(input.parse::<syn::Type>()?);
+            } else if ident == "init" && init.is_none() {
+                init = Some(input.parse::<syn::ExprPath>()?);
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expect #[stateful(state = StateType, init = path)] attribute",
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let state = state.ok_or_else(|| {
+            syn::Error::new(
+                input.span(),
+                "expect #[stateful(state = StateType)] attribute",
+            )
+        })?;
+
+        Ok(Some(StatefulAttr { state, init }))
+    })
+}
+
+/// Derive `StatefulConfiguration`, the state is created by a `create_state(&self) -> S` method.
+///
+/// `#[stateful(state = S)]` creates the state with `S::default()` instead, and
+/// `#[stateful(state = S, init = path)]` with `path(&self)`, e.g. `init = Self::initial_state`
+/// to seed the state from the configuration.
+#[proc_macro_derive(Stateful, attributes(stateful, compose))]
 pub fn derive_composite_with_state(item: TokenStream) -> TokenStream {
     let item_struct = syn::parse_macro_input!(item as ItemStruct);
 
@@ -115,18 +664,63 @@ pub fn derive_composite_with_state(item: TokenStream) -> TokenStream {
 
     let name = &item_struct.ident;
 
+    let key_path = match key_path(&item_struct) {
+        Ok(key_path) => key_path,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let any_eq = match any_eq(&item_struct) {
+        Ok(any_eq) => any_eq,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let stateful = match stateful_attr(&item_struct) {
+        Ok(stateful) => stateful,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    // With `#[stateful(state = ...)]` the state type is checked to implement `State`,
+    // i.e. to build a view, reported at the attribute.
+    let create_state = match stateful {
+        Some(StatefulAttr { state, init: None }) => quote_spanned! {state.span()=>
+            {
+                fn assert_state<S: agoraui_compose::view::State + Default + 'static>() -> S {
+                    S::default()
+                }
+
+                assert_state::<#state>()
+            }
+        },
+        Some(StatefulAttr {
+            state,
+            init: Some(init),
+        }) => quote_spanned! {state.span()=>
+            {
+                fn assert_state<C: ?Sized, S: agoraui_compose::view::State + 'static>(
+                    config: &C,
+                    init: impl FnOnce(&C) -> S,
+                ) -> S {
+                    init(config)
+                }
+
+                assert_state::<Self, #state>(self, #init)
+            }
+        },
+        None => quote! { self.create_state() },
+    };
+
     quote! {
 
         impl #impl_generics agoraui_compose::view::StatefulConfiguration for #name #ty_generics #where_clause {
             fn framework_create_state(&self) -> Box<dyn agoraui_compose::view::State> {
-                Box::new(self.create_state())
+                Box::new(#create_state)
             }
         }
 
         impl #impl_generics agoraui_compose::view::IntoView for #name #ty_generics #where_clause {
             #[track_caller]
             fn into_view(self) ->  agoraui_compose::view::View {
-                let caller: agoraui_compose::keypath::KeyPath = std::panic::Location::caller().into();
+                #key_path
                  agoraui_compose::view::View::Stateful((caller,self).into())
             }
         }
@@ -137,11 +731,96 @@ pub fn derive_composite_with_state(item: TokenStream) -> TokenStream {
             }
         }
 
-        impl #impl_generics agoraui_compose::view::AnyEq for #name #ty_generics #where_clause {
-            fn eq(&self, other: &dyn std::any::Any) -> bool {
-                self == other.downcast_ref::<#name #ty_generics>().unwrap()
+        #any_eq
+    }
+    .into()
+}
+
+#[proc_macro_derive(Inherited, attributes(compose))]
+pub fn derive_inherited(item: TokenStream) -> TokenStream {
+    let item_struct = syn::parse_macro_input!(item as ItemStruct);
+
+    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+
+    let name = &item_struct.ident;
+
+    let key_path = match key_path(&item_struct) {
+        Ok(key_path) => key_path,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let any_eq = match any_eq(&item_struct) {
+        Ok(any_eq) => any_eq,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    quote! {
+
+        impl #impl_generics agoraui_compose::view::InheritedConfiguration for #name #ty_generics #where_clause {
+            fn framework_child(&self) -> agoraui_compose::view::View {
+                self.child()
+            }
+        }
+
+        impl #impl_generics agoraui_compose::view::IntoView for #name #ty_generics #where_clause {
+            #[track_caller]
+            fn into_view(self) ->  agoraui_compose::view::View {
+                #key_path
+                agoraui_compose::view::View::Inherited((caller,self).into())
             }
         }
+
+        impl #impl_generics agoraui_compose::view::ToAny for #name #ty_generics #where_clause {
+            fn to_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+
+        #any_eq
     }
     .into()
 }
+
+/// Declare a view tree, e.g.
+///
+/// ```ignore
+/// view! {
+///     GestureDetector(on_tap: move |_| count.update(|count| *count += 1), ..) {
+///         Column(spacing) [
+///             "title"
+///             if count.get() > 0 {
+///                 Counter(value: count.get())
+///             }
+///             for item in &items {
+///                 Item(key: item.id, label: item.label.clone())
+///             }
+///             match mode {
+///                 Mode::Edit => Editor(..),
+///                 _ => {}
+///             }
+///             { footer.clone() }
+///         ]
+///     }
+/// }
+/// ```
+///
+/// `Name(props)` is the struct literal `Name { props }`, `name` alone is shorthand of `name: name`
+/// and a trailing `..` fills the remaining fields with `Default::default()`. A closure prop is an
+/// event handler set into an `Option<Callback<_>>` field, wrap the closure in parentheses to pass
+/// it as is. The `key` prop layers a key on top of the view key path.
+///
+/// A `{ ... }` block after the props sets the `child` field to its single view, or `View::Empty`
+/// for an `if` without `else`, and a `[ ... ]` list sets the `children` field. A string literal is
+/// a [`Text`] and `{ expr }` converts any `IntoView` expression, e.g. a `View`. The views of
+/// `if`, `match` and `for` bodies are spliced into the children list, views created by a `for`
+/// loop are keyed by the iteration index unless they have a `key`.
+///
+/// Evaluates to a `View`, or to a `Vec<View>` when the whole input is a children list `[ ... ]`.
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    let view = syn::parse_macro_input!(input as view::ViewMacro);
+
+    view.expand()
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
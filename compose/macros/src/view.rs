@@ -0,0 +1,693 @@
+//! Parser and code generator of the `view!` macro.
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{
+    braced, bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    token, Expr, ExprPath, Ident, LitStr, Pat, Token,
+};
+
+/// Root of `view!`, a single view or a children list in brackets.
+#[allow(clippy::large_enum_variant)]
+pub enum ViewMacro {
+    Single(Node),
+    List(Vec<Node>),
+}
+
+impl Parse for ViewMacro {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Err(input.error("expected a view, e.g. `view! { Text(text: ..., ..) }`"));
+        }
+
+        if input.peek(token::Bracket) {
+            let content;
+
+            bracketed!(content in input);
+
+            let nodes = parse_nodes(&content)?;
+
+            if !input.is_empty() {
+                return Err(input.error("unexpected tokens after the children list"));
+            }
+
+            return Ok(ViewMacro::List(nodes));
+        }
+
+        let node = input.parse()?;
+
+        if !input.is_empty() {
+            return Err(input.error(
+                "view! expects a single root view, put siblings into a children list `[...]`",
+            ));
+        }
+
+        Ok(ViewMacro::Single(node))
+    }
+}
+
+impl ViewMacro {
+    /// Expand into a `View` expression, or a `Vec<View>` expression for a children list.
+    pub fn expand(&self) -> syn::Result<TokenStream2> {
+        let scope = Scope::default();
+
+        match self {
+            ViewMacro::Single(node) => node.single(&scope),
+            ViewMacro::List(nodes) => list(nodes, &scope),
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+pub enum Node {
+    Element(Element),
+    /// String literal, shorthand of `Text`.
+    Text(LitStr),
+    /// `{ expr }` of any type implementing `IntoView`.
+    Expr(token::Brace, Expr),
+    If(IfNode),
+    Match(MatchNode),
+    For(ForNode),
+}
+
+impl Node {
+    fn span(&self) -> Span {
+        match self {
+            Node::Element(element) => element.path.span(),
+            Node::Text(text) => text.span(),
+            Node::Expr(brace, _) => brace.span,
+            Node::If(node) => node.if_token.span,
+            Node::Match(node) => node.match_token.span,
+            Node::For(node) => node.for_token.span,
+        }
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![if]) {
+            input.parse().map(Node::If)
+        } else if input.peek(Token![match]) {
+            input.parse().map(Node::Match)
+        } else if input.peek(Token![for]) {
+            input.parse().map(Node::For)
+        } else if input.peek(LitStr) {
+            input.parse().map(Node::Text)
+        } else if input.peek(token::Brace) {
+            let content;
+
+            let brace = braced!(content in input);
+
+            Ok(Node::Expr(brace, content.parse()?))
+        } else if input.peek(Ident) || input.peek(Token![::]) {
+            input.parse().map(Node::Element)
+        } else {
+            Err(input.error(
+                "expected a view: `Name(props) { child }`, `Name(props) [children]`, \
+                 a string literal, `{ expr }`, `if`, `match` or `for`",
+            ))
+        }
+    }
+}
+
+/// Nodes separated by optional commas.
+fn parse_nodes(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = vec![];
+
+    while !input.is_empty() {
+        nodes.push(input.parse()?);
+
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+    }
+
+    Ok(nodes)
+}
+
+/// Nodes of a `{ ... }` block of `if`, `match` or `for`.
+fn parse_block(input: ParseStream) -> syn::Result<(token::Brace, Vec<Node>)> {
+    let content;
+
+    let brace = braced!(content in input);
+
+    Ok((brace, parse_nodes(&content)?))
+}
+
+/// `Name(prop: value, handler: |args| ..., key: value, ..) { child }` or `... [children]`.
+pub struct Element {
+    path: ExprPath,
+    props: Vec<Prop>,
+    key: Option<Expr>,
+    base: Option<Base>,
+    children: Option<Children>,
+}
+
+struct Prop {
+    name: Ident,
+    /// `None` for the `name` shorthand of `name: name`.
+    value: Option<Expr>,
+}
+
+struct Base {
+    dot2: Token![..],
+    /// `None` for `..`, which fills the remaining fields with `Default::default()`.
+    expr: Option<Expr>,
+}
+
+enum Children {
+    Child(token::Brace, Vec<Node>),
+    List(Vec<Node>),
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: ExprPath = input.parse()?;
+
+        if let Some(qself) = &path.qself {
+            return Err(syn::Error::new(
+                qself.lt_token.span,
+                "qualified paths are not supported in view!",
+            ));
+        }
+
+        let mut element = Element {
+            path,
+            props: vec![],
+            key: None,
+            base: None,
+            children: None,
+        };
+
+        if input.peek(token::Paren) {
+            let content;
+
+            parenthesized!(content in input);
+
+            element.parse_props(&content)?;
+        }
+
+        if input.peek(token::Brace) {
+            let (brace, nodes) = parse_block(input)?;
+
+            element.children = Some(Children::Child(brace, nodes));
+        } else if input.peek(token::Bracket) {
+            let content;
+
+            bracketed!(content in input);
+
+            element.children = Some(Children::List(parse_nodes(&content)?));
+        }
+
+        Ok(element)
+    }
+}
+
+impl Element {
+    fn parse_props(&mut self, input: ParseStream) -> syn::Result<()> {
+        while !input.is_empty() {
+            if self.base.is_some() {
+                return Err(input.error("`..` must be the last prop"));
+            }
+
+            if input.peek(Token![..]) {
+                let dot2 = input.parse()?;
+
+                let expr = if input.is_empty() || input.peek(Token![,]) {
+                    None
+                } else {
+                    Some(input.parse()?)
+                };
+
+                self.base = Some(Base { dot2, expr });
+            } else {
+                let name: Ident = input.parse()?;
+
+                let value = if input.peek(Token![:]) {
+                    input.parse::<Token![:]>()?;
+
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
+
+                if name == "key" {
+                    if self.key.is_some() {
+                        return Err(syn::Error::new(name.span(), "duplicate key"));
+                    }
+
+                    self.key = Some(match value {
+                        Some(value) => value,
+                        None => syn::parse_quote!(#name),
+                    });
+                } else {
+                    if self.props.iter().any(|prop| prop.name == name) {
+                        return Err(syn::Error::new(
+                            name.span(),
+                            format!("duplicate prop `{}`", name),
+                        ));
+                    }
+
+                    self.props.push(Prop { name, value });
+                }
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `if cond { nodes } else if cond { nodes } else { nodes }`.
+pub struct IfNode {
+    if_token: Token![if],
+    cond: Expr,
+    then_branch: Vec<Node>,
+    else_branch: Option<ElseBranch>,
+}
+
+enum ElseBranch {
+    If(Box<IfNode>),
+    Block(Vec<Node>),
+}
+
+impl Parse for IfNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let if_token = input.parse()?;
+
+        let cond = Expr::parse_without_eager_brace(input)?;
+
+        let (_, then_branch) = parse_block(input)?;
+
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+
+            if input.peek(Token![if]) {
+                Some(ElseBranch::If(Box::new(input.parse()?)))
+            } else {
+                let (_, nodes) = parse_block(input)?;
+
+                Some(ElseBranch::Block(nodes))
+            }
+        } else {
+            None
+        };
+
+        Ok(IfNode {
+            if_token,
+            cond,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+/// `match expr { pat if guard => node, pat => { nodes } }`.
+pub struct MatchNode {
+    match_token: Token![match],
+    expr: Expr,
+    arms: Vec<Arm>,
+}
+
+struct Arm {
+    pats: Vec<Pat>,
+    guard: Option<Expr>,
+    nodes: Vec<Node>,
+}
+
+impl Parse for MatchNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let match_token = input.parse()?;
+
+        let expr = Expr::parse_without_eager_brace(input)?;
+
+        let content;
+
+        braced!(content in input);
+
+        let mut arms = vec![];
+
+        while !content.is_empty() {
+            if content.peek(Token![|]) {
+                content.parse::<Token![|]>()?;
+            }
+
+            let mut pats = vec![content.parse()?];
+
+            while content.peek(Token![|]) {
+                content.parse::<Token![|]>()?;
+
+                pats.push(content.parse()?);
+            }
+
+            let guard = if content.peek(Token![if]) {
+                content.parse::<Token![if]>()?;
+
+                Some(content.parse()?)
+            } else {
+                None
+            };
+
+            content.parse::<Token![=>]>()?;
+
+            let nodes = if content.peek(token::Brace) {
+                parse_block(&content)?.1
+            } else {
+                vec![content.parse()?]
+            };
+
+            if content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+            }
+
+            arms.push(Arm { pats, guard, nodes });
+        }
+
+        Ok(MatchNode {
+            match_token,
+            expr,
+            arms,
+        })
+    }
+}
+
+/// `for pat in expr { nodes }`, each iteration is keyed by its index unless the view has a `key`.
+pub struct ForNode {
+    for_token: Token![for],
+    pat: Pat,
+    expr: Expr,
+    body: Vec<Node>,
+}
+
+impl Parse for ForNode {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let for_token = input.parse()?;
+
+        let pat = input.parse()?;
+
+        input.parse::<Token![in]>()?;
+
+        let expr = Expr::parse_without_eager_brace(input)?;
+
+        let (_, body) = parse_block(input)?;
+
+        Ok(ForNode {
+            for_token,
+            pat,
+            expr,
+            body,
+        })
+    }
+}
+
+/// Enclosing `for` loops of the generated code.
+#[derive(Default)]
+struct Scope {
+    /// Key path expression of the current iteration, the indices of the enclosing loops.
+    key: Option<TokenStream2>,
+    depth: usize,
+}
+
+impl Scope {
+    fn enter_loop(&self) -> (Ident, Scope) {
+        let index = Ident::new(&format!("index_{}", self.depth), Span::mixed_site());
+
+        let index_key = quote!(agoraui_compose::keypath::KeyPath::Index(#index));
+
+        let key = match &self.key {
+            Some(key) => quote!(#key.join(#index_key)),
+            None => index_key,
+        };
+
+        (
+            index,
+            Scope {
+                key: Some(key),
+                depth: self.depth + 1,
+            },
+        )
+    }
+}
+
+/// Generate a `Vec<View>` expression of `nodes`.
+fn list(nodes: &[Node], scope: &Scope) -> syn::Result<TokenStream2> {
+    let views = Ident::new("views", Span::mixed_site());
+
+    let pushes = push(nodes, &views, scope)?;
+
+    Ok(quote! {
+        {
+            let mut #views: std::vec::Vec<agoraui_compose::view::View> = std::vec::Vec::new();
+            #pushes
+            #views
+        }
+    })
+}
+
+/// Generate statements pushing the views of `nodes` into `views`.
+fn push(nodes: &[Node], views: &Ident, scope: &Scope) -> syn::Result<TokenStream2> {
+    let mut stmts = TokenStream2::new();
+
+    for node in nodes {
+        let stmt = match node {
+            Node::Element(_) | Node::Text(_) | Node::Expr(..) => {
+                let view = node.single(scope)?;
+
+                quote!(#views.push(#view);)
+            }
+            Node::If(node) => node.push(views, scope)?,
+            Node::Match(node) => {
+                let expr = &node.expr;
+
+                let arms = node
+                    .arms
+                    .iter()
+                    .map(|arm| {
+                        let head = arm.head();
+                        let body = push(&arm.nodes, views, scope)?;
+
+                        Ok(quote!(#head => { #body }))
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+
+                quote!(match #expr { #(#arms)* })
+            }
+            Node::For(node) => {
+                let (index, inner) = scope.enter_loop();
+
+                let pat = &node.pat;
+                let expr = &node.expr;
+                let body = push(&node.body, views, &inner)?;
+
+                quote! {
+                    for (#index, #pat) in std::iter::Iterator::enumerate(std::iter::IntoIterator::into_iter(#expr)) {
+                        #body
+                    }
+                }
+            }
+        };
+
+        stmts.extend(stmt);
+    }
+
+    Ok(stmts)
+}
+
+/// Generate a `View` expression of a block which must hold at most one view.
+fn single_of(nodes: &[Node], scope: &Scope) -> syn::Result<TokenStream2> {
+    match nodes {
+        [] => Ok(quote!(agoraui_compose::view::View::Empty)),
+        [node] => node.single(scope),
+        [_, node, ..] => Err(syn::Error::new(
+            node.span(),
+            "expected a single view, put siblings into a children list `[...]`",
+        )),
+    }
+}
+
+impl Node {
+    /// Generate a `View` expression of this node.
+    fn single(&self, scope: &Scope) -> syn::Result<TokenStream2> {
+        match self {
+            Node::Element(element) => element.expand(scope),
+            Node::Text(text) => {
+                let view = quote_spanned! {text.span()=>
+                    agoraui_compose::view::IntoView::into_view(agoraui_compose::text::Text::new(#text))
+                };
+
+                Ok(with_key(view, None, scope))
+            }
+            Node::Expr(brace, expr) => {
+                let view = quote_spanned! {brace.span=>
+                    agoraui_compose::view::IntoView::into_view(#expr)
+                };
+
+                Ok(with_key(view, None, scope))
+            }
+            Node::If(node) => node.single(scope),
+            Node::Match(node) => {
+                let expr = &node.expr;
+
+                let arms = node
+                    .arms
+                    .iter()
+                    .map(|arm| {
+                        let head = arm.head();
+                        let body = single_of(&arm.nodes, scope)?;
+
+                        Ok(quote!(#head => #body,))
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+
+                Ok(quote!(match #expr { #(#arms)* }))
+            }
+            Node::For(node) => Err(syn::Error::new(
+                node.for_token.span,
+                "for loop yields several views, put it into a children list `[...]`",
+            )),
+        }
+    }
+}
+
+impl Arm {
+    fn head(&self) -> TokenStream2 {
+        let pats = &self.pats;
+
+        let guard = self.guard.as_ref().map(|guard| quote!(if #guard));
+
+        quote!(#(#pats)|* #guard)
+    }
+}
+
+impl IfNode {
+    fn single(&self, scope: &Scope) -> syn::Result<TokenStream2> {
+        let cond = &self.cond;
+
+        let then_branch = single_of(&self.then_branch, scope)?;
+
+        let else_branch = match &self.else_branch {
+            Some(ElseBranch::If(node)) => node.single(scope)?,
+            Some(ElseBranch::Block(nodes)) => single_of(nodes, scope)?,
+            None => quote!(agoraui_compose::view::View::Empty),
+        };
+
+        Ok(quote!(if #cond { #then_branch } else { #else_branch }))
+    }
+
+    fn push(&self, views: &Ident, scope: &Scope) -> syn::Result<TokenStream2> {
+        let cond = &self.cond;
+
+        let then_branch = push(&self.then_branch, views, scope)?;
+
+        let else_branch = match &self.else_branch {
+            Some(ElseBranch::If(node)) => {
+                let node = node.push(views, scope)?;
+
+                quote!(else #node)
+            }
+            Some(ElseBranch::Block(nodes)) => {
+                let nodes = push(nodes, views, scope)?;
+
+                quote!(else { #nodes })
+            }
+            None => quote!(),
+        };
+
+        Ok(quote!(if #cond { #then_branch } #else_branch))
+    }
+}
+
+impl Element {
+    fn expand(&self, scope: &Scope) -> syn::Result<TokenStream2> {
+        let path = &self.path;
+
+        let view = Ident::new("view", Span::mixed_site());
+
+        let mut fields = vec![];
+        let mut handlers = vec![];
+
+        for prop in &self.props {
+            let name = &prop.name;
+
+            match &prop.value {
+                // Closures are event handlers, set after the struct is created so the
+                // closure arguments are inferred from the `Option<Callback<_>>` field.
+                Some(Expr::Closure(closure)) => {
+                    fields.push(quote!(#name: std::option::Option::None));
+
+                    handlers.push(quote_spanned! {closure.span()=>
+                        agoraui_compose::callback::Callback::set_handler(&mut #view.#name, #closure);
+                    });
+                }
+                Some(value) => fields.push(quote!(#name: #value)),
+                None => fields.push(quote!(#name)),
+            }
+        }
+
+        match &self.children {
+            Some(Children::Child(brace, nodes)) => {
+                let child = match nodes.as_slice() {
+                    [] => return Err(syn::Error::new(brace.span, "expected a child view")),
+                    nodes => single_of(nodes, &Scope::default())?,
+                };
+
+                fields.push(quote!(child: #child));
+            }
+            Some(Children::List(nodes)) => {
+                let children = list(nodes, &Scope::default())?;
+
+                fields.push(quote!(children: #children));
+            }
+            None => {}
+        }
+
+        let base = self.base.as_ref().map(|base| {
+            let dot2 = &base.dot2;
+
+            match &base.expr {
+                Some(expr) => quote!(#dot2 #expr),
+                None => quote_spanned!(dot2.spans[0]=> #dot2 std::default::Default::default()),
+            }
+        });
+
+        let literal = quote!(#path { #(#fields,)* #base });
+
+        // The call is spanned at the view name, so the caller location of `into_view`
+        // and therefore the key path tells apart sibling views of one `view!`.
+        let view = if handlers.is_empty() {
+            quote_spanned! {path.span()=>
+                agoraui_compose::view::IntoView::into_view(#literal)
+            }
+        } else {
+            let into_view = quote_spanned! {path.span()=>
+                agoraui_compose::view::IntoView::into_view(#view)
+            };
+
+            quote! {
+                {
+                    let mut #view = #literal;
+                    #(#handlers)*
+                    #into_view
+                }
+            }
+        };
+
+        Ok(with_key(view, self.key.as_ref(), scope))
+    }
+}
+
+/// Layer the explicit `key`, or else the index of the enclosing loops, on top of the view key path.
+fn with_key(view: TokenStream2, key: Option<&Expr>, scope: &Scope) -> TokenStream2 {
+    match (key, &scope.key) {
+        (Some(key), _) => quote!(#view.with_key(#key)),
+        (None, Some(key)) => quote!(#view.with_key(#key)),
+        (None, None) => view,
+    }
+}
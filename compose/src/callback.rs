@@ -0,0 +1,46 @@
+use std::{fmt::Debug, rc::Rc};
+
+/// Shared event handler stored in configurations.
+///
+/// Closures can't be compared, two callbacks are equal only if they share the same closure,
+/// so configurations holding callbacks can still derive `PartialEq`.
+pub struct Callback<A = ()>(Rc<dyn Fn(A)>);
+
+impl<A> Callback<A> {
+    pub fn new<F: Fn(A) + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+
+    pub fn call(&self, args: A) {
+        (self.0)(args)
+    }
+
+    /// Store `f` into the handler `slot`, the closure arguments are inferred from the slot type.
+    pub fn set_handler<F: Fn(A) + 'static>(slot: &mut Option<Self>, f: F) {
+        *slot = Some(Self::new(f));
+    }
+}
+
+impl<A> Clone for Callback<A> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<A> PartialEq for Callback<A> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<A> Debug for Callback<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Callback({:p})", Rc::as_ptr(&self.0))
+    }
+}
+
+impl<A, F: Fn(A) + 'static> From<F> for Callback<A> {
+    fn from(f: F) -> Self {
+        Self::new(f)
+    }
+}
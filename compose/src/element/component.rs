@@ -10,8 +10,18 @@ pub trait ComponentElement: Lifecycle {
     fn child(&self) -> Option<ElementId>;
 
     fn composite_rebuild(&self, build_context: &mut FrameworkContext) {
+        build_context
+            .build_owner
+            .clean(self.to_id().expect("Call initialize first"));
+
+        self.clear_dependencies(build_context);
+
+        self.hooks().begin_build();
+
         let new_configuration = self.build();
 
+        self.hooks().end_build();
+
         let child = self.child().map(|id| {
             build_context
                 .element_tree
@@ -31,17 +41,16 @@ pub trait ComponentElement: Lifecycle {
         &self,
         build_context: &FrameworkContext,
     ) -> Option<ElementId> {
-        let arena = build_context.element_tree.borrow();
-        let children = self.to_id().unwrap().children(&arena);
-
-        for child in children {
-            let id = arena.get(child).unwrap().get().to_render_object_id();
-
-            if id.is_some() {
-                return Some(child);
-            }
-        }
+        let child = self.child().map(|id| {
+            build_context
+                .element_tree
+                .borrow()
+                .get(id)
+                .unwrap()
+                .get()
+                .clone()
+        });
 
-        None
+        child.and_then(|child| child.search_first_render_object_element_id(build_context))
     }
 }
@@ -1,21 +1,257 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{
+    cell::{Ref, RefCell},
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    rc::Rc,
+};
 
-use indextree::NodeId;
+use indextree::{Arena, NodeId};
 
 use crate::{
-    framework::FrameworkContext,
-    view::{Configuration, RenderObject, RenderObjectId, View},
+    framework::{BuildOwner, FrameworkContext, WeakFrameworkContext},
+    geometry::Size,
+    keypath::KeyPath,
+    render::mark_parent_needs_layout,
+    view::{Configuration, RenderObject, RenderObjectId, State, View},
 };
 
+use super::{Effect, EffectCleanup, Hooks, UseState};
+
 /// Element id in index tree.
 pub type ElementId = NodeId;
 
-pub trait BuildContext {}
+/// Context of the element being built, passed to `build` methods of configurations and states.
+pub trait BuildContext: Lifecycle {
+    /// Look up the nearest ancestor inherited view of type `T` and call `f` with it.
+    ///
+    /// This element is registered as a dependent, and rebuilt when the inherited view
+    /// is updated with a configuration which should notify, see
+    /// [`InheritedConfiguration::framework_update_should_notify`](crate::view::InheritedConfiguration::framework_update_should_notify).
+    fn depend_on_inherited<T: 'static, R, F: FnOnce(&T) -> R>(&self, f: F) -> Option<R> {
+        let build_context = self.to_context()?;
+
+        let id = self.to_id()?;
+
+        let ancestor = find_ancestor_inherited::<T>(&build_context.element_tree.borrow(), id)?;
+
+        ancestor.0.add_dependent(id);
+
+        let ancestor_id = ancestor.to_id()?;
+
+        if !self.dependencies().borrow().contains(&ancestor_id) {
+            self.dependencies().borrow_mut().push(ancestor_id);
+        }
+
+        match ancestor.to_configuration() {
+            View::Inherited(config) => config.downcast(f),
+            _ => None,
+        }
+    }
+
+    /// Look up the nearest ancestor inherited view of type `T` without registering a dependency.
+    fn get_inherited<T: 'static, R, F: FnOnce(&T) -> R>(&self, f: F) -> Option<R> {
+        let build_context = self.to_context()?;
+
+        let ancestor =
+            find_ancestor_inherited::<T>(&build_context.element_tree.borrow(), self.to_id()?)?;
+
+        match ancestor.to_configuration() {
+            View::Inherited(config) => config.downcast(f),
+            _ => None,
+        }
+    }
+
+    /// Look up the nearest ancestor stateful element which state type is `S` and call `f` with the state.
+    fn find_ancestor_state_of_type<S: State + 'static, R, F: FnOnce(&S) -> R>(
+        &self,
+        f: F,
+    ) -> Option<R> {
+        let build_context = self.to_context()?;
+
+        let arena = build_context.element_tree.borrow();
+
+        let ancestor = self.to_id()?.ancestors(&arena).skip(1).find(|id| {
+            arena
+                .get(*id)
+                .unwrap()
+                .get()
+                .0
+                .to_state()
+                .map(|state| state.as_any().is::<S>())
+                .unwrap_or(false)
+        })?;
+
+        let element = arena.get(ancestor).unwrap().get().clone();
+
+        let state = element.0.to_state()?;
+
+        state.as_any().downcast_ref::<S>().map(f)
+    }
+
+    /// Returns the render object of the nearest ancestor render object element.
+    fn find_ancestor_render_object(&self) -> Option<RenderObject> {
+        let build_context = self.to_context()?;
+
+        let render_object_id =
+            find_ancestor_render_object_id(&build_context.element_tree.borrow(), self.to_id()?)?;
+
+        let render_tree = build_context.render_tree.borrow();
+
+        render_tree
+            .get(render_object_id)
+            .map(|node| node.get().clone())
+    }
+
+    /// Returns the render object owned by this element, the first descendant one for component elements.
+    fn find_render_object(&self) -> Option<RenderObject> {
+        let build_context = self.to_context()?;
+
+        let element = build_context
+            .element_tree
+            .borrow()
+            .get(self.to_id()?)?
+            .get()
+            .clone();
+
+        element.first_render_object(&build_context)
+    }
+
+    /// Returns the size of [`BuildContext::find_render_object`] computed by the last layout.
+    fn size(&self) -> Option<Size> {
+        self.find_render_object()?.size()
+    }
+
+    /// Call `f` with each child element in order.
+    fn visit_children<F: FnMut(&Element)>(&self, mut f: F) {
+        let Some(build_context) = self.to_context() else {
+            return;
+        };
+
+        let Some(id) = self.to_id() else {
+            return;
+        };
+
+        let children = id
+            .children(&build_context.element_tree.borrow())
+            .map(|id| {
+                build_context
+                    .element_tree
+                    .borrow()
+                    .get(id)
+                    .unwrap()
+                    .get()
+                    .clone()
+            })
+            .collect::<Vec<_>>();
+
+        for child in &children {
+            f(child);
+        }
+    }
+
+    /// Get a detached handle which can schedule rebuild of this element later,
+    /// returns `None` if element is not mounted.
+    fn to_rebuild_handle(&self) -> Option<RebuildHandle> {
+        Some(RebuildHandle {
+            id: self.to_id()?,
+            owner: self.to_owner()?,
+        })
+    }
+
+    /// Returns local state kept across builds, initialized by `init` in the first build.
+    ///
+    /// Like all hooks it's stored in the [`Hooks`] slot of its call order, so hooks must be
+    /// called unconditionally and in the same order on every build.
+    fn use_state<T: 'static, F: FnOnce() -> T>(&self, init: F) -> UseState<T> {
+        UseState::new(
+            self.hooks().slot("use_state", init),
+            self.to_rebuild_handle(),
+        )
+    }
+
+    /// Returns a mutable value kept across builds, changing it doesn't rebuild the element.
+    fn use_ref<T: 'static, F: FnOnce() -> T>(&self, init: F) -> Rc<RefCell<T>> {
+        self.hooks().slot("use_ref", init)
+    }
+
+    /// Returns the value computed by `f`, which is called again only when `deps` changed.
+    fn use_memo<D: PartialEq + 'static, T: Clone + 'static, F: FnOnce() -> T>(
+        &self,
+        deps: D,
+        f: F,
+    ) -> T {
+        let memo = self.hooks().slot("use_memo", || None::<(D, T)>);
+
+        let cached = memo
+            .borrow()
+            .as_ref()
+            .filter(|(old, _)| *old == deps)
+            .map(|(_, value)| value.clone());
+
+        cached.unwrap_or_else(|| {
+            let value = f();
+
+            *memo.borrow_mut() = Some((deps, value.clone()));
+
+            value
+        })
+    }
+
+    /// Run `effect` after the build when `deps` changed, including the first build.
+    ///
+    /// Effects run at the end of [`BuildOwner::build_scope`] and of
+    /// [`FrameworkContext::mount_root`], the cleanup returned by the
+    /// previous run is called before the effect runs again and when the element is unmounted.
+    fn use_effect<D, C, F>(&self, deps: D, effect: F)
+    where
+        D: PartialEq + 'static,
+        C: EffectCleanup,
+        F: FnOnce() -> C + 'static,
+    {
+        let slot = self.hooks().slot("use_effect", Effect::default);
+
+        let changed = slot
+            .borrow()
+            .deps
+            .as_ref()
+            .and_then(|old| old.downcast_ref::<D>())
+            .is_none_or(|old| *old != deps);
+
+        if !changed {
+            return;
+        }
+
+        {
+            let mut slot = slot.borrow_mut();
+
+            slot.deps = Some(Box::new(deps));
+            slot.pending = Some(Box::new(move || effect().into_cleanup()));
+        }
+
+        if let (Some(owner), Some(id)) = (self.to_owner(), self.to_id()) {
+            owner.schedule_effects_for(id);
+        }
+    }
+}
 
 pub trait Initializer {
     fn initialize(&self, id: ElementId);
 
     fn to_id(&self) -> Option<ElementId>;
+
+    /// Attach the framework context of the tree this element is mounted in.
+    fn attach_context(&self, build_context: &FrameworkContext);
+
+    fn to_context(&self) -> Option<FrameworkContext>;
+
+    /// Returns the build owner used to schedule rebuilds.
+    fn to_owner(&self) -> Option<BuildOwner>;
+
+    /// Returns the hook slots of this element.
+    fn hooks(&self) -> &Hooks;
+
+    /// Returns the inherited elements this element registered as dependent of.
+    fn dependencies(&self) -> &RefCell<Vec<ElementId>>;
 }
 
 /// Framework call this trait to handle element lifecycle.
@@ -32,18 +268,75 @@ pub trait Lifecycle: Initializer + Debug {
 
     fn rebuild(&self, build_context: &mut FrameworkContext);
 
+    /// Remove child `id` from children records, called when the child is moved to another parent.
+    fn forget_child(&self, id: ElementId);
+
+    /// Register `id` as a dependent of inherited element.
+    fn add_dependent(&self, _id: ElementId) {}
+
+    /// Unregister `id` from the dependents of inherited element.
+    fn remove_dependent(&self, _id: ElementId) {}
+
+    /// Unregister this element from the inherited elements it depends on.
+    ///
+    /// Called before each build, so only the dependencies of the last build are kept.
+    fn clear_dependencies(&self, build_context: &FrameworkContext) {
+        let dependencies = std::mem::take(&mut *self.dependencies().borrow_mut());
+
+        remove_dependent(build_context, self, &dependencies);
+    }
+
+    /// Returns state of stateful element.
+    fn to_state(&self) -> Option<Ref<'_, dyn State>> {
+        None
+    }
+
     /// Mount element into element tree.
     fn mount(&self, build_context: &mut FrameworkContext, parent: Option<ElementId>) {
-        parent.map(|p| {
-            p.append(
-                self.to_id().expect("Call initialize first"),
-                &mut build_context.element_tree.borrow_mut(),
-            )
-        });
+        let id = self.to_id().expect("Call initialize first");
+
+        if let Some(parent) = parent {
+            parent.append(id, &mut build_context.element_tree.borrow_mut());
+        }
 
+        if let Some(key) = self.to_configuration().to_global_key() {
+            build_context.global_keys.register(key, id);
+        }
+
+        self.attach_context(build_context);
+
+        self.first_build(build_context);
+    }
+
+    /// Build element the first time, called once by [`Lifecycle::mount`].
+    fn first_build(&self, build_context: &mut FrameworkContext) {
         self.rebuild(build_context);
     }
 
+    /// Called when an inherited view this element depends on changed.
+    fn did_change_dependencies(&self) {
+        self.mark_needs_build();
+    }
+
+    /// Called when element is moved back into the tree after [`Lifecycle::deactivate`].
+    fn activate(&self) {}
+
+    /// Called when element is removed from the tree, it may be reinserted before the end of build.
+    fn deactivate(&self) {}
+
+    /// Called when element is removed from the tree permanently.
+    fn unmount(&self) {}
+
+    /// Schedule this element to be rebuilt by the next [`BuildOwner::build_scope`].
+    ///
+    /// Calling this method before the element is mounted does nothing,
+    /// mount always builds the element.
+    fn mark_needs_build(&self) {
+        if let (Some(owner), Some(id)) = (self.to_owner(), self.to_id()) {
+            owner.schedule_build_for(id);
+        }
+    }
+
     fn update_child(
         &self,
         build_context: &mut FrameworkContext,
@@ -58,15 +351,13 @@ pub trait Lifecycle: Initializer + Debug {
             return None;
         }
 
-        let configuration = self.to_configuration();
-
         if let Some(child) = child {
+            let configuration = child.to_configuration();
+
             if configuration == new_configuration {
                 // Skip update child element.
                 Some(child.to_id().expect("Call initialize first"))
-            } else if configuration.same_type(&new_configuration)
-                && configuration.to_keypath() == new_configuration.to_keypath()
-            {
+            } else if configuration.can_update(&new_configuration) {
                 // Same element type and path with different configuration.
                 child.update(build_context, new_configuration);
                 Some(child.to_id().expect("Call initialize first"))
@@ -79,8 +370,215 @@ pub trait Lifecycle: Initializer + Debug {
         }
     }
 
+    /// Reconcile `old_children` against `new_configurations`, returns new children ids in order.
+    ///
+    /// Matching children at the top and the bottom of the list are updated in place,
+    /// the middle section reuses old children by [`KeyPath`], so moved children keep their elements
+    /// and states. Old children not reused are deactivated.
+    fn update_children(
+        &self,
+        build_context: &mut FrameworkContext,
+        old_children: Vec<Element>,
+        new_configurations: Vec<View>,
+    ) -> Vec<ElementId> {
+        let mut new_children = Vec::with_capacity(new_configurations.len());
+
+        let mut old_top = 0;
+        let mut new_top = 0;
+        let mut old_bottom = old_children.len();
+        let mut new_bottom = new_configurations.len();
+
+        // Update the top of the list.
+        while old_top < old_bottom && new_top < new_bottom {
+            let old_child = &old_children[old_top];
+            let new_configuration = &new_configurations[new_top];
+
+            if !old_child.to_configuration().can_update(new_configuration) {
+                break;
+            }
+
+            new_children.extend(self.update_child(
+                build_context,
+                Some(old_child.clone()),
+                new_configuration.clone(),
+            ));
+
+            old_top += 1;
+            new_top += 1;
+        }
+
+        // Scan the bottom of the list, updated after the middle section to keep children order.
+        while old_top < old_bottom && new_top < new_bottom {
+            let old_child = &old_children[old_bottom - 1];
+            let new_configuration = &new_configurations[new_bottom - 1];
+
+            if !old_child.to_configuration().can_update(new_configuration) {
+                break;
+            }
+
+            old_bottom -= 1;
+            new_bottom -= 1;
+        }
+
+        // Index keyed old children of the middle section, same key path may appear more than once.
+        let mut keyed_old_children: HashMap<KeyPath, VecDeque<Element>> = HashMap::new();
+
+        for old_child in &old_children[old_top..old_bottom] {
+            match old_child.to_configuration().to_keypath() {
+                Some(key_path) if *key_path != KeyPath::Empty => keyed_old_children
+                    .entry(key_path.clone())
+                    .or_default()
+                    .push_back(old_child.clone()),
+                _ => self.deactive_child(
+                    build_context,
+                    old_child.to_id().expect("Call initialize first"),
+                ),
+            }
+        }
+
+        // Update the middle of the list.
+        while new_top < new_bottom {
+            let new_configuration = new_configurations[new_top].clone();
+
+            let old_child = new_configuration
+                .to_keypath()
+                .and_then(|key_path| keyed_old_children.get_mut(key_path))
+                .and_then(|candidates| {
+                    let index = candidates
+                        .iter()
+                        .position(|c| c.to_configuration().can_update(&new_configuration))?;
+
+                    candidates.remove(index)
+                });
+
+            new_children.extend(self.update_child(build_context, old_child, new_configuration));
+
+            new_top += 1;
+        }
+
+        // Update the bottom of the list.
+        let mut old_index = old_bottom;
+
+        while old_index < old_children.len() && new_top < new_configurations.len() {
+            new_children.extend(self.update_child(
+                build_context,
+                Some(old_children[old_index].clone()),
+                new_configurations[new_top].clone(),
+            ));
+
+            old_index += 1;
+            new_top += 1;
+        }
+
+        // Deactivate old keyed children not reused, skip children retaken by global key.
+        for old_child in keyed_old_children.into_values().flatten() {
+            let id = old_child.to_id().expect("Call initialize first");
+
+            if !new_children.contains(&id) {
+                self.deactive_child(build_context, id);
+            }
+        }
+
+        new_children
+    }
+
     fn deactive_child(&self, build_context: &mut FrameworkContext, id: ElementId) {
-        id.remove(&mut build_context.element_tree.borrow_mut());
+        let element = build_context
+            .element_tree
+            .borrow()
+            .get(id)
+            .map(|node| node.get().clone());
+
+        let Some(element) = element else {
+            return;
+        };
+
+        visit_subtree(build_context, id, |element| {
+            // Keep the dependencies, the element rebuilds if it is activated again.
+            remove_dependent(
+                build_context,
+                &*element.0,
+                &element.0.dependencies().borrow(),
+            );
+
+            element.0.deactivate();
+        });
+
+        // Keep the detached subtree until the end of build, globally keyed elements
+        // of it may be reused by another parent.
+        detach_render_object(build_context, &element);
+
+        id.detach(&mut build_context.element_tree.borrow_mut());
+
+        build_context.build_owner.deactivate(id);
+    }
+
+    /// Move element mounted with the same global key as `configuration` under this element.
+    fn retake_global_element(
+        &self,
+        build_context: &mut FrameworkContext,
+        configuration: &View,
+    ) -> Option<ElementId> {
+        let id = build_context
+            .global_keys
+            .get(configuration.to_global_key()?)?;
+
+        let element = build_context
+            .element_tree
+            .borrow()
+            .get(id)
+            .map(|node| node.get().clone())?;
+
+        if !element.to_configuration().same_type(configuration) {
+            return None;
+        }
+
+        let old_parent = id.parent(&build_context.element_tree.borrow());
+
+        if let Some(old_parent) = old_parent {
+            let old_parent = build_context
+                .element_tree
+                .borrow()
+                .get(old_parent)
+                .unwrap()
+                .get()
+                .clone();
+
+            old_parent.0.forget_child(id);
+        }
+
+        build_context.build_owner.reactivate(id);
+
+        visit_subtree(build_context, id, |element| {
+            if !element.0.dependencies().borrow().is_empty() {
+                element.0.clear_dependencies(build_context);
+                element.mark_needs_build();
+            }
+
+            element.0.activate();
+        });
+
+        let render_object_id = detach_render_object(build_context, &element);
+
+        {
+            let mut arena = build_context.element_tree.borrow_mut();
+
+            id.detach(&mut arena);
+
+            self.to_id()
+                .expect("Call initialize first")
+                .append(id, &mut arena);
+        }
+
+        if let Some(render_object_id) = render_object_id {
+            insert_render_object(build_context, id, render_object_id);
+        }
+
+        if element.to_configuration() != *configuration {
+            element.update(build_context, configuration.clone());
+        }
+
+        Some(id)
     }
 
     fn inflate_view(
@@ -88,6 +586,10 @@ pub trait Lifecycle: Initializer + Debug {
         build_context: &mut FrameworkContext,
         configuration: View,
     ) -> Option<NodeId> {
+        if let Some(id) = self.retake_global_element(build_context, &configuration) {
+            return Some(id);
+        }
+
         let child_id = configuration.into_element(&mut build_context.element_tree.borrow_mut());
 
         if let Some(child_id) = child_id {
@@ -110,6 +612,157 @@ pub trait Lifecycle: Initializer + Debug {
     }
 }
 
+/// Find the nearest inherited element of `id` ancestors which configuration type is `T`, `id` self is skipped.
+pub fn find_ancestor_inherited<T: 'static>(
+    arena: &Arena<Element>,
+    id: ElementId,
+) -> Option<Element> {
+    id.ancestors(arena)
+        .skip(1)
+        .map(|id| arena.get(id).unwrap().get())
+        .find(|element| match element.to_configuration() {
+            View::Inherited(config) => config.view.borrow().to_any().is::<T>(),
+            _ => false,
+        })
+        .cloned()
+}
+
+/// Find the nearest render object of `id` ancestors, `id` self is skipped.
+pub fn find_ancestor_render_object_id(
+    arena: &Arena<Element>,
+    id: ElementId,
+) -> Option<RenderObjectId> {
+    id.ancestors(arena)
+        .skip(1)
+        .find_map(|id| arena.get(id).unwrap().get().to_render_object_id())
+}
+
+/// Insert `render_object_id` of element `id` into the render object of the nearest
+/// ancestor render object element, after the render objects of the elements before `id`.
+pub(crate) fn insert_render_object(
+    build_context: &FrameworkContext,
+    id: ElementId,
+    render_object_id: RenderObjectId,
+) {
+    let element_tree = build_context.element_tree.borrow();
+
+    let Some(ancestor) = id
+        .ancestors(&element_tree)
+        .skip(1)
+        .find(|id| element_tree[*id].get().to_render_object_id().is_some())
+    else {
+        return;
+    };
+
+    let parent = element_tree[ancestor].get().to_render_object_id().unwrap();
+
+    let mut siblings = vec![];
+
+    collect_render_object_ids(&element_tree, ancestor, &mut siblings);
+
+    let mut render_tree = build_context.render_tree.borrow_mut();
+
+    let previous = siblings
+        .into_iter()
+        .take_while(|sibling| *sibling != render_object_id)
+        .filter(|sibling| sibling.parent(&render_tree) == Some(parent))
+        .last();
+
+    match previous {
+        Some(previous) => previous.insert_after(render_object_id, &mut render_tree),
+        None => parent.prepend(render_object_id, &mut render_tree),
+    }
+
+    mark_parent_needs_layout(&render_tree, render_object_id);
+}
+
+/// Collect the render objects of `id` descendants in element order, without
+/// descending into render object elements.
+fn collect_render_object_ids(
+    arena: &Arena<Element>,
+    id: ElementId,
+    render_object_ids: &mut Vec<RenderObjectId>,
+) {
+    for child in id.children(arena) {
+        match arena[child].get().to_render_object_id() {
+            Some(render_object_id) => render_object_ids.push(render_object_id),
+            None => collect_render_object_ids(arena, child, render_object_ids),
+        }
+    }
+}
+
+/// Unregister `element` from the inherited elements `dependencies` still in the tree.
+fn remove_dependent<E: Lifecycle + ?Sized>(
+    build_context: &FrameworkContext,
+    element: &E,
+    dependencies: &[ElementId],
+) {
+    let Some(id) = element.to_id() else {
+        return;
+    };
+
+    let inherited = {
+        let arena = build_context.element_tree.borrow();
+
+        dependencies
+            .iter()
+            .filter_map(|id| arena.get(*id).map(|node| node.get().clone()))
+            .collect::<Vec<_>>()
+    };
+
+    for inherited in inherited {
+        inherited.0.remove_dependent(id);
+    }
+}
+
+/// Call `f` with each element of the subtree rooted at `id`, parent first.
+pub fn visit_subtree<F: FnMut(&Element)>(
+    build_context: &FrameworkContext,
+    id: ElementId,
+    mut f: F,
+) {
+    let elements = id
+        .descendants(&build_context.element_tree.borrow())
+        .map(|id| {
+            build_context
+                .element_tree
+                .borrow()
+                .get(id)
+                .unwrap()
+                .get()
+                .clone()
+        })
+        .collect::<Vec<_>>();
+
+    for element in &elements {
+        f(element);
+    }
+}
+
+/// Detach the first render object of `element` from render tree, returns the detached render object id.
+fn detach_render_object(
+    build_context: &FrameworkContext,
+    element: &Element,
+) -> Option<RenderObjectId> {
+    let render_object_id = element
+        .search_first_render_object_element_id(build_context)
+        .and_then(|id| {
+            build_context
+                .element_tree
+                .borrow()
+                .get(id)
+                .unwrap()
+                .get()
+                .to_render_object_id()
+        })?;
+
+    mark_parent_needs_layout(&build_context.render_tree.borrow(), render_object_id);
+
+    render_object_id.detach(&mut build_context.render_tree.borrow_mut());
+
+    Some(render_object_id)
+}
+
 /// Element wrapper
 #[derive(Debug, Clone)]
 pub struct Element(pub Rc<dyn Lifecycle + 'static>);
@@ -134,6 +787,18 @@ impl Element {
         self.0.update(build_context, configuration);
     }
 
+    pub fn rebuild(&self, build_context: &mut FrameworkContext) {
+        self.0.rebuild(build_context)
+    }
+
+    pub fn mark_needs_build(&self) {
+        self.0.mark_needs_build()
+    }
+
+    pub fn to_configuration(&self) -> View {
+        self.0.to_configuration()
+    }
+
     pub fn initialize(&self, id: ElementId) {
         self.0.initialize(id);
     }
@@ -181,8 +846,24 @@ impl Element {
 #[derive(Debug)]
 pub struct ElementNode<T: ?Sized, C> {
     pub id: RefCell<Option<ElementId>>,
+    pub context: RefCell<Option<WeakFrameworkContext>>,
     pub config: RefCell<Configuration<T>>,
     pub content: RefCell<C>,
+    pub hooks: Hooks,
+    pub dependencies: RefCell<Vec<ElementId>>,
+}
+
+impl<T: ?Sized, C> ElementNode<T, C> {
+    pub fn new_node(config: Configuration<T>, content: C) -> Self {
+        Self {
+            id: RefCell::new(None),
+            context: RefCell::new(None),
+            config: RefCell::new(config),
+            content: RefCell::new(content),
+            hooks: Hooks::default(),
+            dependencies: RefCell::new(vec![]),
+        }
+    }
 }
 
 impl<T: ?Sized, C> Initializer for ElementNode<T, C> {
@@ -191,6 +872,50 @@ impl<T: ?Sized, C> Initializer for ElementNode<T, C> {
     }
 
     fn to_id(&self) -> Option<ElementId> {
-        self.id.borrow().clone()
+        *self.id.borrow()
+    }
+
+    fn attach_context(&self, build_context: &FrameworkContext) {
+        *self.context.borrow_mut() = Some(build_context.downgrade());
+    }
+
+    fn to_context(&self) -> Option<FrameworkContext> {
+        self.context.borrow().as_ref()?.upgrade()
+    }
+
+    fn to_owner(&self) -> Option<BuildOwner> {
+        Some(self.context.borrow().as_ref()?.build_owner.clone())
+    }
+
+    fn hooks(&self) -> &Hooks {
+        &self.hooks
+    }
+
+    fn dependencies(&self) -> &RefCell<Vec<ElementId>> {
+        &self.dependencies
+    }
+}
+
+/// Cloneable handle to schedule element rebuild outside of the build phase,
+/// e.g. from timers or stream subscriptions held by a [`State`](crate::view::State).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebuildHandle {
+    id: ElementId,
+    owner: BuildOwner,
+}
+
+impl RebuildHandle {
+    pub fn to_id(&self) -> ElementId {
+        self.id
+    }
+
+    pub fn mark_needs_build(&self) {
+        self.owner.schedule_build_for(self.id);
+    }
+
+    /// Run `f` and schedule the element rebuild.
+    pub fn set_state<F: FnOnce()>(&self, f: F) {
+        f();
+        self.mark_needs_build();
     }
 }
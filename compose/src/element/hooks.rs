@@ -0,0 +1,302 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell},
+    fmt::Debug,
+    rc::Rc,
+};
+
+use super::RebuildHandle;
+
+/// Value returned by a [`BuildContext::use_effect`](super::BuildContext::use_effect) effect,
+/// `()` or a closure called before the effect runs again and when the element is unmounted.
+pub trait EffectCleanup {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>>;
+}
+
+impl EffectCleanup for () {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        None
+    }
+}
+
+impl<F: FnOnce() + 'static> EffectCleanup for F {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        Some(Box::new(self))
+    }
+}
+
+type EffectFn = dyn FnOnce() -> Option<Box<dyn FnOnce()>>;
+
+/// Slot value of [`BuildContext::use_effect`](super::BuildContext::use_effect).
+#[derive(Default)]
+pub(crate) struct Effect {
+    pub(crate) deps: Option<Box<dyn Any>>,
+    /// Effect to run after the build, set when the dependencies changed.
+    pub(crate) pending: Option<Box<EffectFn>>,
+    cleanup: Option<Box<dyn FnOnce()>>,
+}
+
+/// Local state of [`BuildContext::use_state`](super::BuildContext::use_state),
+/// setting it schedules the element to rebuild.
+pub struct UseState<T> {
+    value: Rc<RefCell<T>>,
+    handle: Option<RebuildHandle>,
+}
+
+impl<T> Clone for UseState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            handle: self.handle.clone(),
+        }
+    }
+}
+
+impl<T> PartialEq for UseState<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+impl<T: Debug> Debug for UseState<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("UseState")
+            .field(&self.value.borrow())
+            .finish()
+    }
+}
+
+impl<T> UseState<T> {
+    pub(crate) fn new(value: Rc<RefCell<T>>, handle: Option<RebuildHandle>) -> Self {
+        Self { value, handle }
+    }
+
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.value.borrow().clone()
+    }
+
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.value.borrow()
+    }
+
+    /// Replace the value and schedule the element to rebuild.
+    pub fn set(&self, value: T) {
+        self.update(|current| *current = value);
+    }
+
+    /// Mutate the value with `f` and schedule the element to rebuild.
+    pub fn update<F: FnOnce(&mut T)>(&self, f: F) {
+        f(&mut self.value.borrow_mut());
+
+        if let Some(handle) = &self.handle {
+            handle.mark_needs_build();
+        }
+    }
+}
+
+struct Slot {
+    hook: &'static str,
+    type_name: &'static str,
+    /// `Rc<RefCell<T>>` of the hook value type `T`.
+    value: Box<dyn Any>,
+}
+
+#[derive(Default)]
+struct HookList {
+    slots: Vec<Slot>,
+    /// Index of the next slot while building.
+    cursor: usize,
+    building: bool,
+    /// The element built once, later builds must call the same hooks.
+    built: bool,
+}
+
+/// Hook values of an element in call order, see [`BuildContext::use_state`](super::BuildContext::use_state).
+///
+/// Hooks are matched with slots by the order they are called in `build`, so they must be called
+/// unconditionally. Debug builds panic when the hooks of a build differ from the previous build.
+#[derive(Default)]
+pub struct Hooks(RefCell<HookList>);
+
+impl Debug for Hooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.0
+                    .borrow()
+                    .slots
+                    .iter()
+                    .map(|slot| format!("{}::<{}>", slot.hook, slot.type_name)),
+            )
+            .finish()
+    }
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().slots.is_empty()
+    }
+
+    pub(crate) fn begin_build(&self) {
+        let mut list = self.0.borrow_mut();
+
+        list.cursor = 0;
+        list.building = true;
+    }
+
+    pub(crate) fn end_build(&self) {
+        let removed = {
+            let mut list = self.0.borrow_mut();
+
+            list.building = false;
+
+            if cfg!(debug_assertions) && list.built && list.cursor < list.slots.len() {
+                panic!(
+                    "Fewer hooks than in the previous build: {} called, {} in the previous build, \
+                     hooks must be called unconditionally and in the same order on every build",
+                    list.cursor,
+                    list.slots.len()
+                );
+            }
+
+            list.built = true;
+
+            let cursor = list.cursor;
+
+            list.slots.split_off(cursor)
+        };
+
+        for slot in removed {
+            dispose_slot(slot);
+        }
+    }
+
+    /// Returns the value of the next slot, created by `init` if the slot is new.
+    pub(crate) fn slot<T: 'static, F: FnOnce() -> T>(
+        &self,
+        hook: &'static str,
+        init: F,
+    ) -> Rc<RefCell<T>> {
+        let type_name = std::any::type_name::<T>();
+
+        let (index, existing) = {
+            let mut list = self.0.borrow_mut();
+
+            assert!(
+                list.building,
+                "{} must be called while the element is building",
+                hook
+            );
+
+            let index = list.cursor;
+
+            list.cursor += 1;
+
+            let existing = match list.slots.get(index) {
+                Some(slot) if slot.hook == hook && slot.value.is::<Rc<RefCell<T>>>() => {
+                    slot.value.downcast_ref::<Rc<RefCell<T>>>().cloned()
+                }
+                Some(slot) if cfg!(debug_assertions) => panic!(
+                    "Hook order changed: hook #{} is {}::<{}>, it was {}::<{}> in the previous build, \
+                     hooks must be called unconditionally and in the same order on every build",
+                    index, hook, type_name, slot.hook, slot.type_name
+                ),
+                None if cfg!(debug_assertions) && list.built => panic!(
+                    "More hooks than in the previous build: hook #{} is {}::<{}>, \
+                     hooks must be called unconditionally and in the same order on every build",
+                    index, hook, type_name
+                ),
+                _ => None,
+            };
+
+            (index, existing)
+        };
+
+        if let Some(value) = existing {
+            return value;
+        }
+
+        // Initialize without borrowing the slots, `init` is user code.
+        let value = Rc::new(RefCell::new(init()));
+
+        let slot = Slot {
+            hook,
+            type_name,
+            value: Box::new(value.clone()),
+        };
+
+        let replaced = {
+            let mut list = self.0.borrow_mut();
+
+            if index < list.slots.len() {
+                Some(std::mem::replace(&mut list.slots[index], slot))
+            } else {
+                list.slots.push(slot);
+
+                None
+            }
+        };
+
+        if let Some(replaced) = replaced {
+            dispose_slot(replaced);
+        }
+
+        value
+    }
+
+    fn effects(&self) -> Vec<Rc<RefCell<Effect>>> {
+        self.0
+            .borrow()
+            .slots
+            .iter()
+            .filter_map(|slot| slot.value.downcast_ref::<Rc<RefCell<Effect>>>().cloned())
+            .collect()
+    }
+
+    /// Run the effects which dependencies changed in the last build, each after the cleanup
+    /// of its previous run.
+    pub(crate) fn run_effects(&self) {
+        for effect in self.effects() {
+            let Some(pending) = effect.borrow_mut().pending.take() else {
+                continue;
+            };
+
+            let cleanup = effect.borrow_mut().cleanup.take();
+
+            if let Some(cleanup) = cleanup {
+                cleanup();
+            }
+
+            let cleanup = pending();
+
+            effect.borrow_mut().cleanup = cleanup;
+        }
+    }
+
+    /// Drop all slots, running the effect cleanups, called when the element is unmounted.
+    pub(crate) fn dispose(&self) {
+        let slots = std::mem::take(&mut self.0.borrow_mut().slots);
+
+        for slot in slots {
+            dispose_slot(slot);
+        }
+    }
+}
+
+fn dispose_slot(slot: Slot) {
+    if let Some(effect) = slot.value.downcast_ref::<Rc<RefCell<Effect>>>() {
+        let cleanup = {
+            let mut effect = effect.borrow_mut();
+
+            effect.pending = None;
+            effect.cleanup.take()
+        };
+
+        if let Some(cleanup) = cleanup {
+            cleanup();
+        }
+    }
+}
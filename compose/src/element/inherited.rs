@@ -0,0 +1,138 @@
+use indextree::Arena;
+
+use crate::{
+    framework::FrameworkContext,
+    view::{Configuration, InheritedConfiguration, RenderObjectId, View},
+};
+
+use super::{
+    component::ComponentElement, BuildContext, Element, ElementId, ElementNode, Lifecycle,
+};
+
+#[derive(Debug)]
+pub struct InheritedElementContent {
+    pub child: Option<ElementId>,
+    /// Elements registered by [`BuildContext::depend_on_inherited`].
+    pub dependents: Vec<ElementId>,
+}
+
+pub type InheritedElement = ElementNode<dyn InheritedConfiguration, InheritedElementContent>;
+
+impl InheritedElement {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        arena: &mut Arena<Element>,
+        config: Configuration<dyn InheritedConfiguration>,
+    ) -> ElementId {
+        let id = arena.new_node(
+            InheritedElement::new_node(
+                config,
+                InheritedElementContent {
+                    child: None,
+                    dependents: vec![],
+                },
+            )
+            .into(),
+        );
+
+        arena.get_mut(id).unwrap().get_mut().initialize(id);
+
+        id
+    }
+
+    fn notify_dependents(&self, build_context: &FrameworkContext) {
+        let dependents = {
+            let arena = build_context.element_tree.borrow();
+
+            let mut content = self.content.borrow_mut();
+
+            // Drop unmounted dependents.
+            content.dependents.retain(|id| arena.get(*id).is_some());
+
+            content
+                .dependents
+                .iter()
+                .map(|id| arena.get(*id).unwrap().get().clone())
+                .collect::<Vec<_>>()
+        };
+
+        for dependent in dependents {
+            dependent.0.did_change_dependencies();
+        }
+    }
+}
+
+impl Lifecycle for InheritedElement {
+    fn search_first_render_object_element_id(
+        &self,
+        build_context: &FrameworkContext,
+    ) -> Option<ElementId> {
+        self.composite_first_render_object_id(build_context)
+    }
+    fn to_render_object_id(&self) -> Option<RenderObjectId> {
+        None
+    }
+    fn rebuild(&self, build_context: &mut FrameworkContext) {
+        self.composite_rebuild(build_context);
+    }
+
+    fn add_dependent(&self, id: ElementId) {
+        let mut content = self.content.borrow_mut();
+
+        if !content.dependents.contains(&id) {
+            content.dependents.push(id);
+        }
+    }
+
+    fn remove_dependent(&self, id: ElementId) {
+        self.content.borrow_mut().dependents.retain(|e| *e != id);
+    }
+
+    fn forget_child(&self, id: ElementId) {
+        if self.child() == Some(id) {
+            self.set_child(None);
+        }
+    }
+
+    fn to_configuration(&self) -> crate::view::View {
+        View::Inherited(self.config.borrow().clone())
+    }
+
+    fn update(&self, build_context: &mut FrameworkContext, configuration: crate::view::View) {
+        let old_config = if let View::Inherited(config) = configuration {
+            std::mem::replace(&mut *self.config.borrow_mut(), config)
+        } else {
+            panic!("Update configuration type mismatch, expect Inherited configuration");
+        };
+
+        let should_notify = self
+            .config
+            .borrow()
+            .view
+            .borrow()
+            .framework_update_should_notify(old_config.view.borrow().to_any());
+
+        if should_notify {
+            self.notify_dependents(build_context);
+        }
+
+        // The child view is part of the configuration.
+        self.rebuild(build_context);
+    }
+}
+
+impl ComponentElement for InheritedElement {
+    fn build(&self) -> crate::view::View {
+        self.config.borrow().view.borrow().framework_child()
+    }
+
+    fn set_child(&self, new: Option<ElementId>) {
+        self.content.borrow_mut().child = new;
+    }
+
+    fn child(&self) -> Option<ElementId> {
+        self.content.borrow().child
+    }
+}
+
+impl BuildContext for InheritedElement {}
@@ -10,4 +10,10 @@ pub use stateful::*;
 mod stateless;
 pub use stateless::*;
 
+mod inherited;
+pub use inherited::*;
+
 mod component;
+
+mod hooks;
+pub use hooks::*;
@@ -1,13 +1,14 @@
-use std::cell::RefCell;
-
 use indextree::Arena;
 
 use crate::{
     framework::FrameworkContext,
+    render::RenderObjectUpdate,
     view::{Configuration, RenderObject, RenderObjectConfiguration, RenderObjectId, View},
 };
 
-use super::{BuildContext, Element, ElementId, ElementNode, Initializer, Lifecycle};
+use super::{
+    insert_render_object, BuildContext, Element, ElementId, ElementNode, Initializer, Lifecycle,
+};
 
 #[derive(Debug)]
 pub struct RenderObjectElementContent {
@@ -19,19 +20,19 @@ pub type RenderObjectElement =
     ElementNode<dyn RenderObjectConfiguration, RenderObjectElementContent>;
 
 impl RenderObjectElement {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         arena: &mut Arena<Element>,
         config: Configuration<dyn RenderObjectConfiguration>,
     ) -> ElementId {
         let id = arena.new_node(
-            RenderObjectElement {
-                id: RefCell::new(None),
-                config: RefCell::new(config),
-                content: RefCell::new(RenderObjectElementContent {
+            RenderObjectElement::new_node(
+                config,
+                RenderObjectElementContent {
                     render_object_id: None,
                     children: vec![],
-                }),
-            }
+                },
+            )
             .into(),
         );
 
@@ -51,40 +52,100 @@ impl RenderObjectElement {
             .new_node(render_object.clone());
 
         render_object.initialize(render_object_id);
+        render_object.0.borrow_mut().element = self.to_id();
+        render_object.attach(build_context.pipeline_owner.clone());
 
         self.content.borrow_mut().render_object_id = Some(render_object_id);
 
-        let ancestor_render_object_id =
-            self.find_ancestor_render_object_element(&mut build_context.element_tree.borrow_mut());
+        insert_render_object(
+            build_context,
+            self.to_id().expect("Call mount method first"),
+            render_object_id,
+        );
+    }
+
+    /// Push the current configuration into the existing render object and flag it dirty
+    /// as reported by the configuration.
+    fn update_render_object(&self, build_context: &FrameworkContext) {
+        let Some(render_object_id) = self.to_render_object_id() else {
+            return;
+        };
 
-        if let Some(ancestor_render_object_id) = ancestor_render_object_id {
-            ancestor_render_object_id.append(
-                render_object_id,
-                &mut build_context.render_tree.borrow_mut(),
-            );
+        let render_object = build_context
+            .render_tree
+            .borrow()
+            .get(render_object_id)
+            .map(|node| node.get().clone());
+
+        if let Some(render_object) = render_object {
+            let update = self
+                .config
+                .borrow()
+                .view
+                .borrow()
+                .framework_update_render_object(render_object.0.borrow_mut().lifecycle.as_mut());
+
+            match update {
+                RenderObjectUpdate::None => {}
+                RenderObjectUpdate::Paint => render_object.mark_needs_paint(),
+                RenderObjectUpdate::Layout => render_object.mark_needs_layout(),
+            }
         }
     }
 
-    fn find_ancestor_render_object_element(
-        &self,
-        arena: &mut Arena<Element>,
-    ) -> Option<RenderObjectId> {
-        let element_id = self.to_id().expect("Call mount method first");
-
-        let mut ancestors = element_id.ancestors(arena);
+    /// Move children element and their render objects to match the order of `children`.
+    fn sync_children_order(&self, build_context: &mut FrameworkContext, children: &[ElementId]) {
+        let id = self.to_id().expect("Call initialize first");
 
-        // Skip self
-        ancestors.next();
+        {
+            let mut arena = build_context.element_tree.borrow_mut();
 
-        for id in ancestors {
-            let render_object_id = arena.get(id).unwrap().get().to_render_object_id();
+            for child in children {
+                child.detach(&mut arena);
+                id.append(*child, &mut arena);
+            }
+        }
 
-            if render_object_id.is_some() {
-                return render_object_id;
+        let render_object_id = self.to_render_object_id().expect("Call mount method first");
+
+        let old_order = render_object_id
+            .children(&build_context.render_tree.borrow())
+            .collect::<Vec<_>>();
+
+        for child in children {
+            let element = build_context
+                .element_tree
+                .borrow()
+                .get(*child)
+                .unwrap()
+                .get()
+                .clone();
+
+            let child_render_object_id = element
+                .search_first_render_object_element_id(build_context)
+                .and_then(|id| {
+                    build_context
+                        .element_tree
+                        .borrow()
+                        .get(id)
+                        .unwrap()
+                        .get()
+                        .to_render_object_id()
+                });
+
+            if let Some(child_render_object_id) = child_render_object_id {
+                let mut render_tree = build_context.render_tree.borrow_mut();
+
+                child_render_object_id.detach(&mut render_tree);
+                render_object_id.append(child_render_object_id, &mut render_tree);
             }
         }
 
-        None
+        let render_tree = build_context.render_tree.borrow();
+
+        if !render_object_id.children(&render_tree).eq(old_order) {
+            render_tree[render_object_id].get().mark_needs_layout();
+        }
     }
 }
 
@@ -96,46 +157,76 @@ impl Lifecycle for RenderObjectElement {
         self.to_id()
     }
     fn to_render_object_id(&self) -> Option<RenderObjectId> {
-        self.content.borrow().render_object_id.clone()
+        self.content.borrow().render_object_id
     }
     fn rebuild(&self, build_context: &mut FrameworkContext) {
-        let render_object = self
+        build_context
+            .build_owner
+            .clean(self.to_id().expect("Call initialize first"));
+
+        let old_children = self
+            .content
+            .borrow()
+            .children
+            .iter()
+            .filter_map(|id| {
+                build_context
+                    .element_tree
+                    .borrow()
+                    .get(*id)
+                    .map(|node| node.get().clone())
+            })
+            .collect::<Vec<_>>();
+
+        let configs = self
             .config
             .borrow()
             .view
             .borrow()
-            .framework_create_render_object();
+            .framework_render_object_children();
 
-        self.attach_render_object(build_context, render_object);
+        let children = self.update_children(build_context, old_children, configs);
 
-        let configs = self
+        self.sync_children_order(build_context, &children);
+
+        self.content.borrow_mut().children = children;
+    }
+
+    fn first_build(&self, build_context: &mut FrameworkContext) {
+        let render_object = self
             .config
             .borrow()
             .view
             .borrow()
-            .framework_render_object_children();
+            .framework_create_render_object();
 
-        let mut children = vec![];
+        self.attach_render_object(build_context, render_object);
 
-        for child in configs {
-            if let Some(id) = self.inflate_view(build_context, child) {
-                children.push(id);
-            }
-        }
+        self.rebuild(build_context);
+    }
 
-        self.content.borrow_mut().children = children;
+    fn forget_child(&self, id: ElementId) {
+        self.content
+            .borrow_mut()
+            .children
+            .retain(|child| *child != id);
     }
 
     fn to_configuration(&self) -> crate::view::View {
         View::RenderObject(self.config.borrow().clone())
     }
 
-    fn update(&self, _build_context: &mut FrameworkContext, configuration: crate::view::View) {
+    fn update(&self, build_context: &mut FrameworkContext, configuration: crate::view::View) {
         if let View::RenderObject(config) = configuration {
             *self.config.borrow_mut() = config
         } else {
             panic!("Update configuration type mismatch, expect RenderObject configuration");
         }
+
+        self.update_render_object(build_context);
+
+        // Reconcile children against the new configuration.
+        self.rebuild(build_context);
     }
 }
 
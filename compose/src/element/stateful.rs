@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::Ref;
 
 use indextree::Arena;
 
@@ -20,6 +20,7 @@ pub struct StatefulElementContent {
 pub type StatefulElement = ElementNode<dyn StatefulConfiguration, StatefulElementContent>;
 
 impl StatefulElement {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         arena: &mut Arena<Element>,
         config: Configuration<dyn StatefulConfiguration>,
@@ -27,14 +28,13 @@ impl StatefulElement {
         let state = config.view.borrow().framework_create_state();
 
         let id = arena.new_node(
-            StatefulElement {
-                id: RefCell::new(None),
-                config: RefCell::new(config),
-                content: RefCell::new(StatefulElementContent {
+            StatefulElement::new_node(
+                config,
+                StatefulElementContent {
                     child: None,
                     state: Some(state),
-                }),
-            }
+                },
+            )
             .into(),
         );
 
@@ -42,6 +42,23 @@ impl StatefulElement {
 
         id
     }
+
+    /// Call `f` with the state taken out of this element, so `f` can borrow the element freely.
+    fn with_state_mut<F: FnOnce(&mut dyn State)>(&self, f: F) {
+        let state = self.content.borrow_mut().state.take();
+
+        if let Some(mut state) = state {
+            f(state.as_mut());
+
+            self.content.borrow_mut().state = Some(state);
+        }
+    }
+
+    /// Run `f` which mutates the state, then schedule this element to rebuild.
+    pub fn set_state<F: FnOnce()>(&self, f: F) {
+        f();
+        self.mark_needs_build();
+    }
 }
 
 impl Lifecycle for StatefulElement {
@@ -58,16 +75,57 @@ impl Lifecycle for StatefulElement {
         self.composite_rebuild(build_context);
     }
 
+    fn first_build(&self, build_context: &mut FrameworkContext) {
+        self.with_state_mut(|state| {
+            state.framework_init_state(self);
+            state.framework_did_change_dependencies(self);
+        });
+
+        self.rebuild(build_context);
+    }
+
+    fn did_change_dependencies(&self) {
+        self.with_state_mut(|state| state.framework_did_change_dependencies(self));
+
+        self.mark_needs_build();
+    }
+
+    fn activate(&self) {
+        self.with_state_mut(|state| state.framework_activate(self));
+    }
+
+    fn deactivate(&self) {
+        self.with_state_mut(|state| state.framework_deactivate(self));
+    }
+
+    fn unmount(&self) {
+        self.with_state_mut(|state| state.framework_dispose(self));
+    }
+
+    fn to_state(&self) -> Option<Ref<'_, dyn State>> {
+        Ref::filter_map(self.content.borrow(), |content| content.state.as_deref()).ok()
+    }
+
+    fn forget_child(&self, id: ElementId) {
+        if self.child() == Some(id) {
+            self.set_child(None);
+        }
+    }
+
     fn to_configuration(&self) -> crate::view::View {
         View::Stateful(self.config.borrow().clone())
     }
 
-    fn update(&self, _build_context: &mut FrameworkContext, configuration: crate::view::View) {
-        if let View::Stateful(config) = configuration {
-            *self.config.borrow_mut() = config
+    fn update(&self, build_context: &mut FrameworkContext, configuration: crate::view::View) {
+        let old_config = if let View::Stateful(config) = configuration {
+            std::mem::replace(&mut *self.config.borrow_mut(), config)
         } else {
             panic!("Update configuration type mismatch, expect Stateful configuration");
-        }
+        };
+
+        self.with_state_mut(|state| state.framework_did_update_config(self, &old_config));
+
+        self.rebuild(build_context);
     }
 }
 
@@ -86,7 +144,7 @@ impl ComponentElement for StatefulElement {
     }
 
     fn child(&self) -> Option<ElementId> {
-        self.content.borrow().child.clone()
+        self.content.borrow().child
     }
 }
 
@@ -1,5 +1,3 @@
-use std::cell::RefCell;
-
 use indextree::Arena;
 
 use crate::{
@@ -14,18 +12,12 @@ use super::{
 pub type StatelessElement = ElementNode<dyn StatelessConfiguration, Option<ElementId>>;
 
 impl StatelessElement {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(
         arena: &mut Arena<Element>,
         config: Configuration<dyn StatelessConfiguration>,
     ) -> ElementId {
-        let id = arena.new_node(
-            StatelessElement {
-                id: RefCell::new(None),
-                config: RefCell::new(config),
-                content: RefCell::new(None),
-            }
-            .into(),
-        );
+        let id = arena.new_node(StatelessElement::new_node(config, None).into());
 
         arena.get_mut(id).unwrap().get_mut().initialize(id);
 
@@ -47,16 +39,24 @@ impl Lifecycle for StatelessElement {
         self.composite_rebuild(build_context);
     }
 
+    fn forget_child(&self, id: ElementId) {
+        if self.child() == Some(id) {
+            self.set_child(None);
+        }
+    }
+
     fn to_configuration(&self) -> crate::view::View {
         View::Stateless(self.config.borrow().clone())
     }
 
-    fn update(&self, _build_context: &mut FrameworkContext, configuration: crate::view::View) {
+    fn update(&self, build_context: &mut FrameworkContext, configuration: crate::view::View) {
         if let View::Stateless(config) = configuration {
             *self.config.borrow_mut() = config
         } else {
             panic!("Update configuration type mismatch, expect Stateless configuration");
         }
+
+        self.rebuild(build_context);
     }
 }
 
@@ -75,7 +75,7 @@ impl ComponentElement for StatelessElement {
     }
 
     fn child(&self) -> Option<ElementId> {
-        self.content.borrow().clone()
+        *self.content.borrow()
     }
 }
 
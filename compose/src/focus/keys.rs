@@ -0,0 +1,137 @@
+use std::{ops::BitOr, time::Duration};
+
+/// Meaning of a key in the current keyboard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogicalKey {
+    /// Key producing a character, the character without modifiers, e.g. `a` for `Shift + A`.
+    Character(char),
+    Enter,
+    Tab,
+    Space,
+    Backspace,
+    Delete,
+    Escape,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Shift,
+    Control,
+    Alt,
+    Meta,
+    /// Function key, `F(1)` is F1.
+    F(u8),
+    /// Key without a logical meaning here, with its platform code.
+    Unidentified(u32),
+}
+
+/// Location of a key on the keyboard, independent of the layout, as a USB HID usage code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PhysicalKey(pub u32);
+
+impl PhysicalKey {
+    pub const UNIDENTIFIED: PhysicalKey = PhysicalKey(0);
+    pub const KEY_A: PhysicalKey = PhysicalKey(0x04);
+    pub const ENTER: PhysicalKey = PhysicalKey(0x28);
+    pub const ESCAPE: PhysicalKey = PhysicalKey(0x29);
+    pub const BACKSPACE: PhysicalKey = PhysicalKey(0x2a);
+    pub const TAB: PhysicalKey = PhysicalKey(0x2b);
+    pub const SPACE: PhysicalKey = PhysicalKey(0x2c);
+}
+
+/// Bit set of pressed modifier keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers(pub u32);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const SHIFT: Modifiers = Modifiers(1);
+    pub const CONTROL: Modifiers = Modifiers(2);
+    pub const ALT: Modifiers = Modifiers(4);
+    /// Command key on macOS, Windows key elsewhere.
+    pub const META: Modifiers = Modifiers(8);
+
+    pub fn contains(&self, modifiers: Modifiers) -> bool {
+        self.0 & modifiers.0 == modifiers.0
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyEventKind {
+    Down,
+    /// The key is held down and the platform repeats it.
+    Repeat,
+    Up,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEvent {
+    pub kind: KeyEventKind,
+    pub logical: LogicalKey,
+    pub physical: PhysicalKey,
+    /// Modifiers pressed when the event happened, excluding the key of this event.
+    pub modifiers: Modifiers,
+    /// Time since an arbitrary epoch.
+    pub timestamp: Duration,
+}
+
+impl KeyEvent {
+    /// Create an event without modifiers at timestamp zero.
+    pub fn new(kind: KeyEventKind, logical: LogicalKey) -> Self {
+        Self {
+            kind,
+            logical,
+            physical: PhysicalKey::UNIDENTIFIED,
+            modifiers: Modifiers::NONE,
+            timestamp: Duration::ZERO,
+        }
+    }
+
+    pub fn down(logical: LogicalKey) -> Self {
+        Self::new(KeyEventKind::Down, logical)
+    }
+
+    pub fn repeat(logical: LogicalKey) -> Self {
+        Self::new(KeyEventKind::Repeat, logical)
+    }
+
+    pub fn up(logical: LogicalKey) -> Self {
+        Self::new(KeyEventKind::Up, logical)
+    }
+
+    pub fn with_physical(mut self, physical: PhysicalKey) -> Self {
+        self.physical = physical;
+        self
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: Duration) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Returns true for a key down or a repeat.
+    pub fn is_pressed(&self) -> bool {
+        self.kind != KeyEventKind::Up
+    }
+
+    pub fn is_repeat(&self) -> bool {
+        self.kind == KeyEventKind::Repeat
+    }
+}
@@ -0,0 +1,448 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::Debug,
+    rc::{Rc, Weak},
+};
+
+use indextree::Arena;
+
+use crate::{
+    callback::Callback,
+    element::{Element, ElementId},
+    framework::FrameworkContext,
+};
+
+use super::KeyEvent;
+
+/// Result of a [`KeyHandler`], tells whether the event goes on to the next focus ancestor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyEventResult {
+    /// Stop the dispatch, the event is consumed.
+    Handled,
+    /// Pass the event to the next handler.
+    Ignored,
+    /// Stop the dispatch without consuming the event, e.g. to let the platform handle it.
+    SkipRemainingHandlers,
+}
+
+type KeyHandlerFn = dyn Fn(&KeyEvent, &FrameworkContext) -> KeyEventResult;
+
+/// Key event handler of a [`FocusNode`], compared by identity like [`Callback`].
+#[derive(Clone)]
+pub struct KeyHandler(Rc<KeyHandlerFn>);
+
+impl KeyHandler {
+    pub fn new<F: Fn(&KeyEvent, &FrameworkContext) -> KeyEventResult + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+
+    pub fn call(&self, event: &KeyEvent, context: &FrameworkContext) -> KeyEventResult {
+        (self.0)(event, context)
+    }
+}
+
+impl PartialEq for KeyHandler {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for KeyHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "KeyHandler({:p})", Rc::as_ptr(&self.0))
+    }
+}
+
+#[derive(Debug)]
+struct FocusNodeData {
+    /// Element owning this node while attached.
+    element: Option<ElementId>,
+    manager: Weak<RefCell<FocusTree>>,
+    is_scope: bool,
+    can_request_focus: bool,
+    skip_traversal: bool,
+    order: i32,
+    on_key: Option<KeyHandler>,
+    on_focus_change: Option<Callback<bool>>,
+    /// Last node focused inside this scope.
+    focused_child: Option<FocusNode>,
+}
+
+/// Node of the focus tree, owned by an element of a [`Focus`](super::Focus) or
+/// [`FocusScope`](super::FocusScope) view.
+///
+/// The parent of a node is the node of the nearest ancestor element owning one,
+/// so the focus tree follows the element tree.
+#[derive(Debug, Clone)]
+pub struct FocusNode(Rc<RefCell<FocusNodeData>>);
+
+impl Default for FocusNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FocusNode {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Properties of a [`FocusNode`] set by the view owning it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct FocusNodeProps {
+    pub is_scope: bool,
+    pub can_request_focus: bool,
+    pub skip_traversal: bool,
+    pub order: i32,
+    pub on_key: Option<KeyHandler>,
+    pub on_focus_change: Option<Callback<bool>>,
+}
+
+impl FocusNode {
+    pub fn new() -> Self {
+        Self(Rc::new(RefCell::new(FocusNodeData {
+            element: None,
+            manager: Weak::new(),
+            is_scope: false,
+            can_request_focus: true,
+            skip_traversal: false,
+            order: 0,
+            on_key: None,
+            on_focus_change: None,
+            focused_child: None,
+        })))
+    }
+
+    pub(crate) fn set_props(&self, props: FocusNodeProps) {
+        let mut data = self.0.borrow_mut();
+
+        data.is_scope = props.is_scope;
+        data.can_request_focus = props.can_request_focus;
+        data.skip_traversal = props.skip_traversal;
+        data.order = props.order;
+        data.on_key = props.on_key;
+        data.on_focus_change = props.on_focus_change;
+    }
+
+    /// Returns the element owning this node, `None` if not attached.
+    pub fn element(&self) -> Option<ElementId> {
+        self.0.borrow().element
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.element().is_some()
+    }
+
+    pub fn is_scope(&self) -> bool {
+        self.0.borrow().is_scope
+    }
+
+    pub fn can_request_focus(&self) -> bool {
+        self.0.borrow().can_request_focus
+    }
+
+    pub fn skip_traversal(&self) -> bool {
+        self.0.borrow().skip_traversal
+    }
+
+    pub fn on_key(&self) -> Option<KeyHandler> {
+        self.0.borrow().on_key.clone()
+    }
+
+    fn manager(&self) -> Option<FocusManager> {
+        self.0.borrow().manager.upgrade().map(FocusManager)
+    }
+
+    /// Returns true if this node or a descendant has the primary focus.
+    pub fn has_focus(&self) -> bool {
+        self.manager()
+            .is_some_and(|manager| manager.focus_chain().contains(self))
+    }
+
+    pub fn has_primary_focus(&self) -> bool {
+        self.manager()
+            .and_then(|manager| manager.primary_focus())
+            .is_some_and(|node| node == *self)
+    }
+
+    /// Give the primary focus to this node, a scope gives it to the node last focused inside it,
+    /// or its first traversable descendant.
+    ///
+    /// Returns false if the node is not attached or can't request focus.
+    pub fn request_focus(&self) -> bool {
+        self.manager()
+            .is_some_and(|manager| manager.request_focus(self))
+    }
+
+    /// Remove the primary focus from this node or its descendant.
+    pub fn unfocus(&self) {
+        if let (true, Some(manager)) = (self.has_focus(), self.manager()) {
+            manager.set_primary_focus(None);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct FocusTree {
+    /// Element tree the nodes are attached in.
+    element_tree: Weak<RefCell<Arena<Element>>>,
+    nodes: HashMap<ElementId, FocusNode>,
+    primary_focus: Option<FocusNode>,
+}
+
+/// Direction of focus traversal, e.g. by tab and shift-tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalDirection {
+    Forward,
+    Backward,
+}
+
+/// Registry of the focus nodes attached to the element tree and owner of the primary focus.
+#[derive(Debug, Default, Clone)]
+pub struct FocusManager(Rc<RefCell<FocusTree>>);
+
+impl PartialEq for FocusManager {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl FocusManager {
+    /// Attach `node` to element `id` of `element_tree`.
+    pub(crate) fn attach(
+        &self,
+        node: &FocusNode,
+        id: ElementId,
+        element_tree: &Rc<RefCell<Arena<Element>>>,
+    ) {
+        {
+            let mut data = node.0.borrow_mut();
+
+            data.element = Some(id);
+            data.manager = Rc::downgrade(&self.0);
+        }
+
+        let mut tree = self.0.borrow_mut();
+
+        tree.element_tree = Rc::downgrade(element_tree);
+        tree.nodes.insert(id, node.clone());
+    }
+
+    /// Detach `node` from its element, the primary focus is lost if it's inside the node.
+    pub(crate) fn detach(&self, node: &FocusNode) {
+        if node.has_focus() {
+            self.set_primary_focus(None);
+        }
+
+        let Some(id) = node.element() else {
+            return;
+        };
+
+        {
+            let mut tree = self.0.borrow_mut();
+
+            if tree.nodes.get(&id) == Some(node) {
+                tree.nodes.remove(&id);
+            }
+        }
+
+        let mut data = node.0.borrow_mut();
+
+        data.element = None;
+        data.manager = Weak::new();
+        data.focused_child = None;
+    }
+
+    pub fn primary_focus(&self) -> Option<FocusNode> {
+        self.0.borrow().primary_focus.clone()
+    }
+
+    /// Returns `node` and its focus ancestors, `node` first.
+    fn ancestors(&self, node: &FocusNode) -> Vec<FocusNode> {
+        let tree = self.0.borrow();
+
+        let (Some(id), Some(element_tree)) = (node.element(), tree.element_tree.upgrade()) else {
+            return vec![];
+        };
+
+        let arena = element_tree.borrow();
+
+        id.ancestors(&arena)
+            .filter_map(|id| tree.nodes.get(&id).cloned())
+            .collect()
+    }
+
+    /// Returns the node which has the primary focus and its focus ancestors, the primary first.
+    ///
+    /// Key events are dispatched along this chain.
+    pub fn focus_chain(&self) -> Vec<FocusNode> {
+        self.primary_focus()
+            .map(|node| self.ancestors(&node))
+            .unwrap_or_default()
+    }
+
+    pub fn parent(&self, node: &FocusNode) -> Option<FocusNode> {
+        self.ancestors(node).into_iter().nth(1)
+    }
+
+    /// Returns the nearest scope above `node`, `None` for nodes outside of any scope.
+    pub fn enclosing_scope(&self, node: &FocusNode) -> Option<FocusNode> {
+        self.ancestors(node)
+            .into_iter()
+            .skip(1)
+            .find(|node| node.is_scope())
+    }
+
+    /// Returns the nodes traversed inside `scope`, or outside of any scope if `None`.
+    ///
+    /// Nodes are in element tree order, then stably sorted by their traversal order.
+    /// Nodes inside nested scopes are skipped, the nested scope stands for them.
+    pub fn traversal_order(&self, scope: Option<&FocusNode>) -> Vec<FocusNode> {
+        let candidates = {
+            let tree = self.0.borrow();
+
+            let Some(element_tree) = tree.element_tree.upgrade() else {
+                return vec![];
+            };
+
+            let arena = element_tree.borrow();
+
+            let ids: Vec<ElementId> = match scope {
+                Some(scope) => match scope.element() {
+                    Some(id) => id.descendants(&arena).skip(1).collect(),
+                    None => return vec![],
+                },
+                None => {
+                    let mut roots = tree
+                        .nodes
+                        .keys()
+                        .filter_map(|id| id.ancestors(&arena).last())
+                        .collect::<Vec<_>>();
+
+                    roots.sort();
+                    roots.dedup();
+
+                    roots
+                        .into_iter()
+                        .flat_map(|root| root.descendants(&arena))
+                        .collect()
+                }
+            };
+
+            ids.into_iter()
+                .filter_map(|id| tree.nodes.get(&id).cloned())
+                .collect::<Vec<_>>()
+        };
+
+        let mut nodes = candidates
+            .into_iter()
+            .filter(|node| node.can_request_focus() && !node.skip_traversal())
+            .filter(|node| self.enclosing_scope(node).as_ref() == scope)
+            .collect::<Vec<_>>();
+
+        nodes.sort_by_key(|node| node.0.borrow().order);
+
+        nodes
+    }
+
+    /// Move the primary focus to the next or previous node inside its scope, wrapping around.
+    ///
+    /// Without primary focus, the first or last node outside of any scope is focused.
+    /// Returns true if the focus moved.
+    pub fn move_focus(&self, direction: TraversalDirection) -> bool {
+        let current = self.primary_focus();
+
+        let scope = current
+            .as_ref()
+            .and_then(|current| self.enclosing_scope(current));
+
+        let nodes = self.traversal_order(scope.as_ref());
+
+        let position = current
+            .as_ref()
+            .and_then(|current| nodes.iter().position(|node| node == current));
+
+        let next = match (direction, position) {
+            (TraversalDirection::Forward, Some(index)) => nodes.get((index + 1) % nodes.len()),
+            (TraversalDirection::Backward, Some(index)) => {
+                nodes.get((index + nodes.len() - 1) % nodes.len())
+            }
+            (TraversalDirection::Forward, None) => nodes.first(),
+            (TraversalDirection::Backward, None) => nodes.last(),
+        };
+
+        match next {
+            Some(next) if Some(next) != current.as_ref() => self.request_focus(next),
+            _ => false,
+        }
+    }
+
+    fn request_focus(&self, node: &FocusNode) -> bool {
+        if !node.is_attached() || !node.can_request_focus() {
+            return false;
+        }
+
+        if !node.is_scope() {
+            self.set_primary_focus(Some(node.clone()));
+
+            return true;
+        }
+
+        let focused_child = node.0.borrow().focused_child.clone();
+
+        if let Some(focused_child) = focused_child.filter(|child| {
+            child.is_attached() && child != node && self.ancestors(child).contains(node)
+        }) {
+            if self.request_focus(&focused_child) {
+                return true;
+            }
+        }
+
+        match self.traversal_order(Some(node)).first() {
+            Some(first) if self.request_focus(first) => true,
+            _ => {
+                // Empty scope.
+                self.set_primary_focus(Some(node.clone()));
+
+                true
+            }
+        }
+    }
+
+    /// Replace the primary focus, scopes on the way remember it and nodes which gain or lose
+    /// focus are notified.
+    fn set_primary_focus(&self, node: Option<FocusNode>) {
+        let old_chain = self.focus_chain();
+
+        self.0.borrow_mut().primary_focus = node.clone();
+
+        let new_chain = self.focus_chain();
+
+        for scope in new_chain.iter().skip(1).filter(|node| node.is_scope()) {
+            scope.0.borrow_mut().focused_child = node.clone();
+        }
+
+        let changes = old_chain
+            .iter()
+            .filter(|node| !new_chain.contains(node))
+            .map(|node| (node, false))
+            .chain(
+                new_chain
+                    .iter()
+                    .filter(|node| !old_chain.contains(node))
+                    .map(|node| (node, true)),
+            )
+            .filter_map(|(node, focused)| {
+                let on_focus_change = node.0.borrow().on_focus_change.clone()?;
+
+                Some((on_focus_change, focused))
+            })
+            .collect::<Vec<_>>();
+
+        for (on_focus_change, focused) in changes {
+            on_focus_change.call(focused);
+        }
+    }
+}
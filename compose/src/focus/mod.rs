@@ -0,0 +1,11 @@
+mod keys;
+pub use keys::*;
+
+mod manager;
+pub use manager::*;
+
+mod scope;
+pub use scope::*;
+
+mod shortcuts;
+pub use shortcuts::*;
@@ -0,0 +1,200 @@
+use crate::{
+    callback::Callback,
+    element::{Initializer, StatefulElement},
+    macos::{State, Stateful},
+    view::{Configuration, IntoView, StatefulConfiguration, View},
+};
+
+use super::{FocusNode, FocusNodeProps, KeyHandler};
+
+/// Attach a [`FocusNode`] to the focus tree for `child`.
+///
+/// Key events reach `on_key` while the node or a descendant has the primary focus,
+/// the node gets focus by [`FocusNode::request_focus`] or tab traversal.
+#[derive(Debug, Clone, PartialEq, Stateful)]
+pub struct Focus {
+    /// Node controlled by the caller, e.g. to request focus, otherwise the view owns one.
+    pub focus_node: Option<FocusNode>,
+    /// Request focus when mounted, if nothing has focus yet.
+    pub autofocus: bool,
+    pub can_request_focus: bool,
+    /// Skip this node in tab traversal, it can still request focus.
+    pub skip_traversal: bool,
+    /// Nodes with a lower order are traversed first, ties keep the element tree order.
+    pub order: i32,
+    pub on_key: Option<KeyHandler>,
+    /// Called with true when this node or a descendant gains focus, false when it loses it.
+    pub on_focus_change: Option<Callback<bool>>,
+    pub child: View,
+}
+
+impl Default for Focus {
+    fn default() -> Self {
+        Self {
+            focus_node: None,
+            autofocus: false,
+            can_request_focus: true,
+            skip_traversal: false,
+            order: 0,
+            on_key: None,
+            on_focus_change: None,
+            child: View::Empty,
+        }
+    }
+}
+
+impl Focus {
+    fn create_state(&self) -> FocusState {
+        FocusState::new(self.focus_node.clone())
+    }
+}
+
+/// Group the focus nodes of `child`: tab traversal stays inside the scope,
+/// and the scope remembers the node last focused inside it.
+#[derive(Debug, Default, Clone, PartialEq, Stateful)]
+pub struct FocusScope {
+    pub focus_node: Option<FocusNode>,
+    pub autofocus: bool,
+    pub on_key: Option<KeyHandler>,
+    pub on_focus_change: Option<Callback<bool>>,
+    pub child: View,
+}
+
+impl FocusScope {
+    fn create_state(&self) -> FocusState {
+        FocusState::new(self.focus_node.clone())
+    }
+}
+
+/// Configuration shared by [`Focus`] and [`FocusScope`].
+struct FocusConfig {
+    focus_node: Option<FocusNode>,
+    autofocus: bool,
+    props: FocusNodeProps,
+    child: View,
+}
+
+fn focus_config(element: &StatefulElement) -> Option<FocusConfig> {
+    let config = element.config.borrow();
+
+    config
+        .downcast(|focus: &Focus| FocusConfig {
+            focus_node: focus.focus_node.clone(),
+            autofocus: focus.autofocus,
+            props: FocusNodeProps {
+                is_scope: false,
+                can_request_focus: focus.can_request_focus,
+                skip_traversal: focus.skip_traversal,
+                order: focus.order,
+                on_key: focus.on_key.clone(),
+                on_focus_change: focus.on_focus_change.clone(),
+            },
+            child: focus.child.clone(),
+        })
+        .or_else(|| {
+            config.downcast(|scope: &FocusScope| FocusConfig {
+                focus_node: scope.focus_node.clone(),
+                autofocus: scope.autofocus,
+                props: FocusNodeProps {
+                    is_scope: true,
+                    can_request_focus: true,
+                    skip_traversal: false,
+                    order: 0,
+                    on_key: scope.on_key.clone(),
+                    on_focus_change: scope.on_focus_change.clone(),
+                },
+                child: scope.child.clone(),
+            })
+        })
+}
+
+/// State of [`Focus`] and [`FocusScope`], attaches the node while the element is mounted.
+#[derive(Debug, State)]
+#[state(init_state, did_update_config, dispose)]
+pub struct FocusState {
+    node: FocusNode,
+    /// True if the node is created by this state rather than passed by the configuration.
+    owned: bool,
+}
+
+impl FocusState {
+    fn new(focus_node: Option<FocusNode>) -> Self {
+        Self {
+            owned: focus_node.is_none(),
+            node: focus_node.unwrap_or_default(),
+        }
+    }
+
+    fn attach(&self, element: &StatefulElement) {
+        if let (Some(context), Some(id)) = (element.to_context(), element.to_id()) {
+            context
+                .focus_manager
+                .attach(&self.node, id, &context.element_tree);
+        }
+    }
+
+    fn detach(&self, element: &StatefulElement) {
+        if let Some(context) = element.to_context() {
+            context.focus_manager.detach(&self.node);
+        }
+    }
+
+    fn init_state(&mut self, element: &StatefulElement) {
+        let Some(config) = focus_config(element) else {
+            return;
+        };
+
+        self.node.set_props(config.props);
+
+        self.attach(element);
+
+        let has_focus = element
+            .to_context()
+            .is_some_and(|context| context.focus_manager.primary_focus().is_some());
+
+        if config.autofocus && !has_focus {
+            self.node.request_focus();
+        }
+    }
+
+    fn did_update_config(
+        &mut self,
+        element: &StatefulElement,
+        _old: &Configuration<dyn StatefulConfiguration>,
+    ) {
+        let Some(config) = focus_config(element) else {
+            return;
+        };
+
+        let owned = config.focus_node.is_none();
+
+        // Switch between the node of the configuration and an owned one.
+        let node = match (config.focus_node, self.owned) {
+            (Some(node), _) => Some(node),
+            (None, false) => Some(FocusNode::new()),
+            (None, true) => None,
+        };
+
+        if let Some(node) = node.filter(|node| *node != self.node) {
+            self.detach(element);
+
+            self.owned = owned;
+            self.node = node;
+            self.node.set_props(config.props);
+
+            self.attach(element);
+        } else {
+            self.node.set_props(config.props);
+        }
+    }
+
+    fn dispose(&mut self, element: &StatefulElement) {
+        self.detach(element);
+    }
+
+    fn build(&self, element: &StatefulElement) -> impl IntoView {
+        focus_config(element)
+            .map(|config| config.child)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    fmt::Debug,
+    rc::Rc,
+};
+
+use crate::{
+    element::{ElementId, StatefulElement},
+    framework::FrameworkContext,
+    macos::{Inherited, State, Stateful},
+    view::{Configuration, IntoView, StatefulConfiguration, View},
+};
+
+use super::{Focus, KeyEvent, KeyEventKind, KeyEventResult, KeyHandler, LogicalKey, Modifiers};
+
+/// Purpose of a key combination, e.g. "save", bound by [`Shortcuts`] and performed by [`Actions`].
+pub trait Intent: Any + Debug {}
+
+/// Key with exactly these modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SingleActivator {
+    pub key: LogicalKey,
+    pub modifiers: Modifiers,
+    /// Activate on key repeats as well.
+    pub include_repeats: bool,
+}
+
+impl SingleActivator {
+    pub fn new(key: LogicalKey) -> Self {
+        Self {
+            key,
+            modifiers: Modifiers::NONE,
+            include_repeats: true,
+        }
+    }
+
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn accepts(&self, event: &KeyEvent) -> bool {
+        let pressed = match event.kind {
+            KeyEventKind::Down => true,
+            KeyEventKind::Repeat => self.include_repeats,
+            KeyEventKind::Up => false,
+        };
+
+        pressed && event.logical == self.key && event.modifiers == self.modifiers
+    }
+}
+
+/// Intents bound to activators, the first accepting activator wins.
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutMap(Vec<(SingleActivator, Rc<dyn Intent>)>);
+
+impl PartialEq for ShortcutMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|(a, b)| a.0 == b.0 && Rc::ptr_eq(&a.1, &b.1))
+    }
+}
+
+impl ShortcutMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind<I: Intent>(mut self, activator: SingleActivator, intent: I) -> Self {
+        self.0.push((activator, Rc::new(intent)));
+        self
+    }
+
+    /// Returns the intent bound to the first activator accepting `event`.
+    pub fn find(&self, event: &KeyEvent) -> Option<Rc<dyn Intent>> {
+        self.0
+            .iter()
+            .find(|(activator, _)| activator.accepts(event))
+            .map(|(_, intent)| intent.clone())
+    }
+}
+
+/// Turn key events which reach it into intents, invoked on the [`Actions`] above the focused node.
+#[derive(Debug, Default, Clone, PartialEq, Stateful)]
+pub struct Shortcuts {
+    pub shortcuts: ShortcutMap,
+    pub child: View,
+}
+
+impl Shortcuts {
+    fn create_state(&self) -> ShortcutsState {
+        let shortcuts = Rc::new(RefCell::new(self.shortcuts.clone()));
+
+        let on_key = KeyHandler::new({
+            let shortcuts = shortcuts.clone();
+
+            move |event, context| {
+                let Some(intent) = shortcuts.borrow().find(event) else {
+                    return KeyEventResult::Ignored;
+                };
+
+                let focused = context
+                    .focus_manager
+                    .primary_focus()
+                    .and_then(|node| node.element());
+
+                match focused {
+                    Some(focused) if Actions::invoke(context, focused, intent.as_ref()) => {
+                        KeyEventResult::Handled
+                    }
+                    _ => KeyEventResult::Ignored,
+                }
+            }
+        });
+
+        ShortcutsState { shortcuts, on_key }
+    }
+}
+
+#[derive(Debug, State)]
+#[state(did_update_config)]
+pub struct ShortcutsState {
+    /// Bindings of the current configuration, shared with `on_key`.
+    shortcuts: Rc<RefCell<ShortcutMap>>,
+    on_key: KeyHandler,
+}
+
+impl ShortcutsState {
+    fn did_update_config(
+        &mut self,
+        element: &StatefulElement,
+        _old: &Configuration<dyn StatefulConfiguration>,
+    ) {
+        if let Some(shortcuts) = element
+            .config
+            .borrow()
+            .downcast(|config: &Shortcuts| config.shortcuts.clone())
+        {
+            *self.shortcuts.borrow_mut() = shortcuts;
+        }
+    }
+
+    fn build(&self, element: &StatefulElement) -> impl IntoView {
+        let child = element
+            .config
+            .borrow()
+            .downcast(|config: &Shortcuts| config.child.clone())
+            .unwrap_or_default();
+
+        Focus {
+            can_request_focus: false,
+            skip_traversal: true,
+            on_key: Some(self.on_key.clone()),
+            child,
+            ..Default::default()
+        }
+    }
+}
+
+type ActionFn = dyn Fn(&dyn Intent);
+
+/// Actions performing intents, by intent type.
+#[derive(Clone, Default)]
+pub struct ActionMap(Vec<(TypeId, &'static str, Rc<ActionFn>)>);
+
+impl PartialEq for ActionMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .zip(&other.0)
+                .all(|(a, b)| a.0 == b.0 && Rc::ptr_eq(&a.2, &b.2))
+    }
+}
+
+impl Debug for ActionMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(_, name, _)| name))
+            .finish()
+    }
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Perform intents of type `I` with `f`.
+    pub fn on<I: Intent, F: Fn(&I) + 'static>(mut self, f: F) -> Self {
+        let action = move |intent: &dyn Intent| {
+            if let Some(intent) = (intent as &dyn Any).downcast_ref::<I>() {
+                f(intent);
+            }
+        };
+
+        self.0.push((
+            TypeId::of::<I>(),
+            std::any::type_name::<I>(),
+            Rc::new(action),
+        ));
+        self
+    }
+
+    fn find(&self, intent: &dyn Intent) -> Option<Rc<ActionFn>> {
+        let type_id = (intent as &dyn Any).type_id();
+
+        self.0
+            .iter()
+            .find(|(id, _, _)| *id == type_id)
+            .map(|(_, _, action)| action.clone())
+    }
+}
+
+/// Provide actions to the descendants, see [`Actions::invoke`].
+#[derive(Debug, Default, Clone, PartialEq, Inherited)]
+pub struct Actions {
+    pub actions: ActionMap,
+    pub child: View,
+}
+
+impl Actions {
+    fn child(&self) -> View {
+        self.child.clone()
+    }
+
+    /// Perform `intent` with the action of the nearest [`Actions`] at or above element `id`
+    /// which handles its type.
+    ///
+    /// Returns false if no action handles the intent.
+    pub fn invoke(context: &FrameworkContext, id: ElementId, intent: &dyn Intent) -> bool {
+        let action = {
+            let arena = context.element_tree.borrow();
+
+            id.ancestors(&arena)
+                .find_map(|id| match arena.get(id)?.get().to_configuration() {
+                    View::Inherited(config) => config
+                        .downcast(|actions: &Actions| actions.actions.find(intent))
+                        .flatten(),
+                    _ => None,
+                })
+        };
+
+        match action {
+            Some(action) => {
+                action(intent);
+
+                true
+            }
+            None => false,
+        }
+    }
+}
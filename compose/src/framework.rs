@@ -1,13 +1,33 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
 use indextree::Arena;
 
-use crate::{element::Element, view::RenderObject};
+use crate::{
+    element::{Element, ElementId},
+    focus::{FocusManager, KeyEvent, KeyEventResult, LogicalKey, Modifiers, TraversalDirection},
+    geometry::{BoxConstraints, Offset},
+    gesture::{GestureBinding, PointerEvent, PointerEventKind},
+    keypath::GlobalKey,
+    layer::Layer,
+    painting::{Canvas, Picture},
+    render::{hit_test, layout, mark_parent_needs_layout, repaint_boundary_layer, HitTestResult},
+    view::{RenderObject, RenderObjectId, State, View},
+};
 
 #[derive(Debug, Default, Clone)]
 pub struct FrameworkContext {
     pub element_tree: Rc<RefCell<Arena<Element>>>,
     pub render_tree: Rc<RefCell<Arena<RenderObject>>>,
+    pub build_owner: BuildOwner,
+    pub pipeline_owner: PipelineOwner,
+    pub global_keys: GlobalKeyRegistry,
+    pub gesture_binding: GestureBinding,
+    pub focus_manager: FocusManager,
 }
 
 impl
@@ -25,6 +45,637 @@ impl
         FrameworkContext {
             element_tree: value.0,
             render_tree: value.1,
+            build_owner: Default::default(),
+            pipeline_owner: Default::default(),
+            global_keys: Default::default(),
+            gesture_binding: Default::default(),
+            focus_manager: Default::default(),
         }
     }
 }
+
+impl FrameworkContext {
+    /// Create a handle which doesn't keep the trees alive, elements hold it to access the trees.
+    pub fn downgrade(&self) -> WeakFrameworkContext {
+        WeakFrameworkContext {
+            element_tree: Rc::downgrade(&self.element_tree),
+            render_tree: Rc::downgrade(&self.render_tree),
+            build_owner: self.build_owner.clone(),
+            pipeline_owner: self.pipeline_owner.clone(),
+            global_keys: self.global_keys.clone(),
+            gesture_binding: self.gesture_binding.clone(),
+            focus_manager: self.focus_manager.clone(),
+        }
+    }
+
+    /// Inflate `view` into the root element and mount it, returns `None` for [`View::Empty`].
+    ///
+    /// Effects scheduled by the first build run once the tree is mounted.
+    pub fn mount_root(&mut self, view: View) -> Option<Element> {
+        let id = view.into_element(&mut self.element_tree.borrow_mut())?;
+
+        let root = self.element_tree.borrow().get(id)?.get().clone();
+
+        root.mount(self, None);
+
+        self.flush_effects();
+
+        Some(root)
+    }
+
+    /// Rebuild all elements marked dirty since the last flush.
+    pub fn flush_build(&mut self) {
+        self.build_owner.clone().build_scope(self);
+    }
+
+    /// Returns the render objects without parent, in creation order.
+    pub fn render_roots(&self) -> Vec<RenderObjectId> {
+        self.render_tree
+            .borrow()
+            .iter()
+            .filter(|node| !node.is_removed() && node.parent().is_none())
+            .filter_map(|node| node.get().to_id())
+            .collect()
+    }
+
+    /// Lay out every render tree root within `constraints`, e.g. the window size.
+    ///
+    /// Only the dirty subtrees are laid out again, see [`PipelineOwner::flush_layout`].
+    pub fn flush_layout(&mut self, constraints: BoxConstraints) {
+        let roots = self.render_roots();
+
+        self.pipeline_owner
+            .clone()
+            .flush_layout(&self.render_tree.borrow(), &roots, constraints);
+    }
+
+    /// Paint render objects which need paint and returns the layer tree of every render tree root.
+    ///
+    /// Clean repaint boundaries reuse their cached layers, see [`PipelineOwner::flush_paint`].
+    pub fn flush_layers(&mut self) -> Layer {
+        let roots = self.render_roots();
+
+        self.pipeline_owner
+            .flush_paint(&self.render_tree.borrow(), &roots)
+    }
+
+    /// Paint every render tree root and record the frame into a single picture,
+    /// which can be replayed on any canvas, diffed or serialized.
+    pub fn record_frame(&mut self) -> Picture {
+        self.flush_layers().to_picture()
+    }
+
+    /// Paint every render tree root and composite the layer tree into `canvas`.
+    pub fn flush_paint(&mut self, canvas: &mut dyn Canvas) {
+        self.flush_layers().composite(canvas);
+    }
+
+    /// Returns the render objects at `position` in the root coordinate, the deepest first.
+    ///
+    /// Render tree roots created later are painted on top, so they are tested first.
+    pub fn hit_test(&self, position: Offset) -> HitTestResult {
+        let render_tree = self.render_tree.borrow();
+
+        self.render_roots()
+            .into_iter()
+            .rev()
+            .map(|root| hit_test(&render_tree, root, position))
+            .find(|result| !result.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Dispatch `event` to the render objects hit by its pointer down, then to the gesture
+    /// recognizers tracking the pointer.
+    ///
+    /// Pointer downs and scrolls are hit tested at their position, the following events of a
+    /// pointer go along the path of its down. Moves of pointers which are not down, e.g. mouse
+    /// hover, are hit tested as well.
+    pub fn dispatch_pointer_event(&mut self, event: PointerEvent) {
+        let result = match event.kind {
+            PointerEventKind::Down => {
+                let result = self.hit_test(event.position);
+
+                self.gesture_binding
+                    .add_hit_test(event.pointer, result.clone());
+
+                result
+            }
+            PointerEventKind::Scroll(_) => self.hit_test(event.position),
+            PointerEventKind::Move | PointerEventKind::Up | PointerEventKind::Cancel => self
+                .gesture_binding
+                .hit_test_of(event.pointer)
+                .unwrap_or_else(|| self.hit_test(event.position)),
+        };
+
+        let targets = {
+            let render_tree = self.render_tree.borrow();
+
+            result
+                .path
+                .iter()
+                .filter_map(|entry| {
+                    let render_object = render_tree.get(entry.render_object_id)?;
+
+                    (!render_object.is_removed())
+                        .then(|| (render_object.get().clone(), entry.transform))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        self.gesture_binding.dispatch(&targets, event);
+    }
+
+    /// Advance the gesture clock to `now`, in the timestamp base of the pointer events,
+    /// e.g. to recognize a long press while the pointer doesn't move.
+    pub fn advance_gestures(&mut self, now: Duration) {
+        self.gesture_binding.advance(now);
+    }
+
+    /// Dispatch `event` to the key handlers of the focused node and its focus ancestors,
+    /// the focused node first, until one handles it.
+    ///
+    /// Unhandled tab and shift-tab move the focus, see [`FocusManager::move_focus`].
+    /// Returns true if the event is handled.
+    pub fn dispatch_key_event(&mut self, event: KeyEvent) -> bool {
+        for node in self.focus_manager.focus_chain() {
+            let Some(on_key) = node.on_key() else {
+                continue;
+            };
+
+            match on_key.call(&event, self) {
+                KeyEventResult::Handled => return true,
+                KeyEventResult::SkipRemainingHandlers => return false,
+                KeyEventResult::Ignored => {}
+            }
+        }
+
+        if !event.is_pressed() || event.logical != LogicalKey::Tab {
+            return false;
+        }
+
+        match event.modifiers {
+            Modifiers::NONE => self.focus_manager.move_focus(TraversalDirection::Forward),
+            Modifiers::SHIFT => self.focus_manager.move_focus(TraversalDirection::Backward),
+            _ => false,
+        }
+    }
+
+    /// Produce a frame: rebuild dirty elements, lay out dirty render objects within `constraints`
+    /// and paint into `canvas` if anything needs repaint.
+    ///
+    /// Returns true if the frame was painted.
+    pub fn draw_frame(&mut self, constraints: BoxConstraints, canvas: &mut dyn Canvas) -> bool {
+        self.flush_build();
+        self.flush_layout(constraints);
+
+        if !self.pipeline_owner.has_dirty_paint() {
+            return false;
+        }
+
+        self.flush_paint(canvas);
+
+        true
+    }
+
+    /// Unmount deactivated elements which were not reused by the end of build.
+    pub fn finalize_tree(&mut self) {
+        for id in self.build_owner.take_inactive() {
+            self.unmount_subtree(id);
+        }
+    }
+
+    /// Unmount the element subtree rooted at `id`, children first.
+    ///
+    /// Each element is disposed, unregistered from global keys and removed from `element_tree`
+    /// together with its render object in `render_tree`.
+    pub fn unmount_subtree(&mut self, id: ElementId) {
+        let ids = id
+            .descendants(&self.element_tree.borrow())
+            .collect::<Vec<_>>();
+
+        for id in ids.into_iter().rev() {
+            let element = self.element_tree.borrow().get(id).unwrap().get().clone();
+
+            element.0.unmount();
+            element.0.hooks().dispose();
+
+            if let Some(key) = element.to_configuration().to_global_key() {
+                self.global_keys.unregister(key, id);
+            }
+
+            if let Some(render_object_id) = element.to_render_object_id() {
+                mark_parent_needs_layout(&self.render_tree.borrow(), render_object_id);
+
+                render_object_id.remove(&mut self.render_tree.borrow_mut());
+            }
+
+            id.remove(&mut self.element_tree.borrow_mut());
+        }
+    }
+
+    /// Run the effects scheduled by [`BuildContext::use_effect`](crate::element::BuildContext::use_effect)
+    /// in the builds since the last flush.
+    pub fn flush_effects(&mut self) {
+        for id in self.build_owner.take_effects() {
+            let element = self
+                .element_tree
+                .borrow()
+                .get(id)
+                .map(|node| node.get().clone());
+
+            if let Some(element) = element {
+                element.0.hooks().run_effects();
+            }
+        }
+    }
+
+    /// Get element mounted with global `key`.
+    pub fn element_of(&self, key: GlobalKey) -> Option<Element> {
+        let id = self.global_keys.get(key)?;
+
+        self.element_tree
+            .borrow()
+            .get(id)
+            .map(|node| node.get().clone())
+    }
+
+    /// Get the first render object of the element mounted with global `key`.
+    pub fn render_object_of(&self, key: GlobalKey) -> Option<RenderObject> {
+        self.element_of(key)?.first_render_object(self)
+    }
+
+    /// Call `f` with the state of stateful element mounted with global `key`.
+    ///
+    /// Returns `None` if no such element, the state type mismatch or the state is building.
+    pub fn with_state_of<S: State + 'static, R, F: FnOnce(&S) -> R>(
+        &self,
+        key: GlobalKey,
+        f: F,
+    ) -> Option<R> {
+        let element = self.element_of(key)?;
+
+        let state: Ref<dyn State> = element.0.to_state()?;
+
+        state.as_any().downcast_ref::<S>().map(f)
+    }
+}
+
+/// Weak version of [`FrameworkContext`], see [`FrameworkContext::downgrade`].
+#[derive(Debug, Clone)]
+pub struct WeakFrameworkContext {
+    element_tree: Weak<RefCell<Arena<Element>>>,
+    render_tree: Weak<RefCell<Arena<RenderObject>>>,
+    pub build_owner: BuildOwner,
+    pub pipeline_owner: PipelineOwner,
+    pub global_keys: GlobalKeyRegistry,
+    pub gesture_binding: GestureBinding,
+    pub focus_manager: FocusManager,
+}
+
+impl WeakFrameworkContext {
+    /// Returns `None` if the trees are dropped.
+    pub fn upgrade(&self) -> Option<FrameworkContext> {
+        Some(FrameworkContext {
+            element_tree: self.element_tree.upgrade()?,
+            render_tree: self.render_tree.upgrade()?,
+            build_owner: self.build_owner.clone(),
+            pipeline_owner: self.pipeline_owner.clone(),
+            global_keys: self.global_keys.clone(),
+            gesture_binding: self.gesture_binding.clone(),
+            focus_manager: self.focus_manager.clone(),
+        })
+    }
+}
+
+/// Registry of elements mounted with [`GlobalKey`].
+#[derive(Debug, Default, Clone)]
+pub struct GlobalKeyRegistry(Rc<RefCell<HashMap<GlobalKey, ElementId>>>);
+
+impl GlobalKeyRegistry {
+    pub fn register(&self, key: GlobalKey, id: ElementId) {
+        let previous = self.0.borrow_mut().insert(key, id);
+
+        if let Some(previous) = previous.filter(|previous| *previous != id) {
+            log::warn!("GlobalKey {:?} reused, replace element {}", key, previous);
+        }
+    }
+
+    /// Remove registration of `key` if it's still associated with element `id`.
+    pub fn unregister(&self, key: GlobalKey, id: ElementId) {
+        let mut keys = self.0.borrow_mut();
+
+        if keys.get(&key) == Some(&id) {
+            keys.remove(&key);
+        }
+    }
+
+    pub fn get(&self, key: GlobalKey) -> Option<ElementId> {
+        self.0.borrow().get(&key).copied()
+    }
+}
+
+#[derive(Debug, Default)]
+struct DirtyElements {
+    elements: Vec<ElementId>,
+    needs_resorting: bool,
+    inactive: Vec<ElementId>,
+    /// Elements with effects to run after the build.
+    effects: Vec<ElementId>,
+}
+
+/// Manager of the dirty element list and the inactive element list.
+///
+/// Elements are scheduled by [`Lifecycle::mark_needs_build`](crate::element::Lifecycle::mark_needs_build)
+/// and rebuilt parent first by [`BuildOwner::build_scope`].
+/// Deactivated elements are parked in the inactive list and unmounted at the end of the build scope,
+/// unless reused by global key.
+#[derive(Debug, Default, Clone)]
+pub struct BuildOwner(Rc<RefCell<DirtyElements>>);
+
+impl PartialEq for BuildOwner {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl BuildOwner {
+    /// Add element to the dirty list, duplicate schedule is ignored.
+    pub fn schedule_build_for(&self, id: ElementId) {
+        let mut dirty = self.0.borrow_mut();
+
+        if !dirty.elements.contains(&id) {
+            dirty.elements.push(id);
+            dirty.needs_resorting = true;
+        }
+    }
+
+    /// Returns true if element is waiting for rebuild.
+    pub fn is_dirty(&self, id: ElementId) -> bool {
+        self.0.borrow().elements.contains(&id)
+    }
+
+    /// Returns true if any element is waiting for rebuild.
+    pub fn has_dirty_elements(&self) -> bool {
+        !self.0.borrow().elements.is_empty()
+    }
+
+    /// Remove element from the dirty list, called when the element is rebuilt.
+    pub fn clean(&self, id: ElementId) {
+        self.0.borrow_mut().elements.retain(|e| *e != id);
+    }
+
+    /// Park detached element subtree until it is reused or the tree is finalized.
+    pub fn deactivate(&self, id: ElementId) {
+        self.0.borrow_mut().inactive.push(id);
+    }
+
+    pub fn reactivate(&self, id: ElementId) {
+        self.0.borrow_mut().inactive.retain(|e| *e != id);
+    }
+
+    pub fn take_inactive(&self) -> Vec<ElementId> {
+        std::mem::take(&mut self.0.borrow_mut().inactive)
+    }
+
+    /// Schedule the pending effects of element `id` to run at the end of the build scope.
+    pub fn schedule_effects_for(&self, id: ElementId) {
+        let mut dirty = self.0.borrow_mut();
+
+        if !dirty.effects.contains(&id) {
+            dirty.effects.push(id);
+        }
+    }
+
+    pub fn take_effects(&self) -> Vec<ElementId> {
+        std::mem::take(&mut self.0.borrow_mut().effects)
+    }
+
+    /// Rebuild dirty elements in depth order, elements scheduled during the flush are rebuilt too.
+    ///
+    /// The tree is finalized and the scheduled effects run once the dirty list is empty.
+    pub fn build_scope(&self, build_context: &mut FrameworkContext) {
+        loop {
+            let id = self.pop_shallowest(&build_context.element_tree.borrow());
+
+            let Some(id) = id else {
+                break;
+            };
+
+            let element = build_context
+                .element_tree
+                .borrow()
+                .get(id)
+                .map(|node| node.get().clone());
+
+            if let Some(element) = element {
+                element.rebuild(build_context);
+            }
+        }
+
+        build_context.finalize_tree();
+        build_context.flush_effects();
+    }
+
+    fn pop_shallowest(&self, arena: &Arena<Element>) -> Option<ElementId> {
+        let mut dirty = self.0.borrow_mut();
+        let dirty = &mut *dirty;
+
+        // Drop elements removed from tree after scheduled, and elements of the subtrees
+        // deactivated in this build scope.
+        let inactive = &dirty.inactive;
+
+        dirty.elements.retain(|id| {
+            arena.get(*id).is_some() && !id.ancestors(arena).any(|id| inactive.contains(&id))
+        });
+
+        if dirty.needs_resorting {
+            // Deepest first, so the shallowest element can be popped from the tail.
+            dirty
+                .elements
+                .sort_by_cached_key(|id| std::cmp::Reverse(id.ancestors(arena).count()));
+
+            dirty.needs_resorting = false;
+        }
+
+        dirty.elements.pop()
+    }
+}
+
+#[derive(Debug, Default)]
+struct DirtyRenderObjects {
+    layout: Vec<RenderObjectId>,
+    paint: Vec<RenderObjectId>,
+}
+
+/// Manager of render objects waiting for layout and paint.
+///
+/// Render objects are scheduled by [`RenderObject::mark_needs_layout`] and
+/// [`RenderObject::mark_needs_paint`], [`PipelineOwner::flush_layout`] lays out only the subtrees
+/// under the nearest relayout boundary of each dirty render object.
+#[derive(Debug, Default, Clone)]
+pub struct PipelineOwner(Rc<RefCell<DirtyRenderObjects>>);
+
+impl PartialEq for PipelineOwner {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PipelineOwner {
+    /// Add render object to the layout list, duplicate schedule is ignored.
+    pub fn schedule_layout(&self, id: RenderObjectId) {
+        let mut dirty = self.0.borrow_mut();
+
+        if !dirty.layout.contains(&id) {
+            dirty.layout.push(id);
+        }
+    }
+
+    /// Add render object to the paint list, duplicate schedule is ignored.
+    pub fn schedule_paint(&self, id: RenderObjectId) {
+        let mut dirty = self.0.borrow_mut();
+
+        if !dirty.paint.contains(&id) {
+            dirty.paint.push(id);
+        }
+    }
+
+    /// Returns true if any render object is waiting for layout.
+    pub fn has_dirty_layout(&self) -> bool {
+        !self.0.borrow().layout.is_empty()
+    }
+
+    /// Returns true if any render object is waiting for paint.
+    pub fn has_dirty_paint(&self) -> bool {
+        !self.0.borrow().paint.is_empty()
+    }
+
+    /// Paint `roots` into a layer tree, render tree roots are repaint boundaries.
+    ///
+    /// Each render object which needs paint flags its ancestors up to the nearest repaint boundary,
+    /// the dirty boundaries are painted again deepest first and updated in place,
+    /// so every layer tree retaining them stays valid.
+    pub fn flush_paint(
+        &self,
+        render_tree: &Arena<RenderObject>,
+        roots: &[RenderObjectId],
+    ) -> Layer {
+        let dirty = std::mem::take(&mut self.0.borrow_mut().paint);
+
+        let mut boundaries = vec![];
+
+        for id in dirty {
+            if let Some(boundary) = mark_until_repaint_boundary(render_tree, id) {
+                if !boundaries.contains(&boundary) {
+                    boundaries.push(boundary);
+                }
+            }
+        }
+
+        boundaries.sort_by_cached_key(|id| std::cmp::Reverse(id.ancestors(render_tree).count()));
+
+        for id in boundaries {
+            if render_tree.get(id).is_some() {
+                repaint_boundary_layer(render_tree, id);
+            }
+        }
+
+        Layer::Container(
+            roots
+                .iter()
+                .map(|id| repaint_boundary_layer(render_tree, *id))
+                .collect(),
+        )
+    }
+
+    /// Lay out `roots` within `constraints` and every dirty render object.
+    ///
+    /// Each dirty render object flags its ancestors up to the nearest relayout boundary,
+    /// then the boundaries are laid out again with their last constraints, shallowest first.
+    pub fn flush_layout(
+        &self,
+        render_tree: &Arena<RenderObject>,
+        roots: &[RenderObjectId],
+        constraints: BoxConstraints,
+    ) {
+        let dirty = std::mem::take(&mut self.0.borrow_mut().layout);
+
+        let mut boundaries = vec![];
+
+        for id in dirty {
+            if let Some(boundary) = mark_until_relayout_boundary(render_tree, id) {
+                if !boundaries.contains(&boundary) {
+                    boundaries.push(boundary);
+                }
+            }
+        }
+
+        for id in roots {
+            layout(render_tree, *id, constraints);
+        }
+
+        boundaries.sort_by_cached_key(|id| id.ancestors(render_tree).count());
+
+        for id in boundaries {
+            let Some(node) = render_tree.get(id) else {
+                continue;
+            };
+
+            let render_object = node.get();
+
+            let constraints = render_object.0.borrow().constraints;
+
+            if let (true, Some(constraints)) = (render_object.needs_layout(), constraints) {
+                layout(render_tree, id, constraints);
+            }
+        }
+    }
+}
+
+/// Flag `id` and its ancestors for relayout up to the nearest relayout boundary, returns the boundary.
+fn mark_until_relayout_boundary(
+    render_tree: &Arena<RenderObject>,
+    id: RenderObjectId,
+) -> Option<RenderObjectId> {
+    let mut boundary = None;
+
+    for id in id.ancestors(render_tree) {
+        let render_object = render_tree.get(id)?.get();
+
+        let mut node = render_object.0.borrow_mut();
+
+        node.needs_layout = true;
+        node.needs_paint = true;
+
+        boundary = Some(id);
+
+        if node.relayout_boundary {
+            break;
+        }
+    }
+
+    boundary
+}
+
+/// Flag `id` and its ancestors for repaint up to the nearest repaint boundary or root,
+/// returns the boundary.
+fn mark_until_repaint_boundary(
+    render_tree: &Arena<RenderObject>,
+    id: RenderObjectId,
+) -> Option<RenderObjectId> {
+    let mut boundary = None;
+
+    for id in id.ancestors(render_tree) {
+        let mut node = render_tree.get(id)?.get().0.borrow_mut();
+
+        node.needs_paint = true;
+
+        boundary = Some(id);
+
+        if node.lifecycle.is_repaint_boundary() {
+            break;
+        }
+    }
+
+    boundary
+}
@@ -0,0 +1,410 @@
+use std::ops::{Add, Sub};
+
+use serde::{Deserialize, Serialize};
+
+/// Width and height of a render object in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    pub const ZERO: Size = Size {
+        width: 0.0,
+        height: 0.0,
+    };
+
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width, height }
+    }
+}
+
+/// Position relative to the parent render object in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Offset {
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Offset {
+    pub const ZERO: Offset = Offset { dx: 0.0, dy: 0.0 };
+
+    pub fn new(dx: f32, dy: f32) -> Self {
+        Self { dx, dy }
+    }
+
+    /// Length of the offset from the origin.
+    pub fn distance(&self) -> f32 {
+        (self.dx * self.dx + self.dy * self.dy).sqrt()
+    }
+
+    pub fn scale(&self, factor: f32) -> Self {
+        Self::new(self.dx * factor, self.dy * factor)
+    }
+}
+
+impl Add for Offset {
+    type Output = Offset;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Offset::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl Sub for Offset {
+    type Output = Offset;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Offset::new(self.dx - rhs.dx, self.dy - rhs.dy)
+    }
+}
+
+/// Box layout constraints passed from parent to child, the child picks a size within them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxConstraints {
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+}
+
+impl Default for BoxConstraints {
+    /// Unconstrained, any size is allowed.
+    fn default() -> Self {
+        Self::new(0.0, f32::INFINITY, 0.0, f32::INFINITY)
+    }
+}
+
+impl BoxConstraints {
+    pub fn new(min_width: f32, max_width: f32, min_height: f32, max_height: f32) -> Self {
+        Self {
+            min_width,
+            max_width,
+            min_height,
+            max_height,
+        }
+    }
+
+    /// Only `size` is allowed.
+    pub fn tight(size: Size) -> Self {
+        Self::new(size.width, size.width, size.height, size.height)
+    }
+
+    /// Any size smaller than `size` is allowed.
+    pub fn loose(size: Size) -> Self {
+        Self::new(0.0, size.width, 0.0, size.height)
+    }
+
+    /// Remove the minimum constraints.
+    pub fn loosen(&self) -> Self {
+        Self::new(0.0, self.max_width, 0.0, self.max_height)
+    }
+
+    /// Shrink the constraints by `horizontal` and `vertical`, e.g. for padding.
+    pub fn deflate(&self, horizontal: f32, vertical: f32) -> Self {
+        let min_width = (self.min_width - horizontal).max(0.0);
+        let min_height = (self.min_height - vertical).max(0.0);
+
+        Self::new(
+            min_width,
+            (self.max_width - horizontal).max(min_width),
+            min_height,
+            (self.max_height - vertical).max(min_height),
+        )
+    }
+
+    pub fn is_tight(&self) -> bool {
+        self.min_width >= self.max_width && self.min_height >= self.max_height
+    }
+
+    /// Returns the size closest to `size` within the constraints.
+    pub fn constrain(&self, size: Size) -> Size {
+        Size::new(
+            size.width
+                .clamp(self.min_width, self.max_width.max(self.min_width)),
+            size.height
+                .clamp(self.min_height, self.max_height.max(self.min_height)),
+        )
+    }
+
+    pub fn smallest(&self) -> Size {
+        Size::new(self.min_width, self.min_height)
+    }
+
+    /// Returns the biggest size within the constraints, unbounded axis fall back to the minimum.
+    pub fn biggest(&self) -> Size {
+        Size::new(
+            if self.max_width.is_finite() {
+                self.max_width
+            } else {
+                self.min_width
+            },
+            if self.max_height.is_finite() {
+                self.max_height
+            } else {
+                self.min_height
+            },
+        )
+    }
+}
+
+/// Axis aligned rectangle in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl Rect {
+    pub const ZERO: Rect = Rect {
+        left: 0.0,
+        top: 0.0,
+        right: 0.0,
+        bottom: 0.0,
+    };
+
+    pub fn from_ltrb(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub fn from_ltwh(left: f32, top: f32, width: f32, height: f32) -> Self {
+        Self::from_ltrb(left, top, left + width, top + height)
+    }
+
+    pub fn from_offset_size(offset: Offset, size: Size) -> Self {
+        Self::from_ltwh(offset.dx, offset.dy, size.width, size.height)
+    }
+
+    pub fn width(&self) -> f32 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f32 {
+        self.bottom - self.top
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+
+    pub fn top_left(&self) -> Offset {
+        Offset::new(self.left, self.top)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left >= self.right || self.top >= self.bottom
+    }
+
+    /// Returns true if `point` is inside, the right and bottom edges are exclusive.
+    pub fn contains(&self, point: Offset) -> bool {
+        point.dx >= self.left
+            && point.dx < self.right
+            && point.dy >= self.top
+            && point.dy < self.bottom
+    }
+
+    pub fn translate(&self, offset: Offset) -> Self {
+        Self::from_ltrb(
+            self.left + offset.dx,
+            self.top + offset.dy,
+            self.right + offset.dx,
+            self.bottom + offset.dy,
+        )
+    }
+
+    /// Returns the overlapping area, empty if the rectangles don't overlap.
+    pub fn intersect(&self, other: &Rect) -> Self {
+        Self::from_ltrb(
+            self.left.max(other.left),
+            self.top.max(other.top),
+            self.right.min(other.right),
+            self.bottom.min(other.bottom),
+        )
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Self {
+        Self::from_ltrb(
+            self.left.min(other.left),
+            self.top.min(other.top),
+            self.right.max(other.right),
+            self.bottom.max(other.bottom),
+        )
+    }
+}
+
+/// Rectangle with rounded corners, each corner is a circular arc.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RRect {
+    pub rect: Rect,
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl RRect {
+    /// All corners rounded with `radius`.
+    pub fn from_rect_radius(rect: Rect, radius: f32) -> Self {
+        Self {
+            rect,
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    pub fn translate(&self, offset: Offset) -> Self {
+        Self {
+            rect: self.rect.translate(offset),
+            ..*self
+        }
+    }
+}
+
+/// 2D affine transform, maps `(x, y)` to `(a * x + c * y + e, b * x + d * y + f)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        e: 0.0,
+        f: 0.0,
+    };
+
+    pub fn translation(dx: f32, dy: f32) -> Self {
+        Self {
+            e: dx,
+            f: dy,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Self {
+            a: sx,
+            d: sy,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Clockwise rotation in radians, y axis points down.
+    pub fn rotation(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    /// Returns the transform applying `self` first, then `other`.
+    pub fn then(&self, other: &Transform) -> Self {
+        Self {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    /// Returns the transform applying `other` first, then `self`.
+    pub fn pre(&self, other: &Transform) -> Self {
+        other.then(self)
+    }
+
+    pub fn map_point(&self, point: Offset) -> Offset {
+        Offset::new(
+            self.a * point.dx + self.c * point.dy + self.e,
+            self.b * point.dx + self.d * point.dy + self.f,
+        )
+    }
+
+    /// Returns `None` if the transform is not invertible, e.g. scale by zero.
+    pub fn invert(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+
+        // Only a zero determinant is singular, tiny scales are still invertible. Subnormal
+        // determinants are rejected too, their reciprocal overflows.
+        if !det.is_normal() {
+            return None;
+        }
+
+        let a = self.d / det;
+        let b = -self.b / det;
+        let c = -self.c / det;
+        let d = self.a / det;
+
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            e: -(a * self.e + c * self.f),
+            f: -(b * self.e + d * self.f),
+        })
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Returns true if the transform only translates and scales, so rectangles stay axis aligned.
+    pub fn is_axis_aligned(&self) -> bool {
+        self.b == 0.0 && self.c == 0.0
+    }
+
+    /// Returns the bounding box of the transformed `rect`.
+    pub fn map_rect(&self, rect: &Rect) -> Rect {
+        let points = [
+            self.map_point(Offset::new(rect.left, rect.top)),
+            self.map_point(Offset::new(rect.right, rect.top)),
+            self.map_point(Offset::new(rect.right, rect.bottom)),
+            self.map_point(Offset::new(rect.left, rect.bottom)),
+        ];
+
+        points[1..].iter().fold(
+            Rect::from_ltrb(points[0].dx, points[0].dy, points[0].dx, points[0].dy),
+            |rect, point| {
+                Rect::from_ltrb(
+                    rect.left.min(point.dx),
+                    rect.top.min(point.dy),
+                    rect.right.max(point.dx),
+                    rect.bottom.max(point.dy),
+                )
+            },
+        )
+    }
+}
@@ -0,0 +1,217 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use super::{GestureRecognizer, PointerId};
+
+/// Recognizer competing in a gesture arena.
+pub type GestureArenaMember = Rc<RefCell<dyn GestureRecognizer>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GestureDisposition {
+    Accepted,
+    Rejected,
+}
+
+/// Decision of an arena delivered to a member, see [`GestureArenaManager`].
+pub type GestureArenaOutcome = (GestureArenaMember, PointerId, GestureDisposition);
+
+#[derive(Default)]
+struct GestureArena {
+    members: Vec<GestureArenaMember>,
+    /// Members can join until the arena is closed, after the pointer down is dispatched.
+    open: bool,
+    /// Sweep is deferred until released, e.g. while waiting for a double tap.
+    held: bool,
+    pending_sweep: bool,
+    /// Member which accepted while the arena was open, wins as soon as the arena is closed.
+    eager_winner: Option<GestureArenaMember>,
+}
+
+/// Resolves which recognizer handles the gesture of each pointer.
+///
+/// Every pointer down opens an arena joined by the recognizers tracking the pointer.
+/// The first member accepting wins and every other member is rejected. Members may also reject
+/// themselves, the last remaining member of a closed arena wins. When the pointer is released
+/// without winner, the arena is swept and the first member wins.
+///
+/// The manager never calls the members, it returns the outcomes to deliver instead.
+#[derive(Default)]
+pub struct GestureArenaManager {
+    arenas: HashMap<PointerId, GestureArena>,
+}
+
+impl std::fmt::Debug for GestureArenaManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GestureArenaManager")
+            .field("pointers", &self.arenas.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl GestureArenaManager {
+    /// Add `member` to the arena of `pointer`, the arena is created on demand.
+    pub fn add(&mut self, pointer: PointerId, member: GestureArenaMember) {
+        let arena = self.arenas.entry(pointer).or_insert_with(|| GestureArena {
+            open: true,
+            ..Default::default()
+        });
+
+        if arena.open && !arena.members.iter().any(|m| Rc::ptr_eq(m, &member)) {
+            arena.members.push(member);
+        }
+    }
+
+    /// Returns true if the arena of `pointer` is not resolved yet.
+    pub fn is_pending(&self, pointer: PointerId) -> bool {
+        self.arenas.contains_key(&pointer)
+    }
+
+    /// Prevent new members from joining, resolve the arena if it can be.
+    pub fn close(&mut self, pointer: PointerId) -> Vec<GestureArenaOutcome> {
+        let Some(arena) = self.arenas.get_mut(&pointer) else {
+            return vec![];
+        };
+
+        arena.open = false;
+
+        self.try_resolve(pointer)
+    }
+
+    /// Force resolution when the pointer is released, the first member wins.
+    ///
+    /// Held arenas are swept when released.
+    pub fn sweep(&mut self, pointer: PointerId) -> Vec<GestureArenaOutcome> {
+        let Some(arena) = self.arenas.get_mut(&pointer) else {
+            return vec![];
+        };
+
+        if arena.held {
+            arena.pending_sweep = true;
+
+            return vec![];
+        }
+
+        let arena = self.arenas.remove(&pointer).unwrap();
+
+        arena
+            .members
+            .into_iter()
+            .enumerate()
+            .map(|(index, member)| {
+                let disposition = if index == 0 {
+                    GestureDisposition::Accepted
+                } else {
+                    GestureDisposition::Rejected
+                };
+
+                (member, pointer, disposition)
+            })
+            .collect()
+    }
+
+    /// Defer the sweep of the arena of `pointer` until [`GestureArenaManager::release`].
+    pub fn hold(&mut self, pointer: PointerId) {
+        if let Some(arena) = self.arenas.get_mut(&pointer) {
+            arena.held = true;
+        }
+    }
+
+    /// Stop holding the arena of `pointer`, sweeping it if the sweep was deferred.
+    pub fn release(&mut self, pointer: PointerId) -> Vec<GestureArenaOutcome> {
+        let Some(arena) = self.arenas.get_mut(&pointer) else {
+            return vec![];
+        };
+
+        arena.held = false;
+
+        if arena.pending_sweep {
+            self.sweep(pointer)
+        } else {
+            vec![]
+        }
+    }
+
+    /// Handle `member` accepting or rejecting the gesture of `pointer`.
+    pub fn resolve(
+        &mut self,
+        pointer: PointerId,
+        member: &GestureArenaMember,
+        disposition: GestureDisposition,
+    ) -> Vec<GestureArenaOutcome> {
+        let Some(arena) = self.arenas.get_mut(&pointer) else {
+            return vec![];
+        };
+
+        let Some(index) = arena.members.iter().position(|m| Rc::ptr_eq(m, member)) else {
+            return vec![];
+        };
+
+        match disposition {
+            GestureDisposition::Rejected => {
+                let member = arena.members.remove(index);
+
+                if arena
+                    .eager_winner
+                    .as_ref()
+                    .is_some_and(|winner| Rc::ptr_eq(winner, &member))
+                {
+                    arena.eager_winner = None;
+                }
+
+                let mut outcomes = vec![(member, pointer, GestureDisposition::Rejected)];
+
+                if !arena.open {
+                    outcomes.extend(self.try_resolve(pointer));
+                }
+
+                outcomes
+            }
+            GestureDisposition::Accepted if arena.open => {
+                arena.eager_winner.get_or_insert_with(|| member.clone());
+
+                vec![]
+            }
+            GestureDisposition::Accepted => self.resolve_in_favor_of(pointer, member),
+        }
+    }
+
+    fn try_resolve(&mut self, pointer: PointerId) -> Vec<GestureArenaOutcome> {
+        let Some(arena) = self.arenas.get(&pointer) else {
+            return vec![];
+        };
+
+        if let Some(winner) = arena.eager_winner.clone() {
+            self.resolve_in_favor_of(pointer, &winner)
+        } else if arena.members.len() == 1 {
+            let member = arena.members[0].clone();
+
+            self.resolve_in_favor_of(pointer, &member)
+        } else if arena.members.is_empty() {
+            self.arenas.remove(&pointer);
+
+            vec![]
+        } else {
+            vec![]
+        }
+    }
+
+    fn resolve_in_favor_of(
+        &mut self,
+        pointer: PointerId,
+        winner: &GestureArenaMember,
+    ) -> Vec<GestureArenaOutcome> {
+        let Some(arena) = self.arenas.remove(&pointer) else {
+            return vec![];
+        };
+
+        let mut outcomes = arena
+            .members
+            .into_iter()
+            .filter(|member| !Rc::ptr_eq(member, winner))
+            .map(|member| (member, pointer, GestureDisposition::Rejected))
+            .collect::<Vec<_>>();
+
+        outcomes.push((winner.clone(), pointer, GestureDisposition::Accepted));
+
+        outcomes
+    }
+}
@@ -0,0 +1,214 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
+
+use crate::{
+    geometry::Transform,
+    render::{HitTestResult, RenderObject},
+};
+
+use super::{
+    GestureArenaManager, GestureArenaMember, GestureArenaOutcome, GestureContext,
+    GestureDisposition, GestureRecognizer, GestureRequest, PointerEvent, PointerEventKind,
+    PointerId,
+};
+
+/// Routes the events of each pointer to the recognizers tracking it and runs their arenas.
+///
+/// Render objects receive it in
+/// [`RenderObjectLifecycle::handle_event`](crate::render::RenderObjectLifecycle::handle_event)
+/// to add recognizers for a pointer down.
+#[derive(Debug, Default)]
+pub struct PointerRouter {
+    now: Duration,
+    arena: GestureArenaManager,
+    /// Recognizers tracking each pointer, with the transform into their local coordinate.
+    routes: HashMap<PointerId, Vec<(GestureArenaMember, Transform)>>,
+    /// Hit test path of each pointer down, events of the pointer are dispatched along it.
+    hit_tests: HashMap<PointerId, HitTestResult>,
+}
+
+impl PointerRouter {
+    /// Offer the pointer down `event` to `recognizer`, which tracks the pointer and joins its
+    /// arena if it accepts it.
+    pub fn add_recognizer(&mut self, recognizer: GestureArenaMember, event: &PointerEvent) {
+        if event.kind != PointerEventKind::Down {
+            return;
+        }
+
+        let mut context = GestureContext::new(self.now);
+
+        let tracked = recognizer.borrow_mut().add_pointer(&mut context, event);
+
+        if tracked {
+            self.arena.add(event.pointer, recognizer.clone());
+
+            self.routes
+                .entry(event.pointer)
+                .or_default()
+                .push((recognizer.clone(), event.transform));
+        }
+
+        self.apply(&recognizer, context);
+    }
+
+    /// Returns true if `pointer` is tracked by any recognizer.
+    pub fn is_tracked(&self, pointer: PointerId) -> bool {
+        self.routes
+            .get(&pointer)
+            .is_some_and(|routes| !routes.is_empty())
+    }
+
+    fn call<F>(&mut self, member: &GestureArenaMember, f: F)
+    where
+        F: FnOnce(&mut dyn GestureRecognizer, &mut GestureContext),
+    {
+        let mut context = GestureContext::new(self.now);
+
+        f(&mut *member.borrow_mut(), &mut context);
+
+        self.apply(member, context);
+    }
+
+    fn apply(&mut self, member: &GestureArenaMember, context: GestureContext) {
+        for request in context.requests {
+            match request {
+                GestureRequest::Resolve(pointer, disposition) => {
+                    let outcomes = self.arena.resolve(pointer, member, disposition);
+
+                    self.notify(outcomes);
+                }
+                GestureRequest::Hold(pointer) => self.arena.hold(pointer),
+                GestureRequest::Release(pointer) => {
+                    let outcomes = self.arena.release(pointer);
+
+                    self.notify(outcomes);
+                }
+                GestureRequest::StopTracking(pointer) => {
+                    if let Some(routes) = self.routes.get_mut(&pointer) {
+                        routes.retain(|(route, _)| !Rc::ptr_eq(route, member));
+
+                        if routes.is_empty() {
+                            self.routes.remove(&pointer);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn notify(&mut self, outcomes: Vec<GestureArenaOutcome>) {
+        for (member, pointer, disposition) in outcomes {
+            self.call(&member, |recognizer, context| match disposition {
+                GestureDisposition::Accepted => recognizer.accept_gesture(context, pointer),
+                GestureDisposition::Rejected => recognizer.reject_gesture(context, pointer),
+            });
+        }
+    }
+
+    /// Deliver `event` to the recognizers tracking its pointer.
+    fn route(&mut self, event: &PointerEvent) {
+        let routes = self.routes.get(&event.pointer).cloned().unwrap_or_default();
+
+        for (member, transform) in routes {
+            let event = event.transformed(transform);
+
+            self.call(&member, |recognizer, context| {
+                recognizer.handle_event(context, &event)
+            });
+        }
+    }
+
+    /// Recognizers tracking any pointer, each once.
+    fn tracking_members(&self) -> Vec<GestureArenaMember> {
+        let mut members: Vec<GestureArenaMember> = vec![];
+
+        for (member, _) in self.routes.values().flatten() {
+            if !members.iter().any(|m| Rc::ptr_eq(m, member)) {
+                members.push(member.clone());
+            }
+        }
+
+        members
+    }
+}
+
+/// Shared handle of the [`PointerRouter`] of a framework context.
+#[derive(Debug, Default, Clone)]
+pub struct GestureBinding(Rc<RefCell<PointerRouter>>);
+
+impl PartialEq for GestureBinding {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl GestureBinding {
+    /// Remember the hit test path of a pointer down.
+    pub fn add_hit_test(&self, pointer: PointerId, result: HitTestResult) {
+        self.0.borrow_mut().hit_tests.insert(pointer, result);
+    }
+
+    /// Returns the hit test path of the pointer down of `pointer`, `None` if it's not down.
+    pub fn hit_test_of(&self, pointer: PointerId) -> Option<HitTestResult> {
+        self.0.borrow().hit_tests.get(&pointer).cloned()
+    }
+
+    /// Returns true if `pointer` is tracked by any recognizer.
+    pub fn is_tracked(&self, pointer: PointerId) -> bool {
+        self.0.borrow().is_tracked(pointer)
+    }
+
+    /// Dispatch `event` to `targets` with their transforms, the deepest first, then to the
+    /// recognizers tracking the pointer.
+    ///
+    /// The arena of a pointer is closed after its down is dispatched and swept after its up.
+    /// Pointer downs are not routed, recognizers receive them in
+    /// [`GestureRecognizer::add_pointer`].
+    pub fn dispatch(&self, targets: &[(RenderObject, Transform)], event: PointerEvent) {
+        let mut router = self.0.borrow_mut();
+
+        router.now = event.timestamp;
+
+        for (render_object, transform) in targets {
+            render_object
+                .0
+                .borrow()
+                .lifecycle
+                .handle_event(&event.transformed(*transform), &mut router);
+        }
+
+        match event.kind {
+            PointerEventKind::Down => {
+                let outcomes = router.arena.close(event.pointer);
+
+                router.notify(outcomes);
+            }
+            PointerEventKind::Move => router.route(&event),
+            PointerEventKind::Up => {
+                router.route(&event);
+
+                let outcomes = router.arena.sweep(event.pointer);
+
+                router.notify(outcomes);
+                router.hit_tests.remove(&event.pointer);
+            }
+            PointerEventKind::Cancel => {
+                router.route(&event);
+                router.hit_tests.remove(&event.pointer);
+            }
+            PointerEventKind::Scroll(_) => {}
+        }
+    }
+
+    /// Advance the gesture clock to `now`, recognizers waiting for a deadline are notified.
+    pub fn advance(&self, now: Duration) {
+        let mut router = self.0.borrow_mut();
+
+        router.now = router.now.max(now);
+
+        for member in router.tracking_members() {
+            router.call(&member, |recognizer, context| {
+                recognizer.handle_timer(context)
+            });
+        }
+    }
+}
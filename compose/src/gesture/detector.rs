@@ -0,0 +1,298 @@
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+use crate::{
+    callback::Callback,
+    element::StatefulElement,
+    geometry::{Offset, Rect},
+    macos::{Render, State, Stateful},
+    render::{HitTestContext, RenderObjectLifecycle, RenderObjectUpdate},
+    view::{Configuration, IntoView, StatefulConfiguration, View},
+};
+
+use super::{
+    DoubleTapGestureRecognizer, DragEndDetails, DragStartDetails, DragUpdateDetails,
+    GestureArenaMember, LongPressDetails, LongPressGestureRecognizer, PanGestureRecognizer,
+    PointerEvent, PointerEventKind, PointerRouter, ScaleEndDetails, ScaleGestureRecognizer,
+    ScaleStartDetails, ScaleUpdateDetails, TapDetails, TapGestureRecognizer,
+};
+
+/// How a [`Listener`] behaves during hit testing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HitTestBehavior {
+    /// Hit only if a child is hit.
+    #[default]
+    DeferToChild,
+    /// Hit anywhere within its bounds, blocking the targets painted below.
+    Opaque,
+}
+
+type PointerHandlerFn = dyn Fn(&PointerEvent, &mut PointerRouter);
+
+/// Raw pointer event handler of [`Listener`], compared by identity like [`Callback`].
+#[derive(Clone)]
+pub struct PointerHandler(Rc<PointerHandlerFn>);
+
+impl PointerHandler {
+    pub fn new<F: Fn(&PointerEvent, &mut PointerRouter) + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+
+    pub fn call(&self, event: &PointerEvent, router: &mut PointerRouter) {
+        (self.0)(event, router)
+    }
+}
+
+impl PartialEq for PointerHandler {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for PointerHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PointerHandler({:p})", Rc::as_ptr(&self.0))
+    }
+}
+
+/// Calls `on_pointer` with every pointer event hitting `child`, in its local coordinate.
+#[derive(Debug, Clone, PartialEq, Render)]
+#[render(update_render_object)]
+pub struct Listener {
+    pub on_pointer: PointerHandler,
+    pub behavior: HitTestBehavior,
+    pub child: View,
+}
+
+impl Listener {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        RenderPointerListener {
+            on_pointer: self.on_pointer.clone(),
+            behavior: self.behavior,
+        }
+    }
+
+    /// Handler and behavior are only read by hit testing, nothing to relayout or repaint.
+    fn update_render_object(
+        &self,
+        render_object: &mut dyn RenderObjectLifecycle,
+    ) -> RenderObjectUpdate {
+        if let Some(render_object) = render_object.downcast_mut::<RenderPointerListener>() {
+            render_object.on_pointer = self.on_pointer.clone();
+            render_object.behavior = self.behavior;
+        }
+
+        RenderObjectUpdate::None
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![self.child.clone()]
+    }
+}
+
+#[derive(Debug)]
+pub struct RenderPointerListener {
+    pub on_pointer: PointerHandler,
+    pub behavior: HitTestBehavior,
+}
+
+impl RenderObjectLifecycle for RenderPointerListener {
+    fn hit_test(&self, context: &mut HitTestContext, position: Offset) -> bool {
+        if !Rect::from_offset_size(Offset::ZERO, context.size()).contains(position) {
+            return false;
+        }
+
+        let hit = context.hit_test_children(position) || self.hit_test_self(position);
+
+        if hit {
+            context.add_self();
+        }
+
+        hit
+    }
+
+    fn hit_test_self(&self, _position: Offset) -> bool {
+        self.behavior == HitTestBehavior::Opaque
+    }
+
+    fn handle_event(&self, event: &PointerEvent, router: &mut PointerRouter) {
+        self.on_pointer.call(event, router);
+    }
+}
+
+/// Recognizes gestures on `child` from the callbacks which are set.
+///
+/// One recognizer per gesture kind competes in the arena, e.g. with both `on_tap` and
+/// `on_double_tap`, a single tap is reported once the double tap timed out.
+#[derive(Debug, Default, Clone, PartialEq, Stateful)]
+pub struct GestureDetector {
+    pub on_tap_down: Option<Callback<TapDetails>>,
+    pub on_tap_up: Option<Callback<TapDetails>>,
+    pub on_tap: Option<Callback>,
+    pub on_tap_cancel: Option<Callback>,
+    pub on_double_tap: Option<Callback>,
+    pub on_long_press: Option<Callback<LongPressDetails>>,
+    pub on_long_press_up: Option<Callback>,
+    pub on_pan_start: Option<Callback<DragStartDetails>>,
+    pub on_pan_update: Option<Callback<DragUpdateDetails>>,
+    pub on_pan_end: Option<Callback<DragEndDetails>>,
+    pub on_scale_start: Option<Callback<ScaleStartDetails>>,
+    pub on_scale_update: Option<Callback<ScaleUpdateDetails>>,
+    pub on_scale_end: Option<Callback<ScaleEndDetails>>,
+    pub behavior: HitTestBehavior,
+    pub child: View,
+}
+
+impl GestureDetector {
+    fn create_state(&self) -> GestureDetectorState {
+        let recognizers = Rc::new(RefCell::new(vec![]));
+
+        let members = recognizers.clone();
+
+        let on_pointer = PointerHandler::new(move |event, router| {
+            if event.kind != PointerEventKind::Down {
+                return;
+            }
+
+            let members: Vec<GestureArenaMember> = members.borrow().clone();
+
+            for member in members {
+                router.add_recognizer(member, event);
+            }
+        });
+
+        let mut state = GestureDetectorState {
+            on_pointer,
+            recognizers,
+            tap: None,
+            double_tap: None,
+            long_press: None,
+            pan: None,
+            scale: None,
+        };
+
+        state.sync_recognizers(self);
+
+        state
+    }
+}
+
+#[derive(Debug, State)]
+#[state(did_update_config)]
+pub struct GestureDetectorState {
+    /// Handler of the listener, kept across builds so the listener is not updated needlessly.
+    on_pointer: PointerHandler,
+    /// Recognizers offered every pointer down, shared with `on_pointer`.
+    recognizers: Rc<RefCell<Vec<GestureArenaMember>>>,
+    tap: Option<Rc<RefCell<TapGestureRecognizer>>>,
+    double_tap: Option<Rc<RefCell<DoubleTapGestureRecognizer>>>,
+    long_press: Option<Rc<RefCell<LongPressGestureRecognizer>>>,
+    pan: Option<Rc<RefCell<PanGestureRecognizer>>>,
+    scale: Option<Rc<RefCell<ScaleGestureRecognizer>>>,
+}
+
+/// Keep the recognizer in `slot` if `enabled`, creating it on demand, so gestures in progress
+/// survive rebuilds. Returns the recognizer to update its callbacks.
+fn sync_slot<R: Default>(slot: &mut Option<Rc<RefCell<R>>>, enabled: bool) -> Option<&RefCell<R>> {
+    if !enabled {
+        *slot = None;
+    }
+
+    if enabled && slot.is_none() {
+        *slot = Some(Default::default());
+    }
+
+    slot.as_deref()
+}
+
+impl GestureDetectorState {
+    fn sync_recognizers(&mut self, config: &GestureDetector) {
+        if let Some(tap) = sync_slot(
+            &mut self.tap,
+            config.on_tap_down.is_some()
+                || config.on_tap_up.is_some()
+                || config.on_tap.is_some()
+                || config.on_tap_cancel.is_some(),
+        ) {
+            let mut tap = tap.borrow_mut();
+
+            tap.on_tap_down = config.on_tap_down.clone();
+            tap.on_tap_up = config.on_tap_up.clone();
+            tap.on_tap = config.on_tap.clone();
+            tap.on_tap_cancel = config.on_tap_cancel.clone();
+        }
+
+        if let Some(double_tap) = sync_slot(&mut self.double_tap, config.on_double_tap.is_some()) {
+            double_tap.borrow_mut().on_double_tap = config.on_double_tap.clone();
+        }
+
+        if let Some(long_press) = sync_slot(
+            &mut self.long_press,
+            config.on_long_press.is_some() || config.on_long_press_up.is_some(),
+        ) {
+            let mut long_press = long_press.borrow_mut();
+
+            long_press.on_long_press = config.on_long_press.clone();
+            long_press.on_long_press_up = config.on_long_press_up.clone();
+        }
+
+        if let Some(pan) = sync_slot(
+            &mut self.pan,
+            config.on_pan_start.is_some()
+                || config.on_pan_update.is_some()
+                || config.on_pan_end.is_some(),
+        ) {
+            let mut pan = pan.borrow_mut();
+
+            pan.on_pan_start = config.on_pan_start.clone();
+            pan.on_pan_update = config.on_pan_update.clone();
+            pan.on_pan_end = config.on_pan_end.clone();
+        }
+
+        if let Some(scale) = sync_slot(
+            &mut self.scale,
+            config.on_scale_start.is_some()
+                || config.on_scale_update.is_some()
+                || config.on_scale_end.is_some(),
+        ) {
+            let mut scale = scale.borrow_mut();
+
+            scale.on_scale_start = config.on_scale_start.clone();
+            scale.on_scale_update = config.on_scale_update.clone();
+            scale.on_scale_end = config.on_scale_end.clone();
+        }
+
+        let mut recognizers: Vec<GestureArenaMember> = vec![];
+
+        recognizers.extend(self.tap.clone().map(|r| r as GestureArenaMember));
+        recognizers.extend(self.double_tap.clone().map(|r| r as GestureArenaMember));
+        recognizers.extend(self.long_press.clone().map(|r| r as GestureArenaMember));
+        recognizers.extend(self.pan.clone().map(|r| r as GestureArenaMember));
+        recognizers.extend(self.scale.clone().map(|r| r as GestureArenaMember));
+
+        *self.recognizers.borrow_mut() = recognizers;
+    }
+
+    fn did_update_config(
+        &mut self,
+        element: &StatefulElement,
+        _old: &Configuration<dyn StatefulConfiguration>,
+    ) {
+        let config = element.config.borrow().clone();
+
+        config.downcast(|config: &GestureDetector| self.sync_recognizers(config));
+    }
+
+    fn build(&self, element: &StatefulElement) -> impl IntoView {
+        let (behavior, child) = element
+            .config
+            .borrow()
+            .downcast(|config: &GestureDetector| (config.behavior, config.child.clone()))
+            .unwrap_or_default();
+
+        Listener {
+            on_pointer: self.on_pointer.clone(),
+            behavior,
+            child,
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use crate::{callback::Callback, geometry::Offset};
+
+use super::{
+    Buttons, GestureContext, GestureRecognizer, PointerEvent, PointerEventKind, PointerId,
+    TOUCH_SLOP,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragStartDetails {
+    /// Position of the pointer down in the root coordinate.
+    pub position: Offset,
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragUpdateDetails {
+    /// Movement since the previous update.
+    pub delta: Offset,
+    pub position: Offset,
+    pub local_position: Offset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragEndDetails {
+    /// Velocity in logical pixels per second when the pointer went up.
+    pub velocity: Offset,
+}
+
+/// Velocity between two events, zero if they happened at the same time.
+pub(crate) fn velocity(from: (Offset, Duration), to: (Offset, Duration)) -> Offset {
+    let seconds = to.1.saturating_sub(from.1).as_secs_f32();
+
+    if seconds > 0.0 {
+        (to.0 - from.0).scale(1.0 / seconds)
+    } else {
+        Offset::ZERO
+    }
+}
+
+/// Recognizes a primary button moving beyond [`TOUCH_SLOP`] in any direction.
+///
+/// Once accepted, `on_pan_start` is called at the down position and the movement so far is
+/// reported by the first `on_pan_update`.
+#[derive(Debug, Default)]
+pub struct PanGestureRecognizer {
+    pub on_pan_start: Option<Callback<DragStartDetails>>,
+    pub on_pan_update: Option<Callback<DragUpdateDetails>>,
+    pub on_pan_end: Option<Callback<DragEndDetails>>,
+    pub on_pan_cancel: Option<Callback>,
+    down: Option<PointerEvent>,
+    /// Last reported event.
+    last: Option<PointerEvent>,
+    velocity: Offset,
+    accepted: bool,
+}
+
+impl PanGestureRecognizer {
+    fn reset(&mut self, context: &mut GestureContext) {
+        if let Some(down) = self.down.take() {
+            context.stop_tracking(down.pointer);
+        }
+
+        self.last = None;
+        self.velocity = Offset::ZERO;
+        self.accepted = false;
+    }
+
+    fn update(&mut self, event: &PointerEvent) {
+        let Some(last) = self.last.replace(event.clone()) else {
+            return;
+        };
+
+        self.velocity = velocity(
+            (last.position, last.timestamp),
+            (event.position, event.timestamp),
+        );
+
+        if let Some(on_pan_update) = &self.on_pan_update {
+            on_pan_update.call(DragUpdateDetails {
+                delta: event.position - last.position,
+                position: event.position,
+                local_position: event.local_position(),
+            });
+        }
+    }
+}
+
+impl GestureRecognizer for PanGestureRecognizer {
+    fn add_pointer(&mut self, _context: &mut GestureContext, event: &PointerEvent) -> bool {
+        if self.down.is_some() || !event.buttons.contains(Buttons::PRIMARY) {
+            return false;
+        }
+
+        self.down = Some(event.clone());
+        self.last = Some(event.clone());
+
+        true
+    }
+
+    fn handle_event(&mut self, context: &mut GestureContext, event: &PointerEvent) {
+        let Some(down) = &self.down else {
+            return;
+        };
+
+        match event.kind {
+            PointerEventKind::Move if self.accepted => self.update(event),
+            PointerEventKind::Move if (event.position - down.position).distance() > TOUCH_SLOP => {
+                // Keep the pending movement, it's reported once accepted.
+                let pointer = down.pointer;
+
+                self.velocity = velocity(
+                    (down.position, down.timestamp),
+                    (event.position, event.timestamp),
+                );
+                self.last = Some(event.clone());
+
+                context.accept(pointer);
+            }
+            PointerEventKind::Up if self.accepted => {
+                if let Some(on_pan_end) = &self.on_pan_end {
+                    on_pan_end.call(DragEndDetails {
+                        velocity: self.velocity,
+                    });
+                }
+
+                self.reset(context);
+            }
+            PointerEventKind::Up | PointerEventKind::Cancel => {
+                if self.accepted {
+                    if let Some(on_pan_cancel) = &self.on_pan_cancel {
+                        on_pan_cancel.call(());
+                    }
+                } else {
+                    context.reject(event.pointer);
+                }
+
+                self.reset(context);
+            }
+            _ => {}
+        }
+    }
+
+    fn accept_gesture(&mut self, _context: &mut GestureContext, pointer: PointerId) {
+        let Some(down) = self.down.clone() else {
+            return;
+        };
+
+        if down.pointer != pointer || self.accepted {
+            return;
+        }
+
+        self.accepted = true;
+
+        if let Some(on_pan_start) = &self.on_pan_start {
+            on_pan_start.call(DragStartDetails {
+                position: down.position,
+                local_position: down.local_position(),
+            });
+        }
+
+        // Report the movement made while the arena was undecided.
+        if let Some(last) = self.last.replace(down.clone()) {
+            if last.position != down.position {
+                let velocity = self.velocity;
+
+                self.update(&last);
+                self.velocity = velocity;
+            }
+        }
+    }
+
+    fn reject_gesture(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        if self.down.as_ref().map(|down| down.pointer) == Some(pointer) {
+            self.reset(context);
+        }
+    }
+}
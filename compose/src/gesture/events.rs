@@ -0,0 +1,129 @@
+use std::{ops::BitOr, time::Duration};
+
+use crate::geometry::{Offset, Transform};
+
+/// Identifies one contact from down to up or cancel, must not be reused by later contacts.
+pub type PointerId = u32;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PointerDeviceKind {
+    #[default]
+    Touch,
+    Mouse,
+    Stylus,
+    Trackpad,
+}
+
+/// Bit set of pressed buttons.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Buttons(pub u32);
+
+impl Buttons {
+    pub const NONE: Buttons = Buttons(0);
+    /// Touch contact, stylus contact or left mouse button.
+    pub const PRIMARY: Buttons = Buttons(1);
+    pub const SECONDARY: Buttons = Buttons(2);
+    pub const MIDDLE: Buttons = Buttons(4);
+
+    pub fn contains(&self, buttons: Buttons) -> bool {
+        self.0 & buttons.0 == buttons.0
+    }
+}
+
+impl BitOr for Buttons {
+    type Output = Buttons;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Buttons(self.0 | rhs.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerEventKind {
+    Down,
+    Move,
+    Up,
+    /// The contact is lost, e.g. the touch was taken over by the system.
+    Cancel,
+    /// Scroll wheel or trackpad scroll by the delta.
+    Scroll(Offset),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerEvent {
+    pub kind: PointerEventKind,
+    pub pointer: PointerId,
+    pub device: PointerDeviceKind,
+    pub buttons: Buttons,
+    /// Position in the root coordinate.
+    pub position: Offset,
+    /// Time since an arbitrary epoch, used to measure gesture durations.
+    pub timestamp: Duration,
+    /// Maps [`PointerEvent::position`] into the local coordinate of the receiver.
+    pub transform: Transform,
+}
+
+impl PointerEvent {
+    /// Create a touch event with primary button pressed at timestamp zero.
+    pub fn new(kind: PointerEventKind, pointer: PointerId, position: Offset) -> Self {
+        Self {
+            kind,
+            pointer,
+            device: PointerDeviceKind::Touch,
+            buttons: Buttons::PRIMARY,
+            position,
+            timestamp: Duration::ZERO,
+            transform: Transform::IDENTITY,
+        }
+    }
+
+    pub fn down(pointer: PointerId, position: Offset) -> Self {
+        Self::new(PointerEventKind::Down, pointer, position)
+    }
+
+    pub fn moved(pointer: PointerId, position: Offset) -> Self {
+        Self::new(PointerEventKind::Move, pointer, position)
+    }
+
+    pub fn up(pointer: PointerId, position: Offset) -> Self {
+        Self::new(PointerEventKind::Up, pointer, position).with_buttons(Buttons::NONE)
+    }
+
+    pub fn cancel(pointer: PointerId, position: Offset) -> Self {
+        Self::new(PointerEventKind::Cancel, pointer, position).with_buttons(Buttons::NONE)
+    }
+
+    pub fn scroll(pointer: PointerId, position: Offset, delta: Offset) -> Self {
+        Self::new(PointerEventKind::Scroll(delta), pointer, position)
+            .with_device(PointerDeviceKind::Mouse)
+            .with_buttons(Buttons::NONE)
+    }
+
+    pub fn with_device(mut self, device: PointerDeviceKind) -> Self {
+        self.device = device;
+        self
+    }
+
+    pub fn with_buttons(mut self, buttons: Buttons) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: Duration) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Returns the event delivered to a receiver which local coordinate is mapped by `transform`.
+    pub fn transformed(&self, transform: Transform) -> Self {
+        Self {
+            transform,
+            ..self.clone()
+        }
+    }
+
+    /// Position in the local coordinate of the receiver.
+    pub fn local_position(&self) -> Offset {
+        self.transform.map_point(self.position)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{callback::Callback, geometry::Offset};
+
+use super::{
+    Buttons, GestureContext, GestureRecognizer, PointerEvent, PointerEventKind, PointerId,
+    LONG_PRESS_TIMEOUT, TOUCH_SLOP,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPressDetails {
+    /// Position of the pointer down in the root coordinate.
+    pub position: Offset,
+    pub local_position: Offset,
+}
+
+/// Recognizes a primary button held down for [`LONG_PRESS_TIMEOUT`] without moving beyond
+/// [`TOUCH_SLOP`].
+///
+/// The deadline is checked when the time advances, see
+/// [`FrameworkContext::advance_gestures`](crate::framework::FrameworkContext::advance_gestures).
+#[derive(Debug, Default)]
+pub struct LongPressGestureRecognizer {
+    pub on_long_press: Option<Callback<LongPressDetails>>,
+    /// Called when the pointer goes up after the long press was recognized.
+    pub on_long_press_up: Option<Callback>,
+    down: Option<PointerEvent>,
+    /// Won the arena, possibly before the deadline as its only member.
+    won: bool,
+    /// The long press was recognized and `on_long_press` called.
+    recognized: bool,
+}
+
+impl LongPressGestureRecognizer {
+    fn reset(&mut self, context: &mut GestureContext) {
+        if let Some(down) = self.down.take() {
+            if !self.won {
+                context.reject(down.pointer);
+            }
+
+            context.stop_tracking(down.pointer);
+        }
+
+        self.won = false;
+        self.recognized = false;
+    }
+
+    /// Call `on_long_press` once both the arena is won and the deadline passed.
+    fn check_recognized(&mut self, context: &mut GestureContext) {
+        let Some(down) = &self.down else {
+            return;
+        };
+
+        if !self.won
+            || self.recognized
+            || context.now().saturating_sub(down.timestamp) < LONG_PRESS_TIMEOUT
+        {
+            return;
+        }
+
+        self.recognized = true;
+
+        if let Some(on_long_press) = &self.on_long_press {
+            on_long_press.call(LongPressDetails {
+                position: down.position,
+                local_position: down.local_position(),
+            });
+        }
+    }
+}
+
+impl GestureRecognizer for LongPressGestureRecognizer {
+    fn add_pointer(&mut self, _context: &mut GestureContext, event: &PointerEvent) -> bool {
+        if self.down.is_some() || !event.buttons.contains(Buttons::PRIMARY) {
+            return false;
+        }
+
+        self.down = Some(event.clone());
+
+        true
+    }
+
+    fn handle_event(&mut self, context: &mut GestureContext, event: &PointerEvent) {
+        let Some(down) = &self.down else {
+            return;
+        };
+
+        match event.kind {
+            PointerEventKind::Move
+                if !self.recognized && (event.position - down.position).distance() > TOUCH_SLOP =>
+            {
+                self.reset(context);
+            }
+            PointerEventKind::Up => {
+                if self.recognized {
+                    if let Some(on_long_press_up) = &self.on_long_press_up {
+                        on_long_press_up.call(());
+                    }
+                } else {
+                    // Released before the deadline, even if the arena was won.
+                    self.won = false;
+                }
+
+                self.reset(context);
+            }
+            PointerEventKind::Cancel => self.reset(context),
+            _ => {}
+        }
+    }
+
+    fn accept_gesture(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        if self.down.as_ref().map(|down| down.pointer) == Some(pointer) {
+            self.won = true;
+
+            self.check_recognized(context);
+        }
+    }
+
+    fn reject_gesture(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        if self.down.as_ref().map(|down| down.pointer) == Some(pointer) {
+            self.reset(context);
+        }
+    }
+
+    fn handle_timer(&mut self, context: &mut GestureContext) {
+        let Some(down) = &self.down else {
+            return;
+        };
+
+        if self.recognized || context.now().saturating_sub(down.timestamp) < LONG_PRESS_TIMEOUT {
+            return;
+        }
+
+        if self.won {
+            self.check_recognized(context);
+        } else {
+            context.accept(down.pointer);
+        }
+    }
+}
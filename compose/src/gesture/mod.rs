@@ -0,0 +1,26 @@
+mod events;
+pub use events::*;
+
+mod arena;
+pub use arena::*;
+
+mod recognizer;
+pub use recognizer::*;
+
+mod tap;
+pub use tap::*;
+
+mod long_press;
+pub use long_press::*;
+
+mod drag;
+pub use drag::*;
+
+mod scale;
+pub use scale::*;
+
+mod binding;
+pub use binding::*;
+
+mod detector;
+pub use detector::*;
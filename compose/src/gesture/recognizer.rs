@@ -0,0 +1,102 @@
+use std::{fmt::Debug, time::Duration};
+
+use super::{GestureDisposition, PointerEvent, PointerId};
+
+/// Distance a touch can move before it's no longer a tap.
+pub const TOUCH_SLOP: f32 = 18.0;
+
+/// Maximum duration between the first up and the second down of a double tap.
+pub const DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Maximum distance between the first and the second down of a double tap.
+pub const DOUBLE_TAP_SLOP: f32 = 100.0;
+
+/// Duration a pointer must stay down to become a long press.
+pub const LONG_PRESS_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Change of the average distance of the pointers to their center before it's a scale.
+pub const SCALE_SLOP: f32 = 18.0;
+
+/// Request of a recognizer, applied by the router after the recognizer returns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum GestureRequest {
+    Resolve(PointerId, GestureDisposition),
+    Hold(PointerId),
+    Release(PointerId),
+    StopTracking(PointerId),
+}
+
+/// Access to the gesture arena and the clock in [`GestureRecognizer`] methods.
+///
+/// Requests are queued and applied once the recognizer returns, so the arena can call back
+/// into the same recognizer.
+#[derive(Debug)]
+pub struct GestureContext {
+    now: Duration,
+    pub(crate) requests: Vec<GestureRequest>,
+}
+
+impl GestureContext {
+    pub(crate) fn new(now: Duration) -> Self {
+        Self {
+            now,
+            requests: vec![],
+        }
+    }
+
+    /// Timestamp of the event being handled, or the time advanced to for timers.
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// Claim the gesture of `pointer`, other members of its arena are rejected.
+    pub fn accept(&mut self, pointer: PointerId) {
+        self.requests.push(GestureRequest::Resolve(
+            pointer,
+            GestureDisposition::Accepted,
+        ));
+    }
+
+    /// Leave the arena of `pointer`.
+    pub fn reject(&mut self, pointer: PointerId) {
+        self.requests.push(GestureRequest::Resolve(
+            pointer,
+            GestureDisposition::Rejected,
+        ));
+    }
+
+    /// Defer the sweep of the arena of `pointer` after it's released, see
+    /// [`GestureArenaManager::hold`](super::GestureArenaManager::hold).
+    pub fn hold(&mut self, pointer: PointerId) {
+        self.requests.push(GestureRequest::Hold(pointer));
+    }
+
+    pub fn release(&mut self, pointer: PointerId) {
+        self.requests.push(GestureRequest::Release(pointer));
+    }
+
+    /// Stop receiving events and timers for `pointer`.
+    pub fn stop_tracking(&mut self, pointer: PointerId) {
+        self.requests.push(GestureRequest::StopTracking(pointer));
+    }
+}
+
+/// Recognizes a gesture from the events of the pointers it tracks, competing with other
+/// recognizers in the gesture arena of each pointer.
+pub trait GestureRecognizer: Debug {
+    /// Called with the pointer down hitting the owner of this recognizer, returns true to track
+    /// the pointer and join its arena.
+    fn add_pointer(&mut self, context: &mut GestureContext, event: &PointerEvent) -> bool;
+
+    /// Called with every following event of a tracked pointer, except scroll events.
+    fn handle_event(&mut self, context: &mut GestureContext, event: &PointerEvent);
+
+    /// Called when this recognizer wins the arena of `pointer`.
+    fn accept_gesture(&mut self, context: &mut GestureContext, pointer: PointerId);
+
+    /// Called when this recognizer loses or leaves the arena of `pointer`.
+    fn reject_gesture(&mut self, context: &mut GestureContext, pointer: PointerId);
+
+    /// Called when the time advances while pointers are tracked, e.g. to detect long presses.
+    fn handle_timer(&mut self, _context: &mut GestureContext) {}
+}
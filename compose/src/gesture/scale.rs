@@ -0,0 +1,221 @@
+use crate::{callback::Callback, geometry::Offset};
+
+use super::{
+    drag::velocity, GestureContext, GestureRecognizer, PointerEvent, PointerEventKind, PointerId,
+    SCALE_SLOP, TOUCH_SLOP,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleStartDetails {
+    /// Center of the pointers in the root coordinate.
+    pub focal_point: Offset,
+    pub pointer_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleUpdateDetails {
+    pub focal_point: Offset,
+    /// Movement of the focal point since the previous update.
+    pub focal_point_delta: Offset,
+    /// Scale relative to the start of the gesture, 1.0 with a single pointer.
+    pub scale: f32,
+    pub pointer_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleEndDetails {
+    /// Velocity of the focal point in logical pixels per second.
+    pub velocity: Offset,
+}
+
+/// Recognizes pointers moving their center beyond [`TOUCH_SLOP`] or changing their average
+/// distance to the center beyond [`SCALE_SLOP`], e.g. a pinch.
+///
+/// A single pointer can pan, the scale is continuous when pointers are added or removed.
+#[derive(Debug, Default)]
+pub struct ScaleGestureRecognizer {
+    pub on_scale_start: Option<Callback<ScaleStartDetails>>,
+    pub on_scale_update: Option<Callback<ScaleUpdateDetails>>,
+    pub on_scale_end: Option<Callback<ScaleEndDetails>>,
+    pointers: Vec<PointerEvent>,
+    /// Focal point and span when the pointers last changed.
+    initial_focal_point: Offset,
+    initial_span: f32,
+    /// Scale when the pointers last changed.
+    base_scale: f32,
+    scale: f32,
+    focal_point: Offset,
+    velocity: Offset,
+    started: bool,
+}
+
+impl ScaleGestureRecognizer {
+    fn focal_point(&self) -> Offset {
+        let count = self.pointers.len().max(1) as f32;
+
+        self.pointers
+            .iter()
+            .fold(Offset::ZERO, |sum, event| sum + event.position)
+            .scale(1.0 / count)
+    }
+
+    /// Average distance of the pointers to the focal point.
+    fn span(&self) -> f32 {
+        let focal_point = self.focal_point();
+        let count = self.pointers.len().max(1) as f32;
+
+        self.pointers
+            .iter()
+            .map(|event| (event.position - focal_point).distance())
+            .sum::<f32>()
+            / count
+    }
+
+    /// Start measuring from the current pointers, keeping the scale reached so far.
+    fn rebase(&mut self) {
+        self.initial_focal_point = self.focal_point();
+        self.focal_point = self.initial_focal_point;
+        self.initial_span = self.span();
+        self.base_scale = self.scale;
+    }
+
+    fn current_scale(&self) -> f32 {
+        if self.initial_span > 0.0 {
+            self.base_scale * self.span() / self.initial_span
+        } else {
+            self.base_scale
+        }
+    }
+
+    fn remove_pointer(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        let Some(index) = self
+            .pointers
+            .iter()
+            .position(|event| event.pointer == pointer)
+        else {
+            return;
+        };
+
+        self.pointers.remove(index);
+
+        context.stop_tracking(pointer);
+
+        if !self.started {
+            context.reject(pointer);
+        }
+
+        if self.pointers.is_empty() {
+            if self.started {
+                if let Some(on_scale_end) = &self.on_scale_end {
+                    on_scale_end.call(ScaleEndDetails {
+                        velocity: self.velocity,
+                    });
+                }
+            }
+
+            self.started = false;
+            self.velocity = Offset::ZERO;
+        } else {
+            self.rebase();
+        }
+    }
+}
+
+impl GestureRecognizer for ScaleGestureRecognizer {
+    fn add_pointer(&mut self, _context: &mut GestureContext, event: &PointerEvent) -> bool {
+        if self.pointers.is_empty() {
+            self.scale = 1.0;
+        }
+
+        self.pointers.push(event.clone());
+
+        self.rebase();
+
+        true
+    }
+
+    fn handle_event(&mut self, context: &mut GestureContext, event: &PointerEvent) {
+        match event.kind {
+            PointerEventKind::Move => {
+                let Some(pointer) = self
+                    .pointers
+                    .iter_mut()
+                    .find(|pointer| pointer.pointer == event.pointer)
+                else {
+                    return;
+                };
+
+                let previous = (self.focal_point, pointer.timestamp);
+
+                *pointer = event.clone();
+
+                let focal_point = self.focal_point();
+
+                self.velocity = velocity(previous, (focal_point, event.timestamp));
+
+                if self.started {
+                    self.scale = self.current_scale();
+
+                    let delta = focal_point - self.focal_point;
+
+                    self.focal_point = focal_point;
+
+                    if let Some(on_scale_update) = &self.on_scale_update {
+                        on_scale_update.call(ScaleUpdateDetails {
+                            focal_point,
+                            focal_point_delta: delta,
+                            scale: self.scale,
+                            pointer_count: self.pointers.len(),
+                        });
+                    }
+                } else if (focal_point - self.initial_focal_point).distance() > TOUCH_SLOP
+                    || (self.span() - self.initial_span).abs() > SCALE_SLOP
+                {
+                    for pointer in self.pointers.iter().map(|event| event.pointer) {
+                        context.accept(pointer);
+                    }
+                }
+            }
+            PointerEventKind::Up | PointerEventKind::Cancel => {
+                self.remove_pointer(context, event.pointer)
+            }
+            _ => {}
+        }
+    }
+
+    fn accept_gesture(&mut self, _context: &mut GestureContext, pointer: PointerId) {
+        if self.started || !self.pointers.iter().any(|event| event.pointer == pointer) {
+            return;
+        }
+
+        self.started = true;
+
+        if let Some(on_scale_start) = &self.on_scale_start {
+            on_scale_start.call(ScaleStartDetails {
+                focal_point: self.initial_focal_point,
+                pointer_count: self.pointers.len(),
+            });
+        }
+
+        // Report the change made while the arena was undecided.
+        let focal_point = self.focal_point();
+
+        if focal_point != self.initial_focal_point || self.current_scale() != self.base_scale {
+            self.scale = self.current_scale();
+            self.focal_point = focal_point;
+
+            if let Some(on_scale_update) = &self.on_scale_update {
+                on_scale_update.call(ScaleUpdateDetails {
+                    focal_point,
+                    focal_point_delta: focal_point - self.initial_focal_point,
+                    scale: self.scale,
+                    pointer_count: self.pointers.len(),
+                });
+            }
+        }
+    }
+
+    fn reject_gesture(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        self.remove_pointer(context, pointer);
+    }
+}
@@ -0,0 +1,261 @@
+use std::time::Duration;
+
+use crate::{callback::Callback, geometry::Offset};
+
+use super::{
+    GestureContext, GestureRecognizer, PointerDeviceKind, PointerEvent, PointerEventKind,
+    PointerId, DOUBLE_TAP_SLOP, DOUBLE_TAP_TIMEOUT, TOUCH_SLOP,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TapDetails {
+    /// Position in the root coordinate.
+    pub position: Offset,
+    pub local_position: Offset,
+    pub device: PointerDeviceKind,
+}
+
+impl From<&PointerEvent> for TapDetails {
+    fn from(event: &PointerEvent) -> Self {
+        Self {
+            position: event.position,
+            local_position: event.local_position(),
+            device: event.device,
+        }
+    }
+}
+
+/// Recognizes a primary button down and up without moving beyond [`TOUCH_SLOP`].
+///
+/// `on_tap_down` is called when the pointer goes down, then either `on_tap_up` and `on_tap`
+/// if the tap wins, or `on_tap_cancel`.
+#[derive(Debug, Default)]
+pub struct TapGestureRecognizer {
+    pub on_tap_down: Option<Callback<TapDetails>>,
+    pub on_tap_up: Option<Callback<TapDetails>>,
+    pub on_tap: Option<Callback>,
+    pub on_tap_cancel: Option<Callback>,
+    down: Option<PointerEvent>,
+    up: Option<PointerEvent>,
+    won: bool,
+}
+
+impl TapGestureRecognizer {
+    fn pointer(&self) -> Option<PointerId> {
+        self.down.as_ref().map(|down| down.pointer)
+    }
+
+    fn check_up(&mut self, context: &mut GestureContext) {
+        if !self.won {
+            return;
+        }
+
+        let Some(up) = self.up.take() else {
+            return;
+        };
+
+        if let Some(on_tap_up) = &self.on_tap_up {
+            on_tap_up.call((&up).into());
+        }
+
+        if let Some(on_tap) = &self.on_tap {
+            on_tap.call(());
+        }
+
+        self.reset(context);
+    }
+
+    fn cancel(&mut self, context: &mut GestureContext) {
+        if let Some(on_tap_cancel) = &self.on_tap_cancel {
+            on_tap_cancel.call(());
+        }
+
+        self.reset(context);
+    }
+
+    fn reset(&mut self, context: &mut GestureContext) {
+        if let Some(pointer) = self.pointer() {
+            context.stop_tracking(pointer);
+        }
+
+        self.down = None;
+        self.up = None;
+        self.won = false;
+    }
+}
+
+impl GestureRecognizer for TapGestureRecognizer {
+    fn add_pointer(&mut self, _context: &mut GestureContext, event: &PointerEvent) -> bool {
+        if self.down.is_some() || !event.buttons.contains(super::Buttons::PRIMARY) {
+            return false;
+        }
+
+        if let Some(on_tap_down) = &self.on_tap_down {
+            on_tap_down.call(event.into());
+        }
+
+        self.down = Some(event.clone());
+
+        true
+    }
+
+    fn handle_event(&mut self, context: &mut GestureContext, event: &PointerEvent) {
+        let Some(down) = &self.down else {
+            return;
+        };
+
+        match event.kind {
+            PointerEventKind::Move if (event.position - down.position).distance() > TOUCH_SLOP => {
+                context.reject(event.pointer);
+
+                self.cancel(context);
+            }
+            PointerEventKind::Up => {
+                self.up = Some(event.clone());
+
+                self.check_up(context);
+            }
+            PointerEventKind::Cancel => {
+                context.reject(event.pointer);
+
+                self.cancel(context);
+            }
+            _ => {}
+        }
+    }
+
+    fn accept_gesture(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        if self.pointer() == Some(pointer) {
+            self.won = true;
+
+            self.check_up(context);
+        }
+    }
+
+    fn reject_gesture(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        if self.pointer() == Some(pointer) {
+            self.cancel(context);
+        }
+    }
+}
+
+/// Recognizes two taps in a row within [`DOUBLE_TAP_TIMEOUT`] and [`DOUBLE_TAP_SLOP`].
+///
+/// The arena of the first tap is held until the second tap or the timeout, so a competing
+/// [`TapGestureRecognizer`] only wins once the double tap gave up.
+#[derive(Debug, Default)]
+pub struct DoubleTapGestureRecognizer {
+    pub on_double_tap: Option<Callback>,
+    first: Option<PointerEvent>,
+    /// Time the first pointer went up.
+    first_up: Option<Duration>,
+    second: Option<PointerEvent>,
+}
+
+impl DoubleTapGestureRecognizer {
+    fn reset(&mut self, context: &mut GestureContext) {
+        for pointer in [self.first.take(), self.second.take()]
+            .into_iter()
+            .flatten()
+            .map(|event| event.pointer)
+        {
+            context.reject(pointer);
+            context.release(pointer);
+            context.stop_tracking(pointer);
+        }
+
+        self.first_up = None;
+    }
+
+    fn tracker(&self, pointer: PointerId) -> Option<&PointerEvent> {
+        [&self.first, &self.second]
+            .into_iter()
+            .flatten()
+            .find(|event| event.pointer == pointer)
+    }
+}
+
+impl GestureRecognizer for DoubleTapGestureRecognizer {
+    fn add_pointer(&mut self, context: &mut GestureContext, event: &PointerEvent) -> bool {
+        if !event.buttons.contains(super::Buttons::PRIMARY) || self.second.is_some() {
+            return false;
+        }
+
+        match (&self.first, self.first_up) {
+            (None, _) => {
+                self.first = Some(event.clone());
+
+                true
+            }
+            (Some(first), Some(first_up))
+                if event.timestamp.saturating_sub(first_up) <= DOUBLE_TAP_TIMEOUT
+                    && (event.position - first.position).distance() <= DOUBLE_TAP_SLOP =>
+            {
+                self.second = Some(event.clone());
+
+                true
+            }
+            (Some(_), Some(_)) => {
+                // Too late or too far, this down starts a new double tap.
+                self.reset(context);
+
+                self.first = Some(event.clone());
+
+                true
+            }
+            // The first pointer is still down.
+            (Some(_), None) => false,
+        }
+    }
+
+    fn handle_event(&mut self, context: &mut GestureContext, event: &PointerEvent) {
+        let Some(down) = self.tracker(event.pointer) else {
+            return;
+        };
+
+        match event.kind {
+            PointerEventKind::Move if (event.position - down.position).distance() > TOUCH_SLOP => {
+                self.reset(context);
+            }
+            PointerEventKind::Up if self.second.is_some() => {
+                let first = self.first.take().unwrap().pointer;
+                let second = self.second.take().unwrap().pointer;
+
+                self.first_up = None;
+
+                for pointer in [first, second] {
+                    context.accept(pointer);
+                    context.release(pointer);
+                    context.stop_tracking(pointer);
+                }
+
+                if let Some(on_double_tap) = &self.on_double_tap {
+                    on_double_tap.call(());
+                }
+            }
+            PointerEventKind::Up => {
+                self.first_up = Some(context.now());
+
+                context.hold(event.pointer);
+            }
+            PointerEventKind::Cancel => self.reset(context),
+            _ => {}
+        }
+    }
+
+    fn accept_gesture(&mut self, _context: &mut GestureContext, _pointer: PointerId) {}
+
+    fn reject_gesture(&mut self, context: &mut GestureContext, pointer: PointerId) {
+        if self.tracker(pointer).is_some() {
+            self.reset(context);
+        }
+    }
+
+    fn handle_timer(&mut self, context: &mut GestureContext) {
+        if let (Some(first_up), None) = (self.first_up, &self.second) {
+            if context.now().saturating_sub(first_up) > DOUBLE_TAP_TIMEOUT {
+                self.reset(context);
+            }
+        }
+    }
+}
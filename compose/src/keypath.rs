@@ -1,12 +1,101 @@
-use std::panic::Location;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    panic::Location,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use uuid::Uuid;
 
 ///  identity of render element in the view tree
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum KeyPath {
     /// Empty KeyPath type.
     Empty,
     /// use rust caller location as unique identities
     TrackCaller(&'static Location<'static>),
+    /// User supplied string key.
+    String(String),
+    /// User supplied integer key.
+    Integer(i128),
+    /// Hash of a user supplied value, see [`KeyPath::hash_of`].
+    Hash(u64),
+    /// Object identity key.
+    Uuid(Uuid),
+    /// Iteration index of a view created in a loop, see [`IntoViews`](crate::view::IntoViews).
+    Index(usize),
+    /// Key layered on top of a parent key path, usually the caller location.
+    Composite(Box<KeyPath>, Box<KeyPath>),
+    /// Key unique across the whole element tree, see [`GlobalKey`].
+    Global(GlobalKey),
+}
+
+/// Key unique across the whole element tree.
+///
+/// Element mounted with a global key is registered in [`FrameworkContext`](crate::framework::FrameworkContext),
+/// moving the view to another parent reuses the element and its state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalKey(u64);
+
+impl GlobalKey {
+    pub fn new() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+
+        GlobalKey(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for GlobalKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyPath {
+    /// Create key path from hash of `value`.
+    pub fn hash_of<T: Hash + ?Sized>(value: &T) -> Self {
+        let mut hasher = DefaultHasher::new();
+
+        value.hash(&mut hasher);
+
+        KeyPath::Hash(hasher.finish())
+    }
+
+    /// Layer `key` on top of this key path.
+    pub fn join<K: Into<KeyPath>>(self, key: K) -> Self {
+        KeyPath::Composite(Box::new(self), Box::new(key.into()))
+    }
+
+    /// Returns the caller location this key path was created at.
+    pub fn to_caller(&self) -> Option<&'static Location<'static>> {
+        match self {
+            KeyPath::TrackCaller(location) => Some(location),
+            KeyPath::Composite(parent, _) => parent.to_caller(),
+            _ => None,
+        }
+    }
+
+    /// Replace the caller location this key path was created at, keeping the layered keys.
+    pub fn with_caller(self, caller: &'static Location<'static>) -> Self {
+        match self {
+            KeyPath::TrackCaller(_) => KeyPath::TrackCaller(caller),
+            KeyPath::Composite(parent, key) => {
+                KeyPath::Composite(Box::new(parent.with_caller(caller)), key)
+            }
+            key_path => key_path,
+        }
+    }
+
+    /// Returns the global key of this key path, searching layered keys too.
+    pub fn to_global_key(&self) -> Option<GlobalKey> {
+        match self {
+            KeyPath::Global(key) => Some(*key),
+            KeyPath::Composite(parent, key) => {
+                key.to_global_key().or_else(|| parent.to_global_key())
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<&'static Location<'static>> for KeyPath {
@@ -14,3 +103,88 @@ impl From<&'static Location<'static>> for KeyPath {
         KeyPath::TrackCaller(value)
     }
 }
+
+impl From<&str> for KeyPath {
+    fn from(value: &str) -> Self {
+        KeyPath::String(value.to_owned())
+    }
+}
+
+impl From<String> for KeyPath {
+    fn from(value: String) -> Self {
+        KeyPath::String(value)
+    }
+}
+
+impl From<GlobalKey> for KeyPath {
+    fn from(value: GlobalKey) -> Self {
+        KeyPath::Global(value)
+    }
+}
+
+impl From<Uuid> for KeyPath {
+    fn from(value: Uuid) -> Self {
+        KeyPath::Uuid(value)
+    }
+}
+
+macro_rules! integer_key_path {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for KeyPath {
+                fn from(value: $t) -> Self {
+                    KeyPath::Integer(value as i128)
+                }
+            }
+
+            impl ToKeyPath for $t {
+                fn to_key_path(&self) -> KeyPath {
+                    (*self).into()
+                }
+            }
+        )*
+    };
+}
+
+/// Convert field value into [`KeyPath`], used by `#[compose(key)]` field attribute of derive macros.
+pub trait ToKeyPath {
+    fn to_key_path(&self) -> KeyPath;
+}
+
+integer_key_path!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl ToKeyPath for str {
+    fn to_key_path(&self) -> KeyPath {
+        self.into()
+    }
+}
+
+impl ToKeyPath for String {
+    fn to_key_path(&self) -> KeyPath {
+        self.as_str().into()
+    }
+}
+
+impl ToKeyPath for Uuid {
+    fn to_key_path(&self) -> KeyPath {
+        (*self).into()
+    }
+}
+
+impl ToKeyPath for GlobalKey {
+    fn to_key_path(&self) -> KeyPath {
+        (*self).into()
+    }
+}
+
+impl ToKeyPath for KeyPath {
+    fn to_key_path(&self) -> KeyPath {
+        self.clone()
+    }
+}
+
+impl<T: ToKeyPath + ?Sized> ToKeyPath for &T {
+    fn to_key_path(&self) -> KeyPath {
+        (**self).to_key_path()
+    }
+}
@@ -0,0 +1,119 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    geometry::{Offset, RRect, Rect, Transform},
+    painting::{Canvas, Path, Picture, PictureRecorder},
+};
+
+/// Clip shape of [`Layer::Clip`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clip {
+    Rect(Rect),
+    RRect(RRect),
+    Path(Path),
+}
+
+/// Retained compositing tree produced by painting the render tree.
+///
+/// Layers of repaint boundaries are cached on their render objects, so unchanged subtrees
+/// are composited again without being painted, see
+/// [`RenderObjectLifecycle::is_repaint_boundary`](crate::render::RenderObjectLifecycle::is_repaint_boundary).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Layer {
+    Container(Vec<Layer>),
+    Offset(Offset, Vec<Layer>),
+    Transform(Transform, Vec<Layer>),
+    Clip(Clip, Vec<Layer>),
+    /// Children are blended as a group with `alpha`.
+    Opacity(u8, Vec<Layer>),
+    Picture(Rc<Picture>),
+    /// Layer of repaint boundary shared with the render object, painting the boundary again
+    /// updates every layer tree retaining it.
+    Retained(Rc<RefCell<Layer>>),
+}
+
+impl Default for Layer {
+    fn default() -> Self {
+        Layer::Container(vec![])
+    }
+}
+
+impl Layer {
+    /// Returns the child layers, empty for picture and retained layer.
+    pub fn children(&self) -> &[Layer] {
+        match self {
+            Layer::Container(children)
+            | Layer::Offset(_, children)
+            | Layer::Transform(_, children)
+            | Layer::Clip(_, children)
+            | Layer::Opacity(_, children) => children,
+            Layer::Picture(_) | Layer::Retained(_) => &[],
+        }
+    }
+
+    /// Returns every picture in paint order, including pictures of retained layers.
+    pub fn pictures(&self) -> Vec<Rc<Picture>> {
+        match self {
+            Layer::Picture(picture) => vec![picture.clone()],
+            Layer::Retained(layer) => layer.borrow().pictures(),
+            layer => layer
+                .children()
+                .iter()
+                .flat_map(|child| child.pictures())
+                .collect(),
+        }
+    }
+
+    /// Flatten the layer tree into a single picture, which draws the same as [`Layer::composite`].
+    pub fn to_picture(&self) -> Picture {
+        let mut recorder = PictureRecorder::default();
+
+        self.composite(&mut recorder);
+
+        recorder.finish()
+    }
+
+    /// Draw the layer tree onto `canvas`.
+    pub fn composite(&self, canvas: &mut dyn Canvas) {
+        let composite_children = |canvas: &mut dyn Canvas| {
+            for child in self.children() {
+                child.composite(canvas);
+            }
+        };
+
+        match self {
+            Layer::Container(_) => composite_children(canvas),
+            Layer::Offset(offset, _) => {
+                canvas.save();
+                canvas.translate(*offset);
+                composite_children(canvas);
+                canvas.restore();
+            }
+            Layer::Transform(transform, _) => {
+                canvas.save();
+                canvas.transform(transform);
+                composite_children(canvas);
+                canvas.restore();
+            }
+            Layer::Clip(clip, _) => {
+                canvas.save();
+
+                match clip {
+                    Clip::Rect(rect) => canvas.clip_rect(*rect),
+                    Clip::RRect(rrect) => canvas.clip_rrect(*rrect),
+                    Clip::Path(path) => canvas.clip_path(path),
+                }
+
+                composite_children(canvas);
+                canvas.restore();
+            }
+            Layer::Opacity(alpha, _) => {
+                canvas.save_layer(*alpha);
+                composite_children(canvas);
+                canvas.restore();
+            }
+            Layer::Picture(picture) => picture.playback(canvas),
+            Layer::Retained(layer) => layer.borrow().composite(canvas),
+        }
+    }
+}
@@ -1,11 +1,28 @@
+// Derive macros refer to `agoraui_compose`, which must resolve inside this crate as well.
+extern crate self as agoraui_compose;
+
 pub mod keypath;
 
+pub mod callback;
+
+pub mod geometry;
+
+pub mod painting;
+
+pub mod layer;
+
 pub mod view;
 
 pub mod element;
 
 pub mod render;
 
+pub mod gesture;
+
+pub mod focus;
+
+pub mod text;
+
 pub mod framework;
 
 pub use agoraui_compose_macros as macos;
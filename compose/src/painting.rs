@@ -0,0 +1,667 @@
+use std::{fmt::Debug, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::{Offset, RRect, Rect, Transform};
+
+/// Non premultiplied RGBA color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+    pub const BLACK: Color = Color::rgba(0, 0, 0, 255);
+    pub const WHITE: Color = Color::rgba(255, 255, 255, 255);
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::rgba(r, g, b, 255)
+    }
+
+    /// Create color from `0xAARRGGBB`.
+    pub const fn from_argb(value: u32) -> Self {
+        Self::rgba(
+            (value >> 16) as u8,
+            (value >> 8) as u8,
+            value as u8,
+            (value >> 24) as u8,
+        )
+    }
+
+    pub const fn with_alpha(&self, a: u8) -> Self {
+        Self::rgba(self.r, self.g, self.b, a)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PaintStyle {
+    #[default]
+    Fill,
+    Stroke,
+}
+
+/// How to draw a shape onto a [`Canvas`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Paint {
+    pub color: Color,
+    pub style: PaintStyle,
+    /// Stroke width in logical pixels, used by [`PaintStyle::Stroke`].
+    pub stroke_width: f32,
+    pub anti_alias: bool,
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            style: PaintStyle::Fill,
+            stroke_width: 1.0,
+            anti_alias: true,
+        }
+    }
+}
+
+impl Paint {
+    pub fn fill(color: Color) -> Self {
+        Self {
+            color,
+            ..Default::default()
+        }
+    }
+
+    pub fn stroke(color: Color, stroke_width: f32) -> Self {
+        Self {
+            color,
+            style: PaintStyle::Stroke,
+            stroke_width,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PathCommand {
+    MoveTo(Offset),
+    LineTo(Offset),
+    /// Quadratic bezier with control point and end point.
+    QuadTo(Offset, Offset),
+    /// Cubic bezier with two control points and end point.
+    CubicTo(Offset, Offset, Offset),
+    Close,
+}
+
+/// Outline made of lines and bezier curves, filled with the non-zero winding rule.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Path {
+    pub commands: Vec<PathCommand>,
+}
+
+/// Control point distance of a cubic bezier approximating a quarter circle.
+const KAPPA: f32 = 0.552_284_8;
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn move_to(&mut self, x: f32, y: f32) -> &mut Self {
+        self.commands.push(PathCommand::MoveTo(Offset::new(x, y)));
+        self
+    }
+
+    pub fn line_to(&mut self, x: f32, y: f32) -> &mut Self {
+        self.commands.push(PathCommand::LineTo(Offset::new(x, y)));
+        self
+    }
+
+    pub fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) -> &mut Self {
+        self.commands
+            .push(PathCommand::QuadTo(Offset::new(x1, y1), Offset::new(x, y)));
+        self
+    }
+
+    pub fn cubic_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) -> &mut Self {
+        self.commands.push(PathCommand::CubicTo(
+            Offset::new(x1, y1),
+            Offset::new(x2, y2),
+            Offset::new(x, y),
+        ));
+        self
+    }
+
+    pub fn close(&mut self) -> &mut Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn from_rect(rect: Rect) -> Self {
+        let mut path = Path::new();
+
+        path.move_to(rect.left, rect.top)
+            .line_to(rect.right, rect.top)
+            .line_to(rect.right, rect.bottom)
+            .line_to(rect.left, rect.bottom)
+            .close();
+
+        path
+    }
+
+    pub fn from_rrect(rrect: RRect) -> Self {
+        let Rect {
+            left,
+            top,
+            right,
+            bottom,
+        } = rrect.rect;
+
+        // Corners can't overlap each other.
+        let max = (rrect.rect.width().min(rrect.rect.height()) / 2.0).max(0.0);
+        let [tl, tr, br, bl] = [
+            rrect.top_left,
+            rrect.top_right,
+            rrect.bottom_right,
+            rrect.bottom_left,
+        ]
+        .map(|radius| radius.clamp(0.0, max));
+
+        let mut path = Path::new();
+
+        path.move_to(left + tl, top)
+            .line_to(right - tr, top)
+            .cubic_to(
+                right - tr * (1.0 - KAPPA),
+                top,
+                right,
+                top + tr * (1.0 - KAPPA),
+                right,
+                top + tr,
+            )
+            .line_to(right, bottom - br)
+            .cubic_to(
+                right,
+                bottom - br * (1.0 - KAPPA),
+                right - br * (1.0 - KAPPA),
+                bottom,
+                right - br,
+                bottom,
+            )
+            .line_to(left + bl, bottom)
+            .cubic_to(
+                left + bl * (1.0 - KAPPA),
+                bottom,
+                left,
+                bottom - bl * (1.0 - KAPPA),
+                left,
+                bottom - bl,
+            )
+            .line_to(left, top + tl)
+            .cubic_to(
+                left,
+                top + tl * (1.0 - KAPPA),
+                left + tl * (1.0 - KAPPA),
+                top,
+                left + tl,
+                top,
+            )
+            .close();
+
+        path
+    }
+
+    /// Ellipse inscribed in `rect`.
+    pub fn from_oval(rect: Rect) -> Self {
+        let rx = rect.width() / 2.0;
+        let ry = rect.height() / 2.0;
+        let cx = rect.left + rx;
+        let cy = rect.top + ry;
+        let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+
+        let mut path = Path::new();
+
+        path.move_to(cx + rx, cy)
+            .cubic_to(cx + rx, cy + ky, cx + kx, cy + ry, cx, cy + ry)
+            .cubic_to(cx - kx, cy + ry, cx - rx, cy + ky, cx - rx, cy)
+            .cubic_to(cx - rx, cy - ky, cx - kx, cy - ry, cx, cy - ry)
+            .cubic_to(cx + kx, cy - ry, cx + rx, cy - ky, cx + rx, cy)
+            .close();
+
+        path
+    }
+
+    /// Returns the path with every point mapped by `transform`.
+    pub fn transform(&self, transform: &Transform) -> Self {
+        let map = |point: &Offset| transform.map_point(*point);
+
+        Self {
+            commands: self
+                .commands
+                .iter()
+                .map(|command| match command {
+                    PathCommand::MoveTo(p) => PathCommand::MoveTo(map(p)),
+                    PathCommand::LineTo(p) => PathCommand::LineTo(map(p)),
+                    PathCommand::QuadTo(c, p) => PathCommand::QuadTo(map(c), map(p)),
+                    PathCommand::CubicTo(c1, c2, p) => {
+                        PathCommand::CubicTo(map(c1), map(c2), map(p))
+                    }
+                    PathCommand::Close => PathCommand::Close,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the bounding box of all points including control points, `None` if empty.
+    pub fn bounds(&self) -> Option<Rect> {
+        self.commands
+            .iter()
+            .flat_map(|command| match command {
+                PathCommand::MoveTo(p) | PathCommand::LineTo(p) => vec![*p],
+                PathCommand::QuadTo(c, p) => vec![*c, *p],
+                PathCommand::CubicTo(c1, c2, p) => vec![*c1, *c2, *p],
+                PathCommand::Close => vec![],
+            })
+            .map(|p| Rect::from_ltrb(p.dx, p.dy, p.dx, p.dy))
+            .reduce(|a, b| a.union(&b))
+    }
+}
+
+/// Glyph of a [`TextRun`], the outline is already scaled to the font size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Glyph {
+    pub id: u16,
+    /// Position of the glyph origin on the baseline, relative to the run offset.
+    pub offset: Offset,
+    pub outline: Path,
+}
+
+/// Shaped and positioned text, drawn with its baseline origin at the paint offset.
+///
+/// Backends with native text support can draw `text` with `font_family` and `font_size`,
+/// others fill the glyph outlines.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TextRun {
+    pub text: String,
+    pub font_family: String,
+    pub font_size: f32,
+    pub glyphs: Vec<Glyph>,
+}
+
+/// Non premultiplied RGBA8 pixels, row by row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "ImageData")]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Rc<Vec<u8>>,
+}
+
+/// Unchecked [`Image`] fields, deserialized images are checked by [`Image::try_new`].
+#[derive(Deserialize)]
+struct ImageData {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl TryFrom<ImageData> for Image {
+    type Error = String;
+
+    fn try_from(data: ImageData) -> Result<Self, Self::Error> {
+        Image::try_new(data.width, data.height, data.pixels)
+    }
+}
+
+impl Image {
+    /// Panics if `pixels` is not `width * height * 4` bytes long, see [`Image::try_new`].
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        Self::try_new(width, height, pixels).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns an error if `pixels` is not `width * height * 4` bytes long.
+    pub fn try_new(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, String> {
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|len| len.checked_mul(4))
+            .ok_or("Image size overflows usize")?;
+
+        if pixels.len() != len {
+            return Err(format!(
+                "Image pixels length mismatch, expect {} bytes, got {}",
+                len,
+                pixels.len()
+            ));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            pixels: Rc::new(pixels),
+        })
+    }
+
+    /// Returns the RGBA of pixel at `(x, y)`.
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let pixel = &self.pixels[index..index + 4];
+
+        Color::rgba(pixel[0], pixel[1], pixel[2], pixel[3])
+    }
+}
+
+/// Backend agnostic drawing surface, render objects paint into it in
+/// [`RenderObjectLifecycle::paint`](crate::render::RenderObjectLifecycle::paint).
+///
+/// Drawing is affected by the current transform and clip, which are pushed by [`Canvas::save`]
+/// and popped by [`Canvas::restore`].
+pub trait Canvas {
+    /// Push the current transform and clip.
+    fn save(&mut self);
+
+    /// Like [`Canvas::save`], drawing until the matching [`Canvas::restore`] goes to an offscreen
+    /// layer, which is blended with `alpha` when restored.
+    ///
+    /// Backends without offscreen support only save, ignoring `alpha`.
+    fn save_layer(&mut self, _alpha: u8) {
+        self.save();
+    }
+
+    /// Pop the transform and clip pushed by the matching [`Canvas::save`].
+    fn restore(&mut self);
+
+    /// Apply `transform` before the current transform.
+    fn transform(&mut self, transform: &Transform);
+
+    fn translate(&mut self, offset: Offset) {
+        self.transform(&Transform::translation(offset.dx, offset.dy));
+    }
+
+    /// Intersect the current clip with `rect`.
+    fn clip_rect(&mut self, rect: Rect) {
+        self.clip_path(&Path::from_rect(rect));
+    }
+
+    fn clip_rrect(&mut self, rrect: RRect) {
+        self.clip_path(&Path::from_rrect(rrect));
+    }
+
+    fn clip_path(&mut self, path: &Path);
+
+    fn draw_rect(&mut self, rect: Rect, paint: &Paint) {
+        self.draw_path(&Path::from_rect(rect), paint);
+    }
+
+    fn draw_rrect(&mut self, rrect: RRect, paint: &Paint) {
+        self.draw_path(&Path::from_rrect(rrect), paint);
+    }
+
+    fn draw_path(&mut self, path: &Path, paint: &Paint);
+
+    /// Draw `run` with its baseline origin at `offset`.
+    fn draw_text(&mut self, run: &TextRun, offset: Offset, paint: &Paint);
+
+    /// Draw `image` scaled into `rect`, the alpha of `paint` color is applied as opacity.
+    fn draw_image(&mut self, image: &Image, rect: Rect, paint: &Paint);
+}
+
+/// Canvas call recorded by [`PictureRecorder`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DrawCommand {
+    Save,
+    SaveLayer(u8),
+    Restore,
+    Transform(Transform),
+    ClipRect(Rect),
+    ClipRRect(RRect),
+    ClipPath(Path),
+    DrawRect(Rect, Paint),
+    DrawRRect(RRect, Paint),
+    DrawPath(Path, Paint),
+    DrawText(TextRun, Offset, Paint),
+    DrawImage(Image, Rect, Paint),
+}
+
+/// Recorded list of canvas calls, which can be replayed on any [`Canvas`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Picture {
+    pub commands: Vec<DrawCommand>,
+}
+
+/// Difference between two pictures, see [`Picture::diff`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PictureChange {
+    /// Command at `index` of the old picture is removed.
+    Remove(usize, DrawCommand),
+    /// Command is inserted at `index` of the new picture.
+    Insert(usize, DrawCommand),
+    /// Command at `index` of the old picture is replaced.
+    Replace(usize, DrawCommand, DrawCommand),
+}
+
+/// Largest `old * new` table of changed commands [`Picture::diff`] matches by their
+/// longest common subsequence, bigger changes replace every changed command.
+const MAX_DIFF_CELLS: usize = 1 << 20;
+
+impl Picture {
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns the changes turning `self` into `other` in order, empty if both are equal.
+    ///
+    /// Unchanged commands are matched by their longest common subsequence, between two
+    /// matches removed and inserted commands are paired into replacements. If the changed
+    /// middle of both pictures is too large to match, every command of it is replaced.
+    pub fn diff(&self, other: &Picture) -> Vec<PictureChange> {
+        let (old, new) = (&self.commands, &other.commands);
+
+        // Trim the common ends, usually only a few commands in the middle change.
+        let prefix = old
+            .iter()
+            .zip(new.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let old_middle = &old[prefix..old.len() - suffix];
+        let new_middle = &new[prefix..new.len() - suffix];
+
+        let mut changes = vec![];
+
+        if old_middle.len().saturating_mul(new_middle.len()) > MAX_DIFF_CELLS {
+            let mut removed = old_middle
+                .iter()
+                .enumerate()
+                .map(|(i, command)| (prefix + i, command.clone()))
+                .collect();
+            let mut inserted = new_middle
+                .iter()
+                .enumerate()
+                .map(|(j, command)| (prefix + j, command.clone()))
+                .collect();
+
+            Self::pair_changes(&mut changes, &mut removed, &mut inserted);
+
+            return changes;
+        }
+
+        // lengths[i][j] is the length of the common subsequence of old_middle[i..] and new_middle[j..].
+        let mut lengths = vec![vec![0usize; new_middle.len() + 1]; old_middle.len() + 1];
+
+        for i in (0..old_middle.len()).rev() {
+            for j in (0..new_middle.len()).rev() {
+                lengths[i][j] = if old_middle[i] == new_middle[j] {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let (mut removed, mut inserted) = (vec![], vec![]);
+        let (mut i, mut j) = (0, 0);
+
+        loop {
+            let matched =
+                i < old_middle.len() && j < new_middle.len() && old_middle[i] == new_middle[j];
+
+            if matched || (i == old_middle.len() && j == new_middle.len()) {
+                Self::pair_changes(&mut changes, &mut removed, &mut inserted);
+
+                if !matched {
+                    break;
+                }
+
+                i += 1;
+                j += 1;
+            } else if j == new_middle.len()
+                || (i < old_middle.len() && lengths[i + 1][j] >= lengths[i][j + 1])
+            {
+                removed.push((prefix + i, old_middle[i].clone()));
+                i += 1;
+            } else {
+                inserted.push((prefix + j, new_middle[j].clone()));
+                j += 1;
+            }
+        }
+
+        changes
+    }
+
+    fn pair_changes(
+        changes: &mut Vec<PictureChange>,
+        removed: &mut Vec<(usize, DrawCommand)>,
+        inserted: &mut Vec<(usize, DrawCommand)>,
+    ) {
+        let paired = removed.len().min(inserted.len());
+        let mut removed = removed.drain(..);
+        let mut inserted = inserted.drain(..);
+
+        for _ in 0..paired {
+            let ((index, old), (_, new)) = (removed.next().unwrap(), inserted.next().unwrap());
+
+            changes.push(PictureChange::Replace(index, old, new));
+        }
+
+        changes.extend(removed.map(|(index, command)| PictureChange::Remove(index, command)));
+        changes.extend(inserted.map(|(index, command)| PictureChange::Insert(index, command)));
+    }
+
+    /// Serialize as pretty printed JSON, e.g. for golden tests of paint output.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Replay the recorded calls on `canvas`.
+    pub fn playback(&self, canvas: &mut dyn Canvas) {
+        for command in &self.commands {
+            match command {
+                DrawCommand::Save => canvas.save(),
+                DrawCommand::SaveLayer(alpha) => canvas.save_layer(*alpha),
+                DrawCommand::Restore => canvas.restore(),
+                DrawCommand::Transform(transform) => canvas.transform(transform),
+                DrawCommand::ClipRect(rect) => canvas.clip_rect(*rect),
+                DrawCommand::ClipRRect(rrect) => canvas.clip_rrect(*rrect),
+                DrawCommand::ClipPath(path) => canvas.clip_path(path),
+                DrawCommand::DrawRect(rect, paint) => canvas.draw_rect(*rect, paint),
+                DrawCommand::DrawRRect(rrect, paint) => canvas.draw_rrect(*rrect, paint),
+                DrawCommand::DrawPath(path, paint) => canvas.draw_path(path, paint),
+                DrawCommand::DrawText(run, offset, paint) => canvas.draw_text(run, *offset, paint),
+                DrawCommand::DrawImage(image, rect, paint) => {
+                    canvas.draw_image(image, *rect, paint)
+                }
+            }
+        }
+    }
+}
+
+/// [`Canvas`] recording the calls into a [`Picture`].
+#[derive(Debug, Default)]
+pub struct PictureRecorder {
+    picture: Picture,
+}
+
+impl PictureRecorder {
+    pub fn is_empty(&self) -> bool {
+        self.picture.is_empty()
+    }
+
+    /// Returns the recorded picture and reset the recorder.
+    pub fn finish(&mut self) -> Picture {
+        std::mem::take(&mut self.picture)
+    }
+
+    fn record(&mut self, command: DrawCommand) {
+        self.picture.commands.push(command);
+    }
+}
+
+impl Canvas for PictureRecorder {
+    fn save(&mut self) {
+        self.record(DrawCommand::Save);
+    }
+
+    fn save_layer(&mut self, alpha: u8) {
+        self.record(DrawCommand::SaveLayer(alpha));
+    }
+
+    fn restore(&mut self) {
+        self.record(DrawCommand::Restore);
+    }
+
+    fn transform(&mut self, transform: &Transform) {
+        self.record(DrawCommand::Transform(*transform));
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.record(DrawCommand::ClipRect(rect));
+    }
+
+    fn clip_rrect(&mut self, rrect: RRect) {
+        self.record(DrawCommand::ClipRRect(rrect));
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        self.record(DrawCommand::ClipPath(path.clone()));
+    }
+
+    fn draw_rect(&mut self, rect: Rect, paint: &Paint) {
+        self.record(DrawCommand::DrawRect(rect, paint.clone()));
+    }
+
+    fn draw_rrect(&mut self, rrect: RRect, paint: &Paint) {
+        self.record(DrawCommand::DrawRRect(rrect, paint.clone()));
+    }
+
+    fn draw_path(&mut self, path: &Path, paint: &Paint) {
+        self.record(DrawCommand::DrawPath(path.clone(), paint.clone()));
+    }
+
+    fn draw_text(&mut self, run: &TextRun, offset: Offset, paint: &Paint) {
+        self.record(DrawCommand::DrawText(run.clone(), offset, paint.clone()));
+    }
+
+    fn draw_image(&mut self, image: &Image, rect: Rect, paint: &Paint) {
+        self.record(DrawCommand::DrawImage(image.clone(), rect, paint.clone()));
+    }
+}
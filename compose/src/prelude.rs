@@ -1,5 +1,12 @@
+pub use super::callback::*;
 pub use super::element::*;
+pub use super::focus::*;
+pub use super::geometry::*;
+pub use super::gesture::*;
 pub use super::keypath::*;
+pub use super::layer::*;
 pub use super::macos::*;
+pub use super::painting::*;
 pub use super::render::*;
+pub use super::text::*;
 pub use super::view::*;
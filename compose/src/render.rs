@@ -1,14 +1,135 @@
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+use std::{any::Any, cell::RefCell, fmt::Debug, rc::Rc};
 
-use indextree::NodeId;
+use indextree::{Arena, NodeId};
 
-pub trait RenderObjectLifecycle: Debug {}
+use crate::{
+    element::ElementId,
+    framework::PipelineOwner,
+    geometry::{BoxConstraints, Offset, Rect, Size, Transform},
+    gesture::{PointerEvent, PointerRouter},
+    layer::{Clip, Layer},
+    painting::{Canvas, PictureRecorder},
+};
+
+pub trait RenderObjectLifecycle: Any + Debug {
+    /// Compute the size of this render object within `constraints`, laying out and positioning
+    /// the children through `context`.
+    ///
+    /// By default every child is laid out with `constraints` at the origin, and this render object
+    /// sizes itself to the biggest child.
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        let mut size = constraints.smallest();
+
+        for child in context.children() {
+            let child_size = context.layout_child(child, constraints);
+
+            size.width = size.width.max(child_size.width);
+            size.height = size.height.max(child_size.height);
+        }
+
+        size
+    }
+
+    /// Returns true if layout changes of this render object never affect its parent,
+    /// e.g. its size only depends on the constraints.
+    ///
+    /// Render objects laid out with tight constraints are relayout boundaries anyway.
+    fn is_relayout_boundary(&self) -> bool {
+        false
+    }
+
+    /// Returns true if this render object paints into its own cached layer, so it's painted again
+    /// only when it or its descendants need paint.
+    fn is_repaint_boundary(&self) -> bool {
+        false
+    }
+
+    /// Paint this render object with its top left corner at `offset` in the canvas coordinate.
+    ///
+    /// By default only the children are painted.
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        context.paint_children(offset);
+    }
+
+    /// Add this render object and the hit descendants to `context` if `position` in the local
+    /// coordinate hits it, returns true if hit.
+    ///
+    /// By default positions outside of the size never hit, children are tested before
+    /// [`RenderObjectLifecycle::hit_test_self`]. Render objects painting children with a
+    /// transform override this to test them with [`HitTestContext::hit_test_child_with_transform`].
+    fn hit_test(&self, context: &mut HitTestContext, position: Offset) -> bool {
+        if !Rect::from_offset_size(Offset::ZERO, context.size()).contains(position) {
+            return false;
+        }
+
+        if context.hit_test_children(position) || self.hit_test_self(position) {
+            context.add_self();
+
+            return true;
+        }
+
+        false
+    }
+
+    /// Returns true if `position` in the local coordinate hits this render object itself,
+    /// e.g. it paints there. Render objects are transparent to hits by default.
+    fn hit_test_self(&self, _position: Offset) -> bool {
+        false
+    }
+
+    /// Handle a pointer event hitting this render object, `event` is mapped into the local
+    /// coordinate. Gesture recognizers are added for pointer downs through `router`.
+    fn handle_event(&self, _event: &PointerEvent, _router: &mut PointerRouter) {}
+}
+
+impl dyn RenderObjectLifecycle {
+    /// Returns the concrete render object, used by `update_render_object` hooks.
+    pub fn downcast_mut<T: RenderObjectLifecycle>(&mut self) -> Option<&mut T> {
+        (self as &mut dyn Any).downcast_mut::<T>()
+    }
+
+    pub fn downcast_ref<T: RenderObjectLifecycle>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref::<T>()
+    }
+}
+
+/// What an `update_render_object` hook changed in the render object, the render object
+/// is marked dirty accordingly, see
+/// [`RenderObjectConfiguration::framework_update_render_object`](crate::view::RenderObjectConfiguration::framework_update_render_object).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderObjectUpdate {
+    /// Nothing affecting layout or paint changed.
+    #[default]
+    None,
+    /// Only the painted output changed.
+    Paint,
+    /// Size or position of the render object or its children may change, implies repaint.
+    Layout,
+}
 
 pub type RenderObjectId = NodeId;
 
 #[derive(Debug)]
 pub struct RenderObjectNode {
     pub id: Option<RenderObjectId>,
+    /// Size computed by the last layout, `None` before the first layout.
+    pub size: Option<Size>,
+    /// Constraints of the last layout.
+    pub constraints: Option<BoxConstraints>,
+    /// Position relative to the parent render object, set by the parent's layout.
+    pub offset: Offset,
+    /// Layout is out of date, render object is created dirty.
+    pub needs_layout: bool,
+    /// Paint is out of date, render object is created dirty.
+    pub needs_paint: bool,
+    /// Computed by the last layout, relayout of this render object doesn't need parent relayout.
+    pub relayout_boundary: bool,
+    /// Cached layer of repaint boundary painted at the origin, shared by [`Layer::Retained`].
+    pub layer: Option<Rc<RefCell<Layer>>>,
+    /// Owner scheduling the render object for layout and paint, set when attached to a render tree.
+    pub owner: Option<PipelineOwner>,
+    /// Render object element which created the render object.
+    pub element: Option<ElementId>,
     pub lifecycle: Box<dyn RenderObjectLifecycle>,
 }
 
@@ -26,6 +147,15 @@ impl<T: RenderObjectLifecycle + 'static> From<T> for RenderObject {
     fn from(value: T) -> Self {
         Self(Rc::new(RefCell::new(RenderObjectNode {
             id: None,
+            size: None,
+            constraints: None,
+            offset: Offset::ZERO,
+            needs_layout: true,
+            needs_paint: true,
+            relayout_boundary: false,
+            layer: None,
+            owner: None,
+            element: None,
             lifecycle: Box::new(value),
         })))
     }
@@ -35,4 +165,549 @@ impl RenderObject {
     pub fn initialize(&mut self, id: RenderObjectId) {
         self.0.borrow_mut().id = Some(id);
     }
+
+    pub fn to_id(&self) -> Option<RenderObjectId> {
+        self.0.borrow().id
+    }
+
+    /// Attach the render object to `owner`, pending layout and paint are scheduled.
+    pub fn attach(&self, owner: PipelineOwner) {
+        let (id, needs_layout, needs_paint) = {
+            let mut node = self.0.borrow_mut();
+
+            node.owner = Some(owner.clone());
+
+            (node.id, node.needs_layout, node.needs_paint)
+        };
+
+        if let Some(id) = id {
+            if needs_layout {
+                owner.schedule_layout(id);
+            }
+
+            if needs_paint {
+                owner.schedule_paint(id);
+            }
+        }
+    }
+
+    pub fn size(&self) -> Option<Size> {
+        self.0.borrow().size
+    }
+
+    /// Returns the render object element owning this render object.
+    pub fn element_id(&self) -> Option<ElementId> {
+        self.0.borrow().element
+    }
+
+    pub fn offset(&self) -> Offset {
+        self.0.borrow().offset
+    }
+
+    pub fn needs_layout(&self) -> bool {
+        self.0.borrow().needs_layout
+    }
+
+    pub fn needs_paint(&self) -> bool {
+        self.0.borrow().needs_paint
+    }
+
+    /// Flag the render object for relayout and schedule it in the pipeline owner.
+    ///
+    /// The relayout is propagated to the nearest relayout boundary when the owner flushes,
+    /// a relayout always repaints.
+    pub fn mark_needs_layout(&self) {
+        let (id, owner) = {
+            let mut node = self.0.borrow_mut();
+
+            node.needs_layout = true;
+            node.needs_paint = true;
+
+            (node.id, node.owner.clone())
+        };
+
+        if let (Some(id), Some(owner)) = (id, owner) {
+            owner.schedule_layout(id);
+            owner.schedule_paint(id);
+        }
+    }
+
+    /// Flag the render object for repaint and schedule it in the pipeline owner.
+    pub fn mark_needs_paint(&self) {
+        let (id, owner) = {
+            let mut node = self.0.borrow_mut();
+
+            node.needs_paint = true;
+
+            (node.id, node.owner.clone())
+        };
+
+        if let (Some(id), Some(owner)) = (id, owner) {
+            owner.schedule_paint(id);
+        }
+    }
+
+    pub fn is_relayout_boundary(&self) -> bool {
+        self.0.borrow().relayout_boundary
+    }
+}
+
+/// Layout of render object subtree rooted at `id` within `constraints`, returns the size of `id`.
+///
+/// The computed size is constrained by `constraints` and stored on the render object.
+/// Render objects which are not dirty and laid out with the same constraints are skipped.
+pub fn layout(
+    render_tree: &Arena<RenderObject>,
+    id: RenderObjectId,
+    constraints: BoxConstraints,
+) -> Size {
+    let render_object = render_tree
+        .get(id)
+        .expect("Layout removed render object")
+        .get()
+        .clone();
+
+    let mut node = render_object.0.borrow_mut();
+
+    if let (false, Some(size)) = (node.needs_layout, node.size) {
+        if node.constraints == Some(constraints) {
+            return size;
+        }
+    }
+
+    let mut context = LayoutContext { render_tree, id };
+
+    let size = constraints.constrain(node.lifecycle.perform_layout(&mut context, constraints));
+
+    node.relayout_boundary = render_tree[id].parent().is_none()
+        || constraints.is_tight()
+        || node.lifecycle.is_relayout_boundary();
+
+    // Size and child offsets may change, the nearest repaint boundary is painted again.
+    node.needs_paint = true;
+
+    if let (Some(owner), Some(id)) = (&node.owner, node.id) {
+        owner.schedule_paint(id);
+    }
+
+    node.size = Some(size);
+    node.constraints = Some(constraints);
+    node.needs_layout = false;
+
+    size
+}
+
+/// Access to the children of the render object in [`RenderObjectLifecycle::perform_layout`].
+pub struct LayoutContext<'a> {
+    render_tree: &'a Arena<RenderObject>,
+    id: RenderObjectId,
+}
+
+impl<'a> LayoutContext<'a> {
+    /// Render object being laid out.
+    pub fn to_id(&self) -> RenderObjectId {
+        self.id
+    }
+
+    /// Children in paint order.
+    pub fn children(&self) -> Vec<RenderObjectId> {
+        self.id.children(self.render_tree).collect()
+    }
+
+    /// Lay out `child` within `constraints`, returns the child size.
+    ///
+    /// `child` must be one of [`LayoutContext::children`], the render object being laid out
+    /// is borrowed during its layout.
+    pub fn layout_child(&mut self, child: RenderObjectId, constraints: BoxConstraints) -> Size {
+        self.assert_child(child);
+
+        layout(self.render_tree, child, constraints)
+    }
+
+    /// Store the position of `child` relative to this render object.
+    ///
+    /// `child` must be one of [`LayoutContext::children`].
+    pub fn position_child(&mut self, child: RenderObjectId, offset: Offset) {
+        self.assert_child(child);
+
+        if let Some(node) = self.render_tree.get(child) {
+            node.get().0.borrow_mut().offset = offset;
+        }
+    }
+
+    /// Size of `child` computed by the last layout.
+    pub fn child_size(&self, child: RenderObjectId) -> Option<Size> {
+        self.render_tree.get(child)?.get().size()
+    }
+
+    fn assert_child(&self, child: RenderObjectId) {
+        assert_eq!(
+            child.parent(self.render_tree),
+            Some(self.id),
+            "{:?} is not a child of the render object being laid out {:?}",
+            child,
+            self.id
+        );
+    }
+}
+
+/// Paint render object subtree rooted at `id` at `offset`, returns the produced layers.
+///
+/// Repaint boundaries in the subtree reuse their cached layer unless they need paint.
+pub fn paint(render_tree: &Arena<RenderObject>, id: RenderObjectId, offset: Offset) -> Vec<Layer> {
+    let mut context = PaintContext::new(render_tree, id);
+
+    context.paint_render_object(id, offset);
+
+    context.finish()
+}
+
+/// Returns the retained layer of repaint boundary `id` painted at the origin,
+/// painted again only if dirty.
+pub fn repaint_boundary_layer(render_tree: &Arena<RenderObject>, id: RenderObjectId) -> Layer {
+    let render_object = render_tree[id].get().clone();
+
+    let cached = render_object.0.borrow().layer.clone();
+
+    match cached {
+        Some(layer) if !render_object.needs_paint() => Layer::Retained(layer),
+        cached => {
+            let layer = Layer::Container(paint(render_tree, id, Offset::ZERO));
+
+            let layer = match cached {
+                Some(cached) => {
+                    *cached.borrow_mut() = layer;
+                    cached
+                }
+                None => Rc::new(RefCell::new(layer)),
+            };
+
+            render_object.0.borrow_mut().layer = Some(layer.clone());
+
+            Layer::Retained(layer)
+        }
+    }
+}
+
+/// Access to the canvas and the children of the render object in [`RenderObjectLifecycle::paint`].
+///
+/// Drawing is recorded into picture layers, `push_*` methods wrap the drawing of a closure
+/// into a compositing layer.
+pub struct PaintContext<'a> {
+    render_tree: &'a Arena<RenderObject>,
+    id: RenderObjectId,
+    recorder: PictureRecorder,
+    layers: Vec<Layer>,
+}
+
+impl<'a> PaintContext<'a> {
+    fn new(render_tree: &'a Arena<RenderObject>, id: RenderObjectId) -> Self {
+        Self {
+            render_tree,
+            id,
+            recorder: PictureRecorder::default(),
+            layers: vec![],
+        }
+    }
+
+    /// Render object being painted.
+    pub fn to_id(&self) -> RenderObjectId {
+        self.id
+    }
+
+    pub fn canvas(&mut self) -> &mut dyn Canvas {
+        &mut self.recorder
+    }
+
+    /// Size of the render object being painted computed by the last layout.
+    pub fn size(&self) -> Size {
+        self.render_tree
+            .get(self.id)
+            .and_then(|node| node.get().size())
+            .unwrap_or_default()
+    }
+
+    /// Children in paint order.
+    pub fn children(&self) -> Vec<RenderObjectId> {
+        self.id.children(self.render_tree).collect()
+    }
+
+    /// Paint `child` at `offset` plus the child offset set by layout.
+    pub fn paint_child(&mut self, child: RenderObjectId, offset: Offset) {
+        let Some(node) = self.render_tree.get(child) else {
+            return;
+        };
+
+        let child_offset = offset + node.get().offset();
+
+        let is_repaint_boundary = node.get().0.borrow().lifecycle.is_repaint_boundary();
+
+        if is_repaint_boundary {
+            let layer = repaint_boundary_layer(self.render_tree, child);
+
+            self.add_layer(Layer::Offset(child_offset, vec![layer]));
+        } else {
+            self.paint_render_object(child, child_offset);
+        }
+    }
+
+    /// Paint every child in child order, see [`PaintContext::paint_child`].
+    pub fn paint_children(&mut self, offset: Offset) {
+        for child in self.children() {
+            self.paint_child(child, offset);
+        }
+    }
+
+    /// Append `layer` after the drawing recorded so far.
+    pub fn add_layer(&mut self, layer: Layer) {
+        self.stop_recording();
+
+        self.layers.push(layer);
+    }
+
+    /// Paint with `f` into the children of the layer created by `wrap`.
+    pub fn push_layer<W, F>(&mut self, wrap: W, f: F)
+    where
+        W: FnOnce(Vec<Layer>) -> Layer,
+        F: FnOnce(&mut PaintContext),
+    {
+        let mut context = PaintContext::new(self.render_tree, self.id);
+
+        f(&mut context);
+
+        self.add_layer(wrap(context.finish()));
+    }
+
+    pub fn push_offset<F: FnOnce(&mut PaintContext)>(&mut self, offset: Offset, f: F) {
+        self.push_layer(|children| Layer::Offset(offset, children), f);
+    }
+
+    pub fn push_transform<F: FnOnce(&mut PaintContext)>(&mut self, transform: Transform, f: F) {
+        self.push_layer(|children| Layer::Transform(transform, children), f);
+    }
+
+    pub fn push_clip<F: FnOnce(&mut PaintContext)>(&mut self, clip: Clip, f: F) {
+        self.push_layer(|children| Layer::Clip(clip, children), f);
+    }
+
+    pub fn push_opacity<F: FnOnce(&mut PaintContext)>(&mut self, alpha: u8, f: F) {
+        self.push_layer(|children| Layer::Opacity(alpha, children), f);
+    }
+
+    fn paint_render_object(&mut self, id: RenderObjectId, offset: Offset) {
+        let render_object = self
+            .render_tree
+            .get(id)
+            .expect("Paint removed render object")
+            .get()
+            .clone();
+
+        let parent = std::mem::replace(&mut self.id, id);
+
+        render_object.0.borrow().lifecycle.paint(self, offset);
+
+        self.id = parent;
+
+        render_object.0.borrow_mut().needs_paint = false;
+    }
+
+    fn stop_recording(&mut self) {
+        if !self.recorder.is_empty() {
+            self.layers
+                .push(Layer::Picture(Rc::new(self.recorder.finish())));
+        }
+    }
+
+    fn finish(mut self) -> Vec<Layer> {
+        self.stop_recording();
+
+        self.layers
+    }
+}
+
+/// Render object hit by a position, see [`hit_test`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitTestEntry {
+    pub render_object_id: RenderObjectId,
+    /// Render object element owning the render object.
+    pub element_id: Option<ElementId>,
+    /// Maps positions in the hit test coordinate into the local coordinate of the render object.
+    pub transform: Transform,
+}
+
+impl HitTestEntry {
+    /// Returns `position` in the hit test coordinate mapped into the local coordinate.
+    pub fn local_position(&self, position: Offset) -> Offset {
+        self.transform.map_point(position)
+    }
+}
+
+/// Path of render objects hit by a position, the deepest render object first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HitTestResult {
+    pub path: Vec<HitTestEntry>,
+}
+
+impl HitTestResult {
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    pub fn render_object_ids(&self) -> Vec<RenderObjectId> {
+        self.path
+            .iter()
+            .map(|entry| entry.render_object_id)
+            .collect()
+    }
+
+    /// Returns the render object elements of the path, the deepest first.
+    pub fn element_ids(&self) -> Vec<ElementId> {
+        self.path
+            .iter()
+            .filter_map(|entry| entry.element_id)
+            .collect()
+    }
+}
+
+/// Hit test render object subtree rooted at `id` with `position` in the coordinate of `id`.
+///
+/// Children are tested in reverse paint order, so the render object painted on top is hit first.
+pub fn hit_test(
+    render_tree: &Arena<RenderObject>,
+    id: RenderObjectId,
+    position: Offset,
+) -> HitTestResult {
+    let mut context = HitTestContext {
+        render_tree,
+        id,
+        transform: Transform::IDENTITY,
+        result: HitTestResult::default(),
+    };
+
+    context.hit_test_render_object(id, position);
+
+    context.result
+}
+
+/// Access to the children of the render object in [`RenderObjectLifecycle::hit_test`].
+pub struct HitTestContext<'a> {
+    render_tree: &'a Arena<RenderObject>,
+    id: RenderObjectId,
+    /// Maps the hit test coordinate into the local coordinate of `id`.
+    transform: Transform,
+    result: HitTestResult,
+}
+
+impl<'a> HitTestContext<'a> {
+    /// Render object being hit tested.
+    pub fn to_id(&self) -> RenderObjectId {
+        self.id
+    }
+
+    /// Size of the render object being hit tested computed by the last layout.
+    pub fn size(&self) -> Size {
+        self.render_tree
+            .get(self.id)
+            .and_then(|node| node.get().size())
+            .unwrap_or_default()
+    }
+
+    /// Children in paint order.
+    pub fn children(&self) -> Vec<RenderObjectId> {
+        self.id.children(self.render_tree).collect()
+    }
+
+    /// Hit test `child` with `position` in the local coordinate, applying the child offset set
+    /// by layout. Returns true if the child was hit.
+    pub fn hit_test_child(&mut self, child: RenderObjectId, position: Offset) -> bool {
+        let Some(node) = self.render_tree.get(child) else {
+            return false;
+        };
+
+        let offset = node.get().offset();
+
+        self.with_transform(Transform::translation(-offset.dx, -offset.dy), |context| {
+            context.hit_test_render_object(child, position - offset)
+        })
+    }
+
+    /// Hit test `child` painted with `transform`, e.g. by [`PaintContext::push_transform`].
+    ///
+    /// Nothing is hit if `transform` is not invertible.
+    pub fn hit_test_child_with_transform(
+        &mut self,
+        child: RenderObjectId,
+        transform: &Transform,
+        position: Offset,
+    ) -> bool {
+        let Some(inverse) = transform.invert() else {
+            return false;
+        };
+
+        self.with_transform(inverse, |context| {
+            context.hit_test_child(child, inverse.map_point(position))
+        })
+    }
+
+    /// Hit test the children in reverse paint order until one is hit, returns true if hit.
+    pub fn hit_test_children(&mut self, position: Offset) -> bool {
+        self.children()
+            .into_iter()
+            .rev()
+            .any(|child| self.hit_test_child(child, position))
+    }
+
+    /// Add the render object being hit tested to the result, after its hit descendants.
+    pub fn add_self(&mut self) {
+        let element_id = self
+            .render_tree
+            .get(self.id)
+            .and_then(|node| node.get().element_id());
+
+        self.result.path.push(HitTestEntry {
+            render_object_id: self.id,
+            element_id,
+            transform: self.transform,
+        });
+    }
+
+    fn with_transform<R, F: FnOnce(&mut Self) -> R>(&mut self, transform: Transform, f: F) -> R {
+        let parent = self.transform;
+
+        self.transform = parent.then(&transform);
+
+        let result = f(self);
+
+        self.transform = parent;
+
+        result
+    }
+
+    fn hit_test_render_object(&mut self, id: RenderObjectId, position: Offset) -> bool {
+        let Some(node) = self.render_tree.get(id) else {
+            return false;
+        };
+
+        let render_object = node.get().clone();
+
+        let parent = std::mem::replace(&mut self.id, id);
+
+        let hit = render_object.0.borrow().lifecycle.hit_test(self, position);
+
+        self.id = parent;
+
+        hit
+    }
+}
+
+/// Flag the parent render object of `id` for relayout, called when children are added, removed or moved.
+pub fn mark_parent_needs_layout(render_tree: &Arena<RenderObject>, id: RenderObjectId) {
+    let parent = render_tree
+        .get(id)
+        .and_then(|node| node.parent())
+        .and_then(|parent| render_tree.get(parent));
+
+    if let Some(parent) = parent {
+        parent.get().mark_needs_layout();
+    }
 }
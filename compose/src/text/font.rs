@@ -0,0 +1,197 @@
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, io, rc::Rc};
+
+use ttf_parser::{name_id, OutlineBuilder};
+
+use crate::{geometry::Transform, painting::Path};
+
+/// Vertical metrics of a font at a font size, in logical pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FontMetrics {
+    /// Distance from the baseline to the top of the tallest glyphs.
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the lowest glyphs, positive.
+    pub descent: f32,
+    /// Recommended gap between lines.
+    pub line_gap: f32,
+}
+
+struct FontData {
+    family: String,
+    data: Vec<u8>,
+    index: u32,
+    units_per_em: f32,
+    ascender: f32,
+    descender: f32,
+    line_gap: f32,
+    /// Glyph outlines in font units with the y axis pointing down.
+    outlines: RefCell<HashMap<u16, Path>>,
+}
+
+/// Font face parsed from TTF or OTF data, cheap to clone.
+#[derive(Clone)]
+pub struct Font(Rc<FontData>);
+
+impl PartialEq for Font {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Debug for Font {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Font({:?})", self.0.family)
+    }
+}
+
+impl Font {
+    /// Parse face `index` of a font file or collection, the family is read from the name table.
+    pub fn from_bytes(data: Vec<u8>, index: u32) -> io::Result<Self> {
+        let face = ttf_parser::Face::parse(&data, index)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let family = face
+            .names()
+            .into_iter()
+            .filter(|name| name.name_id == name_id::TYPOGRAPHIC_FAMILY)
+            .chain(
+                face.names()
+                    .into_iter()
+                    .filter(|name| name.name_id == name_id::FAMILY),
+            )
+            .find_map(|name| name.to_string())
+            .unwrap_or_default();
+
+        let units_per_em = face.units_per_em() as f32;
+        let ascender = face.ascender() as f32;
+        let descender = face.descender() as f32;
+        let line_gap = face.line_gap() as f32;
+
+        Ok(Self(Rc::new(FontData {
+            family,
+            data,
+            index,
+            units_per_em,
+            ascender,
+            descender,
+            line_gap,
+            outlines: Default::default(),
+        })))
+    }
+
+    /// Load the first face of a local TTF or OTF file.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> io::Result<Self> {
+        Self::from_bytes(std::fs::read(path)?, 0)
+    }
+
+    pub fn family(&self) -> &str {
+        &self.0.family
+    }
+
+    /// Returns the scale from font units to logical pixels at `font_size`.
+    pub fn scale(&self, font_size: f32) -> f32 {
+        font_size / self.0.units_per_em
+    }
+
+    pub fn metrics(&self, font_size: f32) -> FontMetrics {
+        let scale = self.scale(font_size);
+
+        FontMetrics {
+            ascent: self.0.ascender * scale,
+            descent: -self.0.descender * scale,
+            line_gap: self.0.line_gap * scale,
+        }
+    }
+
+    /// Returns the face for shaping, the data is parsed again, which is cheap.
+    pub(crate) fn face(&self) -> Option<rustybuzz::Face<'_>> {
+        rustybuzz::Face::from_slice(&self.0.data, self.0.index)
+    }
+
+    /// Returns the outline of glyph `id` at `font_size`, with its origin on the baseline
+    /// and the y axis pointing down.
+    pub fn outline(&self, id: u16, font_size: f32) -> Path {
+        let scale = self.scale(font_size);
+
+        let mut outlines = self.0.outlines.borrow_mut();
+
+        let outline = outlines.entry(id).or_insert_with(|| {
+            let mut builder = PathBuilder(Path::new());
+
+            if let Some(face) = self.face() {
+                face.outline_glyph(ttf_parser::GlyphId(id), &mut builder);
+            }
+
+            builder.0
+        });
+
+        outline.transform(&Transform::scale(scale, scale))
+    }
+}
+
+/// Collect glyph outlines, flipping the y axis of the font units.
+struct PathBuilder(Path);
+
+impl OutlineBuilder for PathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to(x, -y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to(x, -y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to(x1, -y1, x, -y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.cubic_to(x1, -y1, x2, -y2, x, -y);
+    }
+
+    fn close(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Fonts available to paragraphs, looked up by family.
+#[derive(Debug, Clone, Default)]
+pub struct FontCollection(Rc<RefCell<Vec<Font>>>);
+
+impl PartialEq for FontCollection {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl FontCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&self, font: Font) {
+        self.0.borrow_mut().push(font);
+    }
+
+    /// Load a local TTF or OTF file and add it, returns the loaded font.
+    pub fn load_file<P: AsRef<std::path::Path>>(&self, path: P) -> io::Result<Font> {
+        let font = Font::from_file(path)?;
+
+        self.add(font.clone());
+
+        Ok(font)
+    }
+
+    /// Returns the first font of `family`, ignoring case, falling back to the first font added.
+    pub fn resolve(&self, family: Option<&str>) -> Option<Font> {
+        let fonts = self.0.borrow();
+
+        family
+            .and_then(|family| {
+                fonts
+                    .iter()
+                    .find(|font| font.family().eq_ignore_ascii_case(family))
+            })
+            .or_else(|| fonts.first())
+            .cloned()
+    }
+}
@@ -0,0 +1,11 @@
+mod font;
+pub use font::*;
+
+mod style;
+pub use style::*;
+
+mod paragraph;
+pub use paragraph::*;
+
+mod render;
+pub use render::*;
@@ -0,0 +1,687 @@
+use std::ops::Range;
+
+use rustybuzz::{Direction, UnicodeBuffer};
+use unicode_linebreak::BreakOpportunity;
+
+use crate::{
+    geometry::{Offset, Rect},
+    painting::{Canvas, Glyph, Paint, TextRun},
+};
+
+use super::{Font, FontCollection, FontMetrics, ParagraphStyle, TextAlign};
+
+/// Tolerance of the fit test, so text laid out at its own intrinsic width doesn't wrap.
+const EPSILON: f32 = 1e-3;
+
+/// Returns true if `c` ends a line, see UAX #14 mandatory breaks.
+fn is_line_terminator(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+#[derive(Debug, Clone)]
+struct ShapedGlyph {
+    id: u16,
+    /// Offset of the glyph origin relative to the cluster origin on the baseline.
+    offset: Offset,
+}
+
+/// Glyphs shaped from `text[start..end]`, the smallest unit of line breaking and hit testing.
+#[derive(Debug, Clone)]
+struct Cluster {
+    start: usize,
+    end: usize,
+    advance: f32,
+    glyphs: Vec<ShapedGlyph>,
+    whitespace: bool,
+    /// Line terminators have no advance and no glyphs.
+    terminator: bool,
+}
+
+/// Text between two break opportunities.
+#[derive(Debug, Clone)]
+struct Segment {
+    start: usize,
+    end: usize,
+    clusters: Range<usize>,
+    /// The segment ends with a line terminator.
+    hard_break: bool,
+}
+
+/// Geometry of a laid out line, in the paragraph coordinate.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LineMetrics {
+    /// Byte range of the text on the line, without the line terminator.
+    pub start: usize,
+    pub end: usize,
+    /// The line ends with a line terminator rather than wrapping.
+    pub hard_break: bool,
+    /// Width of the line without trailing whitespace.
+    pub width: f32,
+    /// Position of the left edge of the line after alignment.
+    pub left: f32,
+    pub top: f32,
+    /// Position of the alphabetic baseline.
+    pub baseline: f32,
+    pub ascent: f32,
+    pub descent: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Clone)]
+struct Line {
+    metrics: LineMetrics,
+    clusters: Range<usize>,
+    /// End of the clusters up to the last non whitespace cluster.
+    visible_end: usize,
+    /// Extra advance of the inner whitespace clusters, when justified.
+    word_spacing: f32,
+    /// The ellipsis is drawn after the clusters.
+    ellipsis: bool,
+}
+
+/// Block of text in a single style, shaped once and laid out for a width by [`Paragraph::layout`].
+///
+/// Text is shaped left to right with the font of the style family, falling back to the
+/// first font of the collection. Without fonts text has no glyphs and no width.
+#[derive(Debug, Clone)]
+pub struct Paragraph {
+    text: String,
+    style: ParagraphStyle,
+    font: Option<Font>,
+    font_metrics: FontMetrics,
+    clusters: Vec<Cluster>,
+    segments: Vec<Segment>,
+    ellipsis: Vec<Cluster>,
+    min_intrinsic_width: f32,
+    max_intrinsic_width: f32,
+    width: f32,
+    lines: Vec<Line>,
+    did_exceed_max_lines: bool,
+}
+
+impl Paragraph {
+    pub fn new(text: &str, style: ParagraphStyle, fonts: &FontCollection) -> Self {
+        let text_style = &style.text_style;
+
+        let font = fonts.resolve(text_style.font_family.as_deref());
+
+        let font_metrics = match &font {
+            Some(font) => font.metrics(text_style.font_size),
+            None => FontMetrics {
+                ascent: text_style.font_size * 0.8,
+                descent: text_style.font_size * 0.2,
+                line_gap: 0.0,
+            },
+        };
+
+        let clusters = shape(text, font.as_ref(), text_style.font_size);
+
+        let ellipsis = style
+            .ellipsis
+            .as_deref()
+            .map(|ellipsis| shape(ellipsis, font.as_ref(), text_style.font_size))
+            .unwrap_or_default();
+
+        let mut segments = vec![];
+        let mut start = 0;
+
+        for (end, opportunity) in unicode_linebreak::linebreaks(text) {
+            let cluster_index = |byte: usize| clusters.partition_point(|c| c.start < byte);
+
+            segments.push(Segment {
+                start,
+                end,
+                clusters: cluster_index(start)..cluster_index(end),
+                hard_break: opportunity == BreakOpportunity::Mandatory
+                    && text[..end]
+                        .chars()
+                        .next_back()
+                        .is_some_and(is_line_terminator),
+            });
+
+            start = end;
+        }
+
+        let mut paragraph = Self {
+            text: text.to_owned(),
+            style,
+            font,
+            font_metrics,
+            clusters,
+            segments,
+            ellipsis,
+            min_intrinsic_width: 0.0,
+            max_intrinsic_width: 0.0,
+            width: 0.0,
+            lines: vec![],
+            did_exceed_max_lines: false,
+        };
+
+        paragraph.compute_intrinsic_widths();
+
+        paragraph
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn style(&self) -> &ParagraphStyle {
+        &self.style
+    }
+
+    /// Width passed to the last [`Paragraph::layout`].
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.lines
+            .last()
+            .map_or(0.0, |line| line.metrics.top + line.metrics.height)
+    }
+
+    /// Width of the widest line, without trailing whitespace.
+    pub fn longest_line(&self) -> f32 {
+        self.lines
+            .iter()
+            .map(|line| line.metrics.width)
+            .fold(0.0, f32::max)
+    }
+
+    /// Width of the widest unbreakable run of text, narrower widths overflow.
+    pub fn min_intrinsic_width(&self) -> f32 {
+        self.min_intrinsic_width
+    }
+
+    /// Width of the text laid out without soft wrapping, wider widths don't change the layout.
+    pub fn max_intrinsic_width(&self) -> f32 {
+        self.max_intrinsic_width
+    }
+
+    /// Height of the paragraph laid out for `width`, without changing the current layout.
+    pub fn intrinsic_height(&self, width: f32) -> f32 {
+        let (lines, _) = self.truncate_lines(self.break_lines(width), width);
+
+        lines.len() as f32 * self.line_height()
+    }
+
+    /// Distance from the top of the paragraph to the baseline of the first line.
+    pub fn alphabetic_baseline(&self) -> f32 {
+        self.lines.first().map_or(0.0, |line| line.metrics.baseline)
+    }
+
+    /// Returns true if lines were dropped by the last layout because of `max_lines`.
+    pub fn did_exceed_max_lines(&self) -> bool {
+        self.did_exceed_max_lines
+    }
+
+    pub fn line_metrics(&self) -> Vec<LineMetrics> {
+        self.lines.iter().map(|line| line.metrics).collect()
+    }
+
+    /// Break the text into lines no wider than `width` if possible, and position them.
+    ///
+    /// Text wraps at the break opportunities of UAX #14, trailing whitespace hangs past the
+    /// width, and runs of text wider than `width` are split between clusters.
+    pub fn layout(&mut self, width: f32) {
+        let (mut lines, did_exceed_max_lines) = self.truncate_lines(self.break_lines(width), width);
+
+        let align_width = if width.is_finite() {
+            width
+        } else {
+            lines
+                .iter()
+                .map(|line| line.metrics.width)
+                .fold(0.0, f32::max)
+        };
+
+        let FontMetrics {
+            ascent, descent, ..
+        } = self.font_metrics;
+
+        let line_height = self.line_height();
+        let half_leading = (line_height - ascent - descent) / 2.0;
+
+        let count = lines.len();
+
+        for (index, line) in lines.iter_mut().enumerate() {
+            let extra = (align_width - line.metrics.width).max(0.0);
+
+            line.metrics.left = match self.style.align {
+                TextAlign::Left | TextAlign::Justify => 0.0,
+                TextAlign::Right => extra,
+                TextAlign::Center => extra / 2.0,
+            };
+
+            let justify = self.style.align == TextAlign::Justify
+                && index + 1 < count
+                && !line.metrics.hard_break
+                && !line.ellipsis;
+
+            if justify {
+                let spaces = (line.clusters.start..line.visible_end)
+                    .filter(|&index| self.clusters[index].whitespace)
+                    .count();
+
+                if spaces > 0 {
+                    line.word_spacing = extra / spaces as f32;
+                    line.metrics.width = align_width;
+                }
+            }
+
+            line.metrics.top = index as f32 * line_height;
+            line.metrics.baseline = line.metrics.top + half_leading + ascent;
+            line.metrics.ascent = ascent;
+            line.metrics.descent = descent;
+            line.metrics.height = line_height;
+        }
+
+        self.width = width;
+        self.lines = lines;
+        self.did_exceed_max_lines = did_exceed_max_lines;
+    }
+
+    /// Returns the zero width caret before the character at byte `index`, at the start of
+    /// the next line if `index` is a soft wrap position.
+    pub fn caret_rect(&self, index: usize) -> Rect {
+        let index = index.min(self.text.len());
+
+        let Some(line) = self
+            .lines
+            .iter()
+            .rev()
+            .find(|line| line.metrics.start <= index)
+            .or(self.lines.first())
+        else {
+            return Rect::from_ltwh(0.0, 0.0, 0.0, self.line_height());
+        };
+
+        let metrics = &line.metrics;
+
+        Rect::from_ltwh(
+            metrics.left + self.x_for_index(line, index),
+            metrics.top,
+            0.0,
+            metrics.height,
+        )
+    }
+
+    /// Returns the byte index of the caret position nearest to `position`.
+    pub fn index_for_position(&self, position: Offset) -> usize {
+        let Some(line) = self
+            .lines
+            .iter()
+            .find(|line| position.dy < line.metrics.top + line.metrics.height)
+            .or(self.lines.last())
+        else {
+            return 0;
+        };
+
+        let offsets = self.cluster_offsets(line);
+        let x = position.dx - line.metrics.left;
+
+        line.clusters
+            .clone()
+            .zip(offsets.windows(2))
+            .find(|(_, offsets)| x < (offsets[0] + offsets[1]) / 2.0)
+            .map_or(line.metrics.end, |(index, _)| self.clusters[index].start)
+    }
+
+    /// Returns one box per line covering the text of byte `range`.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<Rect> {
+        self.lines
+            .iter()
+            .filter(|line| range.start < line.metrics.end && line.metrics.start < range.end)
+            .filter_map(|line| {
+                let metrics = &line.metrics;
+
+                let left = self.x_for_index(line, range.start.max(metrics.start));
+                let right = self.x_for_index(line, range.end.min(metrics.end));
+
+                (right > left).then(|| {
+                    Rect::from_ltwh(
+                        metrics.left + left,
+                        metrics.top,
+                        right - left,
+                        metrics.height,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Draw the laid out lines with the top left corner at `offset`, a text run per line.
+    pub fn paint(&self, canvas: &mut dyn Canvas, offset: Offset) {
+        let Some(font) = &self.font else {
+            return;
+        };
+
+        let font_size = self.style.text_style.font_size;
+        let paint = Paint::fill(self.style.text_style.color);
+
+        for line in &self.lines {
+            let offsets = self.cluster_offsets(line);
+
+            let mut text = self.text[line.metrics.start..line.metrics.end].to_owned();
+            let mut glyphs = vec![];
+
+            let mut push_glyphs = |cluster: &Cluster, x: f32| {
+                glyphs.extend(cluster.glyphs.iter().map(|glyph| Glyph {
+                    id: glyph.id,
+                    offset: Offset::new(x + glyph.offset.dx, glyph.offset.dy),
+                    outline: font.outline(glyph.id, font_size),
+                }));
+            };
+
+            for (index, x) in line.clusters.clone().zip(&offsets) {
+                push_glyphs(&self.clusters[index], *x);
+            }
+
+            if line.ellipsis {
+                let mut x = offsets.last().copied().unwrap_or_default();
+
+                for cluster in &self.ellipsis {
+                    push_glyphs(cluster, x);
+                    x += cluster.advance;
+                }
+
+                text.push_str(self.style.ellipsis.as_deref().unwrap_or_default());
+            }
+
+            if glyphs.is_empty() {
+                continue;
+            }
+
+            let run = TextRun {
+                text,
+                font_family: font.family().to_owned(),
+                font_size,
+                glyphs,
+            };
+
+            canvas.draw_text(
+                &run,
+                offset + Offset::new(line.metrics.left, line.metrics.baseline),
+                &paint,
+            );
+        }
+    }
+
+    fn line_height(&self) -> f32 {
+        let FontMetrics {
+            ascent,
+            descent,
+            line_gap,
+        } = self.font_metrics;
+
+        match self.style.text_style.height {
+            Some(height) => height * self.style.text_style.font_size,
+            None => ascent + descent + line_gap,
+        }
+    }
+
+    /// Returns the advance of `clusters` without and with the trailing whitespace.
+    fn measure(&self, clusters: Range<usize>) -> (f32, f32) {
+        let clusters = &self.clusters[clusters];
+
+        let visible = clusters
+            .iter()
+            .rposition(|cluster| !cluster.whitespace)
+            .map_or(0.0, |last| {
+                clusters[..=last]
+                    .iter()
+                    .map(|cluster| cluster.advance)
+                    .sum()
+            });
+
+        (
+            visible,
+            clusters.iter().map(|cluster| cluster.advance).sum(),
+        )
+    }
+
+    fn compute_intrinsic_widths(&mut self) {
+        let mut line = 0.0;
+
+        for segment in &self.segments {
+            let (visible, advance) = self.measure(segment.clusters.clone());
+
+            self.min_intrinsic_width = self.min_intrinsic_width.max(visible);
+            self.max_intrinsic_width = self.max_intrinsic_width.max(line + visible);
+
+            line += advance;
+
+            if segment.hard_break {
+                line = 0.0;
+            }
+        }
+    }
+
+    /// Line of `clusters` starting at byte `start`, without trailing line terminators.
+    fn line(&self, start: usize, mut clusters: Range<usize>, hard_break: bool) -> Line {
+        while clusters.end > clusters.start && self.clusters[clusters.end - 1].terminator {
+            clusters.end -= 1;
+        }
+
+        let visible_end = self.clusters[clusters.clone()]
+            .iter()
+            .rposition(|cluster| !cluster.whitespace)
+            .map_or(clusters.start, |last| clusters.start + last + 1);
+
+        let end = match clusters.is_empty() {
+            true => start,
+            false => self.clusters[clusters.end - 1].end,
+        };
+
+        Line {
+            metrics: LineMetrics {
+                start,
+                end,
+                hard_break,
+                width: self.measure(clusters.start..visible_end).0,
+                ..Default::default()
+            },
+            clusters,
+            visible_end,
+            word_spacing: 0.0,
+            ellipsis: false,
+        }
+    }
+
+    /// Greedily fill lines with segments, splitting segments which fit no line.
+    fn break_lines(&self, width: f32) -> Vec<Line> {
+        let mut lines = vec![];
+
+        let mut start = 0;
+        let mut start_byte = 0;
+        let mut advance = 0.0;
+
+        for segment in &self.segments {
+            let (segment_visible, segment_advance) = self.measure(segment.clusters.clone());
+
+            if start < segment.clusters.start && advance + segment_visible > width + EPSILON {
+                lines.push(self.line(start_byte, start..segment.clusters.start, false));
+
+                start = segment.clusters.start;
+                start_byte = segment.start;
+                advance = 0.0;
+            }
+
+            if segment_visible > width + EPSILON {
+                for index in segment.clusters.clone() {
+                    let cluster = &self.clusters[index];
+
+                    if start < index
+                        && !cluster.whitespace
+                        && advance + cluster.advance > width + EPSILON
+                    {
+                        lines.push(self.line(start_byte, start..index, false));
+
+                        start = index;
+                        start_byte = cluster.start;
+                        advance = 0.0;
+                    }
+
+                    advance += cluster.advance;
+                }
+            } else {
+                advance += segment_advance;
+            }
+
+            if segment.hard_break {
+                lines.push(self.line(start_byte, start..segment.clusters.end, true));
+
+                start = segment.clusters.end;
+                start_byte = segment.end;
+                advance = 0.0;
+            }
+        }
+
+        // Text after the last hard break, an empty line if the text ends with a line terminator.
+        lines.push(self.line(start_byte, start..self.clusters.len(), false));
+
+        lines
+    }
+
+    /// Drop the lines past `max_lines` and ellipsize the last line, returns true if lines were dropped.
+    fn truncate_lines(&self, mut lines: Vec<Line>, width: f32) -> (Vec<Line>, bool) {
+        let Some(max_lines) = self.style.max_lines.filter(|max| lines.len() > *max) else {
+            return (lines, false);
+        };
+
+        lines.truncate(max_lines);
+
+        if let (Some(line), Some(_)) = (lines.last_mut(), &self.style.ellipsis) {
+            let ellipsis_width: f32 = self.ellipsis.iter().map(|cluster| cluster.advance).sum();
+
+            let mut end = line.visible_end;
+
+            while end > line.clusters.start
+                && (self.clusters[end - 1].whitespace
+                    || self.measure(line.clusters.start..end).0 + ellipsis_width > width + EPSILON)
+            {
+                end -= 1;
+            }
+
+            *line = self.line(line.metrics.start, line.clusters.start..end, false);
+
+            line.metrics.width += ellipsis_width;
+            line.ellipsis = true;
+        }
+
+        (lines, true)
+    }
+
+    /// Returns the position of each cluster of `line` relative to the line left, followed by the line end.
+    fn cluster_offsets(&self, line: &Line) -> Vec<f32> {
+        let mut x = 0.0;
+
+        let mut offsets = line
+            .clusters
+            .clone()
+            .map(|index| {
+                let cluster = &self.clusters[index];
+                let offset = x;
+
+                x += cluster.advance;
+
+                if cluster.whitespace && index < line.visible_end {
+                    x += line.word_spacing;
+                }
+
+                offset
+            })
+            .collect::<Vec<_>>();
+
+        offsets.push(x);
+
+        offsets
+    }
+
+    /// Returns the position of the cluster containing byte `index` relative to the line left.
+    fn x_for_index(&self, line: &Line, index: usize) -> f32 {
+        let offsets = self.cluster_offsets(line);
+
+        line.clusters
+            .clone()
+            .position(|cluster| index < self.clusters[cluster].end)
+            .map_or(offsets[offsets.len() - 1], |position| offsets[position])
+    }
+}
+
+/// Shape `text` left to right into clusters, one cluster per character without font.
+fn shape(text: &str, font: Option<&Font>, font_size: f32) -> Vec<Cluster> {
+    let mut clusters: Vec<Cluster> = vec![];
+
+    match font.and_then(|font| Some((font.face()?, font.scale(font_size)))) {
+        Some((face, scale)) => {
+            let mut buffer = UnicodeBuffer::new();
+            buffer.push_str(text);
+            buffer.set_direction(Direction::LeftToRight);
+
+            let output = rustybuzz::shape(&face, &[], buffer);
+
+            for (info, position) in output.glyph_infos().iter().zip(output.glyph_positions()) {
+                let start = info.cluster as usize;
+
+                if clusters.last().map(|cluster| cluster.start) != Some(start) {
+                    clusters.push(Cluster {
+                        start,
+                        end: start,
+                        advance: 0.0,
+                        glyphs: vec![],
+                        whitespace: false,
+                        terminator: false,
+                    });
+                }
+
+                let cluster = clusters.last_mut().unwrap();
+
+                cluster.glyphs.push(ShapedGlyph {
+                    id: info.glyph_id as u16,
+                    offset: Offset::new(
+                        cluster.advance + position.x_offset as f32 * scale,
+                        -position.y_offset as f32 * scale,
+                    ),
+                });
+                cluster.advance += position.x_advance as f32 * scale;
+            }
+        }
+        None => {
+            clusters.extend(text.char_indices().map(|(start, _)| Cluster {
+                start,
+                end: start,
+                advance: 0.0,
+                glyphs: vec![],
+                whitespace: false,
+                terminator: false,
+            }));
+        }
+    }
+
+    let ends = clusters
+        .iter()
+        .skip(1)
+        .map(|cluster| cluster.start)
+        .chain([text.len()])
+        .collect::<Vec<_>>();
+
+    for (cluster, end) in clusters.iter_mut().zip(ends) {
+        let text = &text[cluster.start..end];
+
+        cluster.end = end;
+        cluster.whitespace = text.chars().all(char::is_whitespace);
+        cluster.terminator = text.chars().any(is_line_terminator);
+
+        if cluster.terminator {
+            cluster.advance = 0.0;
+            cluster.glyphs.clear();
+        }
+    }
+
+    clusters
+}
@@ -0,0 +1,171 @@
+use crate::{
+    element::{BuildContext, StatelessElement},
+    geometry::{BoxConstraints, Offset, Rect, Size},
+    macos::{Inherited, Render, Stateless},
+    render::{LayoutContext, PaintContext, RenderObjectLifecycle, RenderObjectUpdate},
+    view::{IntoView, View},
+};
+
+use super::{FontCollection, LineMetrics, Paragraph, ParagraphStyle, TextAlign, TextStyle};
+
+/// Lays out and paints a [`Paragraph`], as wide as its longest unwrapped line allowed by the constraints.
+#[derive(Debug, Clone)]
+pub struct RenderParagraph {
+    paragraph: Paragraph,
+}
+
+impl RenderParagraph {
+    pub fn new(paragraph: Paragraph) -> Self {
+        Self { paragraph }
+    }
+
+    /// Paragraph with the geometry of the last layout, see [`Paragraph::caret_rect`].
+    pub fn paragraph(&self) -> &Paragraph {
+        &self.paragraph
+    }
+
+    /// Replace the paragraph, the caller marks the render object as needing layout.
+    pub fn set_paragraph(&mut self, paragraph: Paragraph) {
+        self.paragraph = paragraph;
+    }
+
+    pub fn compute_min_intrinsic_width(&self) -> f32 {
+        self.paragraph.min_intrinsic_width()
+    }
+
+    pub fn compute_max_intrinsic_width(&self) -> f32 {
+        self.paragraph.max_intrinsic_width()
+    }
+
+    /// Height of the paragraph laid out for `width`.
+    pub fn compute_intrinsic_height(&self, width: f32) -> f32 {
+        self.paragraph.intrinsic_height(width)
+    }
+
+    pub fn line_metrics(&self) -> Vec<LineMetrics> {
+        self.paragraph.line_metrics()
+    }
+
+    pub fn caret_rect(&self, index: usize) -> Rect {
+        self.paragraph.caret_rect(index)
+    }
+
+    pub fn index_for_position(&self, position: Offset) -> usize {
+        self.paragraph.index_for_position(position)
+    }
+
+    pub fn selection_rects(&self, range: std::ops::Range<usize>) -> Vec<Rect> {
+        self.paragraph.selection_rects(range)
+    }
+}
+
+impl RenderObjectLifecycle for RenderParagraph {
+    fn perform_layout(
+        &mut self,
+        _context: &mut LayoutContext,
+        constraints: BoxConstraints,
+    ) -> Size {
+        let width = self
+            .paragraph
+            .max_intrinsic_width()
+            .min(constraints.max_width)
+            .max(constraints.min_width);
+
+        self.paragraph.layout(width);
+
+        constraints.constrain(Size::new(width, self.paragraph.height()))
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        self.paragraph.paint(context.canvas(), offset);
+    }
+}
+
+/// Paint `text` in a fully specified style with `fonts`, see [`Text`] for the inherited style.
+#[derive(Debug, Clone, Default, PartialEq, Render)]
+#[render(update_render_object)]
+pub struct RichText {
+    pub text: String,
+    pub style: ParagraphStyle,
+    pub fonts: FontCollection,
+}
+
+impl RichText {
+    fn paragraph(&self) -> Paragraph {
+        Paragraph::new(&self.text, self.style.clone(), &self.fonts)
+    }
+
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        RenderParagraph::new(self.paragraph())
+    }
+
+    fn update_render_object(
+        &self,
+        render_object: &mut dyn RenderObjectLifecycle,
+    ) -> RenderObjectUpdate {
+        match render_object.downcast_mut::<RenderParagraph>() {
+            Some(render_object) => {
+                render_object.set_paragraph(self.paragraph());
+
+                RenderObjectUpdate::Layout
+            }
+            None => RenderObjectUpdate::None,
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+/// Text style and fonts of the [`Text`] descendants which don't specify their own style.
+#[derive(Debug, Clone, Default, PartialEq, Inherited)]
+pub struct DefaultTextStyle {
+    pub style: TextStyle,
+    pub fonts: FontCollection,
+    pub child: View,
+}
+
+impl DefaultTextStyle {
+    fn child(&self) -> View {
+        self.child.clone()
+    }
+}
+
+/// Paint `text` with the nearest [`DefaultTextStyle`], `style` replaces its style.
+#[derive(Debug, Clone, Default, PartialEq, Stateless)]
+pub struct Text {
+    pub text: String,
+    pub style: Option<TextStyle>,
+    pub align: TextAlign,
+    pub max_lines: Option<usize>,
+    pub ellipsis: Option<String>,
+}
+
+impl Text {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    fn build(&self, element: &StatelessElement) -> impl IntoView {
+        let (style, fonts) = element
+            .depend_on_inherited(|default: &DefaultTextStyle| {
+                (default.style.clone(), default.fonts.clone())
+            })
+            .unwrap_or_default();
+
+        RichText {
+            text: self.text.clone(),
+            style: ParagraphStyle {
+                text_style: self.style.clone().unwrap_or(style),
+                align: self.align,
+                max_lines: self.max_lines,
+                ellipsis: self.ellipsis.clone(),
+            },
+            fonts,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use crate::painting::Color;
+
+/// Font and color of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    /// Family resolved in the [`FontCollection`](super::FontCollection), `None` for its first font.
+    pub font_family: Option<String>,
+    pub font_size: f32,
+    pub color: Color,
+    /// Line height as a multiple of the font size, `None` for the font line height.
+    pub height: Option<f32>,
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self {
+            font_family: None,
+            font_size: 14.0,
+            color: Color::BLACK,
+            height: None,
+        }
+    }
+}
+
+/// Horizontal alignment of the lines of a paragraph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Right,
+    Center,
+    /// Stretch soft wrapped lines to the paragraph width by widening spaces,
+    /// other lines are aligned left.
+    Justify,
+}
+
+/// Style of a [`Paragraph`](super::Paragraph).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParagraphStyle {
+    pub text_style: TextStyle,
+    pub align: TextAlign,
+    /// Lines past `max_lines` are dropped, `None` for no limit.
+    pub max_lines: Option<usize>,
+    /// Appended to the last line when lines are dropped, e.g. `"…"`.
+    pub ellipsis: Option<String>,
+}
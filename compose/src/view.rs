@@ -1,11 +1,13 @@
-use std::any::Any;
+use std::any::{Any, TypeId};
 use std::cell::RefCell;
 use std::fmt::Debug;
-use std::rc::Rc;
+use std::panic::Location;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
 
 use indextree::Arena;
 
-use crate::keypath::KeyPath;
+use crate::keypath::{GlobalKey, KeyPath};
 
 pub use super::element::*;
 pub use super::render::*;
@@ -15,20 +17,135 @@ pub trait IntoView {
     fn into_view(self) -> View;
 }
 
+/// Convert iterator of configurations into views, layering the iteration index
+/// on top of the caller location so items of the same loop get distinct key paths.
+///
+/// Items with a user key, e.g. a `#[compose(key)]` field, keep it instead of the index,
+/// so reordered items keep their elements and states.
+pub trait IntoViews: Iterator {
+    fn into_views(self) -> Vec<View>;
+}
+
+impl<I> IntoViews for I
+where
+    I: Iterator,
+    I::Item: IntoView,
+{
+    #[track_caller]
+    fn into_views(self) -> Vec<View> {
+        let caller = Location::caller();
+
+        self.enumerate()
+            .map(|(index, item)| {
+                let view = item.into_view();
+
+                let key_path = match view.to_keypath() {
+                    Some(KeyPath::TrackCaller(_)) | None => {
+                        KeyPath::from(caller).join(KeyPath::Index(index))
+                    }
+                    Some(key_path) => key_path.clone().with_caller(caller),
+                };
+
+                view.with_key_path(key_path)
+            })
+            .collect()
+    }
+}
+
+/// Compare configuration with another configuration of any type, which is never equal.
 pub trait AnyEq {
     fn eq(&self, rhs: &dyn Any) -> bool;
 }
 
+/// Identity comparison of configuration fields marked `#[compose(ptr_eq)]`.
+pub trait PtrEq {
+    fn ptr_eq(&self, other: &Self) -> bool;
+}
+
+impl<T: ?Sized> PtrEq for Rc<T> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(self, other)
+    }
+}
+
+impl<T: ?Sized> PtrEq for Weak<T> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Weak::ptr_eq(self, other)
+    }
+}
+
+impl<T: ?Sized> PtrEq for Arc<T> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(self, other)
+    }
+}
+
+impl<T: ?Sized> PtrEq for &T {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        std::ptr::eq(*self, *other)
+    }
+}
+
+impl<T: PtrEq> PtrEq for Option<T> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(lhs), Some(rhs)) => lhs.ptr_eq(rhs),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
 pub trait ToAny {
     fn to_any(&self) -> &dyn Any;
 }
 
+/// Implemented for every `'static` type, lets the framework downcast `dyn State`
+/// without requiring [`ToAny`] from state implementations.
+#[doc(hidden)]
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 pub trait StatefulConfiguration: ToAny + AnyEq + IntoView + Debug {
     fn framework_create_state(&self) -> Box<dyn State>;
 }
 
-pub trait State: Debug {
+/// State of [`StatefulElement`], lives as long as the element.
+///
+/// Lifecycle hooks are called by the element with the state temporarily taken out of it,
+/// the default implementations do nothing.
+pub trait State: AsAny + Debug {
     fn framework_build(&self, element: &StatefulElement) -> View;
+
+    /// Called once when the element is mounted, before the first build.
+    fn framework_init_state(&mut self, _element: &StatefulElement) {}
+
+    /// Called when the element is updated with new configuration, `old` is the replaced configuration.
+    fn framework_did_update_config(
+        &mut self,
+        _element: &StatefulElement,
+        _old: &Configuration<dyn StatefulConfiguration>,
+    ) {
+    }
+
+    /// Called after [`State::framework_init_state`] and when an inherited view the element depends on changed.
+    fn framework_did_change_dependencies(&mut self, _element: &StatefulElement) {}
+
+    /// Called when a deactivated element is reinserted into the tree.
+    fn framework_activate(&mut self, _element: &StatefulElement) {}
+
+    /// Called when the element is removed from the tree, it may be reinserted before the end of build.
+    fn framework_deactivate(&mut self, _element: &StatefulElement) {}
+
+    /// Called when the element is removed from the tree permanently, release resources here.
+    fn framework_dispose(&mut self, _element: &StatefulElement) {}
 }
 
 pub trait StatelessConfiguration: ToAny + AnyEq + IntoView + Debug {
@@ -38,6 +155,25 @@ pub trait StatelessConfiguration: ToAny + AnyEq + IntoView + Debug {
 pub trait RenderObjectConfiguration: ToAny + AnyEq + IntoView + Debug {
     fn framework_render_object_children(&self) -> Vec<View>;
     fn framework_create_render_object(&self) -> RenderObject;
+
+    /// Push properties of this configuration into the existing render object, returns what
+    /// changed so only the needed layout or paint is scheduled. By default do nothing.
+    fn framework_update_render_object(
+        &self,
+        _render_object: &mut dyn RenderObjectLifecycle,
+    ) -> RenderObjectUpdate {
+        RenderObjectUpdate::None
+    }
+}
+
+/// Configuration of a view providing data to its descendants, see [`BuildContext::depend_on_inherited`].
+pub trait InheritedConfiguration: ToAny + AnyEq + IntoView + Debug {
+    fn framework_child(&self) -> View;
+
+    /// Returns true if elements depending on `old` should be rebuilt, by default if `self != old`.
+    fn framework_update_should_notify(&self, old: &dyn Any) -> bool {
+        !AnyEq::eq(self, old)
+    }
 }
 
 #[derive(Debug)]
@@ -58,6 +194,16 @@ where
     }
 }
 
+impl<T> Configuration<T>
+where
+    T: ?Sized + ToAny,
+{
+    /// Call `f` with the concrete configuration, returns `None` if the type mismatch.
+    pub fn downcast<V: 'static, R, F: FnOnce(&V) -> R>(&self, f: F) -> Option<R> {
+        self.view.borrow().to_any().downcast_ref::<V>().map(f)
+    }
+}
+
 impl<T> PartialEq for Configuration<T>
 where
     T: ?Sized + AnyEq + ToAny,
@@ -100,12 +246,25 @@ impl<T: RenderObjectConfiguration + 'static> From<(KeyPath, T)>
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl<T: InheritedConfiguration + 'static> From<(KeyPath, T)>
+    for Configuration<dyn InheritedConfiguration>
+{
+    fn from(value: (KeyPath, T)) -> Self {
+        Configuration {
+            key_path: value.0,
+            view: Rc::new(RefCell::new(value.1)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum View {
+    #[default]
     Empty,
     Stateful(Configuration<dyn StatefulConfiguration>),
     Stateless(Configuration<dyn StatelessConfiguration>),
     RenderObject(Configuration<dyn RenderObjectConfiguration>),
+    Inherited(Configuration<dyn InheritedConfiguration>),
 }
 
 impl View {
@@ -115,17 +274,64 @@ impl View {
             View::Stateful(config) => StatefulElement::new(arena, config).into(),
             View::Stateless(config) => StatelessElement::new(arena, config).into(),
             View::RenderObject(config) => RenderObjectElement::new(arena, config).into(),
+            View::Inherited(config) => InheritedElement::new(arena, config).into(),
         }
     }
 
+    /// Returns true if both views have the same configuration type, e.g. the same struct.
     pub fn same_type(&self, view: &View) -> bool {
-        match (self, view) {
-            (View::Empty, View::Empty) => true,
-            (View::Stateful(_), View::Stateful(_)) => true,
-            (View::Stateless(_), View::Stateless(_)) => true,
-            (View::RenderObject(_), View::RenderObject(_)) => true,
-            _ => false,
+        matches!(
+            (self, view),
+            (View::Empty, View::Empty)
+                | (View::Stateful(_), View::Stateful(_))
+                | (View::Stateless(_), View::Stateless(_))
+                | (View::RenderObject(_), View::RenderObject(_))
+                | (View::Inherited(_), View::Inherited(_))
+        ) && self.to_type_id() == view.to_type_id()
+    }
+
+    /// Returns the type id of the configuration struct, `None` for [`View::Empty`].
+    pub fn to_type_id(&self) -> Option<TypeId> {
+        match self {
+            View::Empty => None,
+            View::Stateful(config) => Some(config.view.borrow().to_any().type_id()),
+            View::Stateless(config) => Some(config.view.borrow().to_any().type_id()),
+            View::RenderObject(config) => Some(config.view.borrow().to_any().type_id()),
+            View::Inherited(config) => Some(config.view.borrow().to_any().type_id()),
+        }
+    }
+
+    /// Returns true if an element created from `self` can be updated with `view`,
+    /// i.e. both configurations have the same type and key path.
+    pub fn can_update(&self, view: &View) -> bool {
+        self.same_type(view) && self.to_keypath() == view.to_keypath()
+    }
+
+    /// Replace configuration key path, does nothing for [`View::Empty`].
+    pub fn with_key_path(mut self, key_path: KeyPath) -> Self {
+        match &mut self {
+            View::Empty => {}
+            View::Stateful(config) => config.key_path = key_path,
+            View::Stateless(config) => config.key_path = key_path,
+            View::RenderObject(config) => config.key_path = key_path,
+            View::Inherited(config) => config.key_path = key_path,
         }
+
+        self
+    }
+
+    /// Layer user key on top of the configuration key path.
+    ///
+    /// Use this to distinguish views created by the same `into_view` call, e.g. inside a `for` loop.
+    pub fn with_key<K: Into<KeyPath>>(self, key: K) -> Self {
+        match self.to_keypath().cloned() {
+            Some(key_path) => self.with_key_path(key_path.join(key)),
+            None => self,
+        }
+    }
+
+    pub fn to_global_key(&self) -> Option<GlobalKey> {
+        self.to_keypath()?.to_global_key()
     }
 
     pub fn to_keypath(&self) -> Option<&KeyPath> {
@@ -134,6 +340,7 @@ impl View {
             View::Stateful(config) => Some(&config.key_path),
             View::Stateless(config) => Some(&config.key_path),
             View::RenderObject(config) => Some(&config.key_path),
+            View::Inherited(config) => Some(&config.key_path),
         }
     }
 }
@@ -143,3 +350,9 @@ impl IntoView for () {
         View::Empty
     }
 }
+
+impl IntoView for View {
+    fn into_view(self) -> View {
+        self
+    }
+}
@@ -0,0 +1,152 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, Default, PartialEq)]
+struct Probe {
+    state_value: Option<u32>,
+    inherited: Option<&'static str>,
+    ancestor_render_object: bool,
+    size: Option<Size>,
+    handle: Option<RebuildHandle>,
+    children: usize,
+}
+
+type SharedProbe = Rc<RefCell<Probe>>;
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    probe: SharedProbe,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            value: 42,
+            probe: self.probe.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+struct RootState {
+    value: u32,
+    probe: SharedProbe,
+}
+
+impl RootState {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Locale {
+            name: "en",
+            child: Holder {
+                children: vec![Prober {
+                    probe: self.probe.clone(),
+                }
+                .into_view()],
+            }
+            .into_view(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Inherited)]
+struct Locale {
+    name: &'static str,
+    child: View,
+}
+
+impl Locale {
+    fn child(&self) -> View {
+        self.child.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Prober {
+    probe: SharedProbe,
+}
+
+impl Prober {
+    fn build(&self, context: &impl BuildContext) -> impl IntoView {
+        let mut probe = self.probe.borrow_mut();
+
+        probe.state_value = context.find_ancestor_state_of_type(|state: &RootState| state.value);
+        probe.inherited = context.get_inherited(|locale: &Locale| locale.name);
+        probe.ancestor_render_object = context.find_ancestor_render_object().is_some();
+        probe.size = context.size();
+        probe.handle = context.to_rebuild_handle();
+
+        // Not built yet.
+        context.visit_children(|_| probe.children += 1);
+
+        Holder { children: vec![] }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Holder {
+    children: Vec<View>,
+}
+
+impl Holder {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+#[test]
+fn test_build_context() {
+    let mut context = FrameworkContext::default();
+
+    let probe = SharedProbe::default();
+
+    context
+        .mount_root(
+            Root {
+                probe: probe.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let handle = {
+        let probe = probe.borrow();
+
+        assert_eq!(probe.state_value, Some(42));
+        assert_eq!(probe.inherited, Some("en"));
+        assert!(probe.ancestor_render_object);
+        assert_eq!(probe.size, None);
+        assert_eq!(probe.children, 0);
+
+        probe.handle.clone().unwrap()
+    };
+
+    // Layout result is visible through the context.
+    let render_object = context
+        .element_tree
+        .borrow()
+        .get(handle.to_id())
+        .unwrap()
+        .get()
+        .first_render_object(&context)
+        .unwrap();
+
+    render_object.0.borrow_mut().size = Some(Size::new(10.0, 20.0));
+
+    handle.mark_needs_build();
+    context.flush_build();
+
+    let probe = probe.borrow();
+
+    assert_eq!(probe.size, Some(Size::new(10.0, 20.0)));
+    assert_eq!(probe.children, 1);
+}
@@ -0,0 +1,262 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Counter {
+    builds: Rc<Cell<usize>>,
+    handle: Rc<RefCell<Option<RebuildHandle>>>,
+}
+
+impl Counter {
+    fn create_state(&self) -> CounterState {
+        CounterState {
+            count: Rc::new(Cell::new(0)),
+            builds: self.builds.clone(),
+            handle: self.handle.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CounterState {
+    count: Rc<Cell<usize>>,
+    builds: Rc<Cell<usize>>,
+    handle: Rc<RefCell<Option<RebuildHandle>>>,
+}
+
+impl State for CounterState {
+    fn framework_build(&self, element: &StatefulElement) -> View {
+        self.builds.set(self.builds.get() + 1);
+
+        *self.handle.borrow_mut() = element.to_rebuild_handle();
+
+        if self.count.get() == 0 {
+            // Trigger rebuild from inside the first build.
+            let count = self.count.clone();
+            element.set_state(move || count.set(1));
+        }
+
+        Label {
+            count: self.count.get(),
+        }
+        .into_view()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Label {
+    count: usize,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct LabelRenderObject {}
+
+impl RenderObjectLifecycle for LabelRenderObject {}
+
+#[test]
+fn test_set_state_rebuilds_dirty_element() {
+    let mut context = FrameworkContext::default();
+
+    let builds = Rc::new(Cell::new(0));
+
+    let element = context
+        .mount_root(
+            Counter {
+                builds: builds.clone(),
+                handle: Default::default(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(builds.get(), 1);
+    assert!(context.build_owner.has_dirty_elements());
+
+    context.flush_build();
+
+    assert_eq!(builds.get(), 2);
+    assert!(!context.build_owner.has_dirty_elements());
+
+    let label_id = element
+        .search_first_render_object_element_id(&context)
+        .unwrap();
+
+    let label = context
+        .element_tree
+        .borrow()
+        .get(label_id)
+        .unwrap()
+        .get()
+        .to_configuration();
+
+    if let View::RenderObject(config) = label {
+        let view = config.view.borrow();
+        let label = view.to_any().downcast_ref::<Label>().unwrap();
+
+        assert_eq!(label.count, 1);
+    } else {
+        panic!("expect render object configuration");
+    }
+
+    // Nothing dirty, flush is a no-op.
+    context.flush_build();
+
+    assert_eq!(builds.get(), 2);
+}
+
+#[test]
+fn test_rebuild_handle() {
+    let mut context = FrameworkContext::default();
+
+    let builds = Rc::new(Cell::new(0));
+    let handle = Rc::new(RefCell::new(None));
+
+    let element = context
+        .mount_root(
+            Counter {
+                builds: builds.clone(),
+                handle: handle.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    context.flush_build();
+
+    let handle = handle.borrow().clone().unwrap();
+
+    assert_eq!(handle.to_id(), element.to_id().unwrap());
+
+    // Duplicate schedule rebuilds only once.
+    handle.mark_needs_build();
+    handle.set_state(|| {});
+
+    context.flush_build();
+
+    assert_eq!(builds.get(), 3);
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Toggle {
+    show: Rc<Cell<bool>>,
+    child_builds: Rc<Cell<usize>>,
+    handle: Rc<RefCell<Option<RebuildHandle>>>,
+    child_handle: Rc<RefCell<Option<RebuildHandle>>>,
+}
+
+impl Toggle {
+    fn create_state(&self) -> ToggleState {
+        ToggleState {
+            show: self.show.clone(),
+            child_builds: self.child_builds.clone(),
+            handle: self.handle.clone(),
+            child_handle: self.child_handle.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ToggleState {
+    show: Rc<Cell<bool>>,
+    child_builds: Rc<Cell<usize>>,
+    handle: Rc<RefCell<Option<RebuildHandle>>>,
+    child_handle: Rc<RefCell<Option<RebuildHandle>>>,
+}
+
+impl State for ToggleState {
+    fn framework_build(&self, element: &StatefulElement) -> View {
+        *self.handle.borrow_mut() = element.to_rebuild_handle();
+
+        if self.show.get() {
+            Child {
+                builds: self.child_builds.clone(),
+                handle: self.child_handle.clone(),
+            }
+            .into_view()
+        } else {
+            Label { count: 0 }.into_view()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Child {
+    builds: Rc<Cell<usize>>,
+    handle: Rc<RefCell<Option<RebuildHandle>>>,
+}
+
+impl Child {
+    fn create_state(&self) -> ChildState {
+        ChildState {
+            builds: self.builds.clone(),
+            handle: self.handle.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ChildState {
+    builds: Rc<Cell<usize>>,
+    handle: Rc<RefCell<Option<RebuildHandle>>>,
+}
+
+impl State for ChildState {
+    fn framework_build(&self, element: &StatefulElement) -> View {
+        self.builds.set(self.builds.get() + 1);
+
+        *self.handle.borrow_mut() = element.to_rebuild_handle();
+
+        Label { count: 1 }.into_view()
+    }
+}
+
+#[test]
+fn test_removed_dirty_element_not_rebuilt() {
+    let mut context = FrameworkContext::default();
+
+    let show = Rc::new(Cell::new(true));
+    let child_builds = Rc::new(Cell::new(0));
+    let handle = Rc::new(RefCell::new(None));
+    let child_handle = Rc::new(RefCell::new(None));
+
+    context
+        .mount_root(
+            Toggle {
+                show: show.clone(),
+                child_builds: child_builds.clone(),
+                handle: handle.clone(),
+                child_handle: child_handle.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(child_builds.get(), 1);
+
+    let handle = handle.borrow().clone().unwrap();
+    let child_handle = child_handle.borrow().clone().unwrap();
+
+    // The child is scheduled, then removed by its parent in the same frame.
+    child_handle.mark_needs_build();
+    handle.set_state(|| show.set(false));
+
+    context.flush_build();
+
+    assert_eq!(child_builds.get(), 1);
+    assert!(!context.build_owner.has_dirty_elements());
+    assert!(!context.build_owner.is_dirty(child_handle.to_id()));
+}
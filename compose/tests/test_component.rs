@@ -0,0 +1,153 @@
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, PartialEq, Render)]
+struct Label {
+    text: String,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject {
+            text: self.text.clone(),
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct LabelRenderObject {
+    text: String,
+}
+
+impl RenderObjectLifecycle for LabelRenderObject {}
+
+/// Greet `name` in the inherited font size.
+#[component]
+fn Greeting(name: &String, cx: &impl BuildContext) -> impl IntoView {
+    let size = cx
+        .depend_on_inherited(|style: &DefaultTextStyle| style.style.font_size)
+        .unwrap_or_default();
+
+    Label {
+        text: format!("hello {} {}", name, size),
+    }
+}
+
+#[component]
+fn Item(#[compose(key)] id: u32, mut label: String) -> impl IntoView {
+    label.push('!');
+
+    Label {
+        text: format!("{} {}", id, label),
+    }
+}
+
+#[component]
+fn Nothing() {}
+
+fn label_text(context: &FrameworkContext, root: &Element) -> String {
+    root.first_render_object(context)
+        .unwrap()
+        .0
+        .borrow()
+        .lifecycle
+        .downcast_ref::<LabelRenderObject>()
+        .unwrap()
+        .text
+        .clone()
+}
+
+#[test]
+fn test_component_build() {
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(
+            DefaultTextStyle {
+                style: TextStyle {
+                    font_size: 12.0,
+                    ..Default::default()
+                },
+                child: Greeting {
+                    name: "world".to_owned(),
+                }
+                .into_view(),
+                ..Default::default()
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(label_text(&context, &root), "hello world 12");
+
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(
+            Item {
+                id: 3,
+                label: "item".to_owned(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(label_text(&context, &root), "3 item!");
+
+    let mut context = FrameworkContext::default();
+
+    let root = context.mount_root(Nothing {}.into_view()).unwrap();
+    assert!(root.first_render_object(&context).is_none());
+}
+
+#[test]
+fn test_component_props() {
+    let greeting = Greeting {
+        name: "world".to_owned(),
+    };
+
+    assert!(AnyEq::eq(&greeting, greeting.clone().to_any()));
+    assert!(!AnyEq::eq(
+        &greeting,
+        Greeting {
+            name: "other".to_owned()
+        }
+        .to_any()
+    ));
+}
+
+#[test]
+fn test_component_key() {
+    let item = |id: u32| {
+        Item {
+            id,
+            label: String::new(),
+        }
+        .into_view()
+    };
+
+    // Same caller location, the key field tells items apart.
+    assert_eq!(item(1).to_keypath(), item(1).to_keypath());
+    assert_ne!(item(1).to_keypath(), item(2).to_keypath());
+
+    // Caller location is kept without key.
+    let greeting = || {
+        Greeting {
+            name: String::new(),
+        }
+        .into_view()
+    };
+
+    assert_eq!(greeting().to_keypath(), greeting().to_keypath());
+    assert_ne!(
+        greeting().to_keypath(),
+        Greeting {
+            name: String::new()
+        }
+        .into_view()
+        .to_keypath()
+    );
+}
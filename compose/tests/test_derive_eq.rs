@@ -0,0 +1,196 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Handle which can't be compared.
+#[derive(Debug, Clone)]
+struct Handle(u32);
+
+#[derive(Debug, PartialEq, Render)]
+struct Label {
+    text: String,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct LabelRenderObject {}
+
+impl RenderObjectLifecycle for LabelRenderObject {}
+
+#[derive(Debug, Render)]
+struct Column {
+    #[compose(ptr_eq)]
+    log: Log,
+    children: Vec<View>,
+}
+
+impl Column {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug, Stateless)]
+struct Props {
+    text: String,
+    #[compose(skip_eq)]
+    handle: Handle,
+    #[compose(ptr_eq)]
+    log: Log,
+}
+
+impl Props {
+    fn build(&self, _element: &StatelessElement) -> impl IntoView {
+        self.log
+            .borrow_mut()
+            .push(format!("props {} {}", self.text, self.handle.0));
+
+        Label {
+            text: self.text.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Stateless)]
+#[compose(always_rebuild)]
+struct Always {
+    log: Log,
+}
+
+impl Always {
+    fn build(&self, _element: &StatelessElement) -> impl IntoView {
+        self.log.borrow_mut().push("always".to_owned());
+    }
+}
+
+#[component]
+fn Greeting(name: String, #[compose(skip_eq)] handle: Handle) -> impl IntoView {
+    Label {
+        text: format!("hello {} {}", name, handle.0),
+    }
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Root {
+    log: Log,
+}
+
+impl Root {
+    fn build(&self, _element: &StatelessElement) -> impl IntoView {
+        Column {
+            log: self.log.clone(),
+            children: vec![
+                Props {
+                    text: "a".to_owned(),
+                    handle: Handle(self.log.borrow().len() as u32),
+                    log: self.log.clone(),
+                }
+                .into_view(),
+                Always {
+                    log: self.log.clone(),
+                }
+                .into_view(),
+            ],
+        }
+    }
+}
+
+#[test]
+fn test_any_eq_type_mismatch() {
+    let label = Label {
+        text: "a".to_owned(),
+    };
+
+    let log = Log::default();
+
+    assert!(!AnyEq::eq(&label, Always { log: log.clone() }.to_any()));
+    assert!(!AnyEq::eq(&Always { log }, label.to_any()));
+
+    // Views created by the same call share the key path, only the type tells them apart.
+    fn wrap<V: IntoView>(view: V) -> View {
+        view.into_view()
+    }
+
+    let a = wrap(Label {
+        text: "a".to_owned(),
+    });
+    let b = wrap(Greeting {
+        name: "a".to_owned(),
+        handle: Handle(0),
+    });
+
+    assert_eq!(a.to_keypath(), b.to_keypath());
+    assert_ne!(a, b);
+    assert!(!a.can_update(&b));
+}
+
+#[test]
+fn test_any_eq_field_attributes() {
+    let log = Log::default();
+
+    let props = |text: &str, handle: u32, log: &Log| Props {
+        text: text.to_owned(),
+        handle: Handle(handle),
+        log: log.clone(),
+    };
+
+    assert!(AnyEq::eq(
+        &props("a", 1, &log),
+        props("a", 2, &log).to_any()
+    ));
+    assert!(!AnyEq::eq(
+        &props("a", 1, &log),
+        props("b", 1, &log).to_any()
+    ));
+
+    // Same content in another `Rc` is not the same log.
+    assert!(!AnyEq::eq(
+        &props("a", 1, &log),
+        props("a", 1, &Log::default()).to_any()
+    ));
+
+    let greeting = |handle: u32| Greeting {
+        name: "world".to_owned(),
+        handle: Handle(handle),
+    };
+
+    assert!(AnyEq::eq(&greeting(1), greeting(2).to_any()));
+
+    let always = Always { log };
+
+    assert!(!AnyEq::eq(&always, always.to_any()));
+}
+
+#[test]
+fn test_always_rebuild() {
+    let log = Log::default();
+
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(Root { log: log.clone() }.into_view())
+        .unwrap();
+
+    assert_eq!(*log.borrow(), vec!["props a 0", "always"]);
+
+    // `Props` is equal despite the new handle, `Always` rebuilds with its parent.
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(*log.borrow(), vec!["props a 0", "always", "always"]);
+}
@@ -0,0 +1,118 @@
+// Only the derives are imported, generated code must not rely on `IntoView` or `View` in scope.
+use agoraui_compose::{
+    element::StatefulElement,
+    framework::FrameworkContext,
+    macos::{Render, State, Stateful},
+    render::RenderObjectLifecycle,
+};
+
+#[derive(Debug, PartialEq, Stateful)]
+#[stateful(state = CounterState)]
+struct Counter {}
+
+#[derive(Debug, State)]
+#[state(init_state)]
+struct CounterState {
+    value: u32,
+}
+
+impl Default for CounterState {
+    fn default() -> Self {
+        Self { value: 7 }
+    }
+}
+
+impl CounterState {
+    fn init_state(&mut self, _element: &StatefulElement) {
+        self.value += 1;
+    }
+
+    fn build(&self, _element: &StatefulElement) -> impl agoraui_compose::view::IntoView {
+        Label { value: self.value }
+    }
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+#[stateful(state = SeededState, init = Self::seed)]
+struct Seeded {
+    value: u32,
+}
+
+impl Seeded {
+    fn seed(&self) -> SeededState {
+        SeededState { value: self.value }
+    }
+}
+
+/// Can't be created by `Default`.
+#[derive(Debug, State)]
+struct SeededState {
+    value: u32,
+}
+
+impl SeededState {
+    fn build(&self, _element: &StatefulElement) -> impl agoraui_compose::view::IntoView {
+        Label { value: self.value }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Label {
+    value: u32,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject { value: self.value }
+    }
+
+    fn render_object_children(&self) -> Vec<agoraui_compose::view::View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct LabelRenderObject {
+    value: u32,
+}
+
+impl RenderObjectLifecycle for LabelRenderObject {}
+
+fn label_value(context: &FrameworkContext, root: &agoraui_compose::element::Element) -> u32 {
+    let render_object = root.first_render_object(context).unwrap();
+
+    let value = render_object
+        .0
+        .borrow()
+        .lifecycle
+        .downcast_ref::<LabelRenderObject>()
+        .unwrap()
+        .value;
+
+    value
+}
+
+#[test]
+fn test_stateful_state_attribute() {
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(agoraui_compose::view::IntoView::into_view(Counter {}))
+        .unwrap();
+
+    // Created by `Default`, then initialized by the state hook.
+    assert_eq!(label_value(&context, &root), 8);
+}
+
+#[test]
+fn test_stateful_init_attribute() {
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(agoraui_compose::view::IntoView::into_view(Seeded {
+            value: 3,
+        }))
+        .unwrap();
+
+    assert_eq!(label_value(&context, &root), 3);
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::map_clone)]
+
 use agoraui_compose::{framework::FrameworkContext, prelude::*};
 use indextree::Arena;
 
@@ -0,0 +1,341 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[derive(Debug, PartialEq, Render)]
+struct Row {
+    children: Vec<View>,
+}
+
+impl Row {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+fn row(children: Vec<View>) -> View {
+    Row { children }.into_view()
+}
+
+fn focus(node: &FocusNode) -> View {
+    Focus {
+        focus_node: Some(node.clone()),
+        ..Default::default()
+    }
+    .into_view()
+}
+
+fn tab(context: &mut FrameworkContext) -> bool {
+    context.dispatch_key_event(KeyEvent::down(LogicalKey::Tab))
+}
+
+fn shift_tab(context: &mut FrameworkContext) -> bool {
+    context.dispatch_key_event(KeyEvent::down(LogicalKey::Tab).with_modifiers(Modifiers::SHIFT))
+}
+
+fn log_key(log: &Log, name: &'static str, handled: LogicalKey) -> Option<KeyHandler> {
+    let log = log.clone();
+
+    Some(KeyHandler::new(move |event, _context| {
+        log.borrow_mut()
+            .push(format!("{} {:?}", name, event.logical));
+
+        if event.logical == handled {
+            KeyEventResult::Handled
+        } else {
+            KeyEventResult::Ignored
+        }
+    }))
+}
+
+#[test]
+fn test_traversal() {
+    let nodes = (0..4).map(|_| FocusNode::new()).collect::<Vec<_>>();
+
+    let mut context = FrameworkContext::default();
+
+    context
+        .mount_root(row(vec![
+            focus(&nodes[0]),
+            Focus {
+                focus_node: Some(nodes[1].clone()),
+                skip_traversal: true,
+                ..Default::default()
+            }
+            .into_view(),
+            focus(&nodes[2]),
+            Focus {
+                focus_node: Some(nodes[3].clone()),
+                order: -1,
+                ..Default::default()
+            }
+            .into_view(),
+        ]))
+        .unwrap();
+
+    let focused = |context: &FrameworkContext| {
+        let primary = context.focus_manager.primary_focus();
+
+        nodes.iter().position(|node| Some(node) == primary.as_ref())
+    };
+
+    assert_eq!(focused(&context), None);
+
+    // Lower order first, then the element tree order, skipped nodes are left out.
+    assert!(tab(&mut context));
+    assert_eq!(focused(&context), Some(3));
+
+    assert!(tab(&mut context));
+    assert_eq!(focused(&context), Some(0));
+
+    assert!(tab(&mut context));
+    assert_eq!(focused(&context), Some(2));
+
+    assert!(tab(&mut context));
+    assert_eq!(focused(&context), Some(3));
+
+    assert!(shift_tab(&mut context));
+    assert_eq!(focused(&context), Some(2));
+
+    // Skipped nodes can still request focus.
+    assert!(nodes[1].request_focus());
+    assert!(nodes[1].has_primary_focus());
+
+    assert!(tab(&mut context));
+    assert_eq!(focused(&context), Some(3));
+
+    // Other modifiers don't traverse.
+    assert!(!context
+        .dispatch_key_event(KeyEvent::down(LogicalKey::Tab).with_modifiers(Modifiers::CONTROL)));
+    assert_eq!(focused(&context), Some(3));
+}
+
+#[test]
+fn test_scope() {
+    let outside = FocusNode::new();
+    let scope = FocusNode::new();
+    let first = FocusNode::new();
+    let second = FocusNode::new();
+
+    let mut context = FrameworkContext::default();
+
+    context
+        .mount_root(row(vec![
+            focus(&outside),
+            FocusScope {
+                focus_node: Some(scope.clone()),
+                child: row(vec![focus(&first), focus(&second)]),
+                ..Default::default()
+            }
+            .into_view(),
+        ]))
+        .unwrap();
+
+    assert!(outside.request_focus());
+
+    // The scope gives focus to its first node.
+    assert!(tab(&mut context));
+    assert!(first.has_primary_focus());
+    assert!(scope.has_focus());
+    assert_eq!(context.focus_manager.parent(&first), Some(scope.clone()));
+    assert_eq!(
+        context.focus_manager.enclosing_scope(&first),
+        Some(scope.clone())
+    );
+
+    // Traversal stays inside the scope.
+    assert!(tab(&mut context));
+    assert!(second.has_primary_focus());
+
+    assert!(tab(&mut context));
+    assert!(first.has_primary_focus());
+
+    assert!(shift_tab(&mut context));
+    assert!(second.has_primary_focus());
+
+    // The scope restores the node last focused inside it.
+    assert!(outside.request_focus());
+    assert!(!scope.has_focus());
+
+    assert!(scope.request_focus());
+    assert!(second.has_primary_focus());
+
+    second.unfocus();
+    assert_eq!(context.focus_manager.primary_focus(), None);
+}
+
+#[test]
+fn test_key_dispatch() {
+    let log = Log::default();
+
+    let on_focus_change = |name: &'static str| {
+        let log = log.clone();
+
+        Some(Callback::new(move |focused: bool| {
+            log.borrow_mut()
+                .push(format!("{} focused {}", name, focused));
+        }))
+    };
+
+    let mut context = FrameworkContext::default();
+
+    context
+        .mount_root(
+            Focus {
+                on_key: log_key(&log, "outer", LogicalKey::Escape),
+                on_focus_change: on_focus_change("outer"),
+                child: row(vec![
+                    Focus {
+                        autofocus: true,
+                        on_key: log_key(&log, "inner", LogicalKey::Enter),
+                        on_focus_change: on_focus_change("inner"),
+                        ..Default::default()
+                    }
+                    .into_view(),
+                    Focus {
+                        autofocus: true,
+                        on_key: log_key(&log, "sibling", LogicalKey::Enter),
+                        ..Default::default()
+                    }
+                    .into_view(),
+                ]),
+                ..Default::default()
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    // Only the first autofocus takes the focus.
+    assert_eq!(
+        std::mem::take(&mut *log.borrow_mut()),
+        vec!["inner focused true", "outer focused true"]
+    );
+
+    assert!(!context.dispatch_key_event(KeyEvent::down(LogicalKey::Character('x'))));
+    assert!(context.dispatch_key_event(KeyEvent::down(LogicalKey::Escape)));
+    assert!(context.dispatch_key_event(KeyEvent::repeat(LogicalKey::Enter)));
+
+    assert_eq!(
+        std::mem::take(&mut *log.borrow_mut()),
+        vec![
+            "inner Character('x')",
+            "outer Character('x')",
+            "inner Escape",
+            "outer Escape",
+            "inner Enter",
+        ]
+    );
+
+    // Focus moves to the sibling, the outer node keeps focus.
+    assert!(tab(&mut context));
+
+    assert!(context.dispatch_key_event(KeyEvent::up(LogicalKey::Enter)));
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "inner Tab",
+            "outer Tab",
+            "inner focused false",
+            "sibling Enter"
+        ]
+    );
+}
+
+#[derive(Debug)]
+struct SaveIntent;
+
+impl Intent for SaveIntent {}
+
+#[derive(Debug)]
+struct CloseIntent;
+
+impl Intent for CloseIntent {}
+
+#[test]
+fn test_shortcuts() {
+    let log = Log::default();
+
+    let mut context = FrameworkContext::default();
+
+    context
+        .mount_root(
+            Actions {
+                actions: ActionMap::new().on({
+                    let log = log.clone();
+
+                    move |_: &SaveIntent| log.borrow_mut().push("save".to_owned())
+                }),
+                child: Shortcuts {
+                    shortcuts: ShortcutMap::new()
+                        .bind(
+                            SingleActivator::new(LogicalKey::Character('s'))
+                                .with_modifiers(Modifiers::CONTROL),
+                            SaveIntent,
+                        )
+                        .bind(SingleActivator::new(LogicalKey::Escape), CloseIntent),
+                    child: Focus {
+                        autofocus: true,
+                        ..Default::default()
+                    }
+                    .into_view(),
+                }
+                .into_view(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let save = KeyEvent::down(LogicalKey::Character('s')).with_modifiers(Modifiers::CONTROL);
+
+    assert!(context.dispatch_key_event(save.clone()));
+    assert!(context.dispatch_key_event(KeyEvent {
+        kind: KeyEventKind::Repeat,
+        ..save.clone()
+    }));
+    assert!(!context.dispatch_key_event(KeyEvent {
+        kind: KeyEventKind::Up,
+        ..save
+    }));
+    assert!(!context.dispatch_key_event(KeyEvent::down(LogicalKey::Character('s'))));
+
+    // No action for the intent.
+    assert!(!context.dispatch_key_event(KeyEvent::down(LogicalKey::Escape)));
+
+    assert_eq!(*log.borrow(), vec!["save", "save"]);
+
+    // Shortcuts are not traversed.
+    let focused = context.focus_manager.primary_focus().unwrap();
+    assert_eq!(context.focus_manager.traversal_order(None), vec![focused]);
+}
+
+#[test]
+fn test_unmount() {
+    let node = FocusNode::new();
+
+    let mut context = FrameworkContext::default();
+
+    context.mount_root(row(vec![focus(&node)])).unwrap();
+
+    assert!(node.request_focus());
+
+    let id = node.element().unwrap();
+
+    context.unmount_subtree(id);
+
+    assert!(!node.is_attached());
+    assert!(!node.has_focus());
+    assert_eq!(context.focus_manager.primary_focus(), None);
+    assert!(!node.request_focus());
+}
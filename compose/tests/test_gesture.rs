@@ -0,0 +1,518 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Place `child` at `offset` with `size`, hit anywhere within its own bounds.
+#[derive(Debug, PartialEq, Render)]
+struct Positioned {
+    offset: Offset,
+    size: Size,
+    child: View,
+}
+
+impl Positioned {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        RenderPositioned {
+            offset: self.offset,
+            size: self.size,
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![self.child.clone()]
+    }
+}
+
+#[derive(Debug)]
+struct RenderPositioned {
+    offset: Offset,
+    size: Size,
+}
+
+impl RenderObjectLifecycle for RenderPositioned {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        for child in context.children() {
+            context.layout_child(child, BoxConstraints::tight(self.size));
+            context.position_child(child, self.offset);
+        }
+
+        constraints.biggest()
+    }
+
+    fn hit_test_self(&self, _position: Offset) -> bool {
+        true
+    }
+}
+
+fn positioned(offset: Offset, size: Size, child: View) -> View {
+    Positioned {
+        offset,
+        size,
+        child,
+    }
+    .into_view()
+}
+
+fn mount(view: View) -> FrameworkContext {
+    let mut context = FrameworkContext::default();
+
+    context.mount_root(view).unwrap();
+
+    context.flush_layout(BoxConstraints::tight(Size::new(100.0, 100.0)));
+
+    context
+}
+
+fn log_callback<A: std::fmt::Debug + 'static>(
+    log: &Log,
+    name: &'static str,
+) -> Option<Callback<A>> {
+    let log = log.clone();
+
+    Some(Callback::new(move |args: A| {
+        log.borrow_mut().push(format!("{} {:?}", name, args));
+    }))
+}
+
+fn log_unit(log: &Log, name: &'static str) -> Option<Callback> {
+    let log = log.clone();
+
+    Some(Callback::new(move |_| {
+        log.borrow_mut().push(name.to_owned())
+    }))
+}
+
+fn at(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+fn tap(context: &mut FrameworkContext, pointer: PointerId, position: Offset, millis: u64) {
+    context
+        .dispatch_pointer_event(PointerEvent::down(pointer, position).with_timestamp(at(millis)));
+    context.dispatch_pointer_event(
+        PointerEvent::up(pointer, position).with_timestamp(at(millis + 50)),
+    );
+}
+
+#[test]
+fn test_tap() {
+    let log = Log::default();
+
+    let mut context = mount(positioned(
+        Offset::new(10.0, 10.0),
+        Size::new(20.0, 20.0),
+        GestureDetector {
+            on_tap: log_unit(&log, "tap"),
+            on_tap_cancel: log_unit(&log, "cancel"),
+            on_tap_up: log_callback(&log, "up"),
+            behavior: HitTestBehavior::Opaque,
+            ..Default::default()
+        }
+        .into_view(),
+    ));
+
+    tap(&mut context, 1, Offset::new(15.0, 20.0), 0);
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            format!(
+                "up {:?}",
+                TapDetails {
+                    position: Offset::new(15.0, 20.0),
+                    local_position: Offset::new(5.0, 10.0),
+                    device: PointerDeviceKind::Touch,
+                }
+            ),
+            "tap".to_owned(),
+        ]
+    );
+
+    log.borrow_mut().clear();
+
+    // Outside of the detector.
+    tap(&mut context, 2, Offset::new(50.0, 50.0), 1000);
+
+    assert!(log.borrow().is_empty());
+
+    // Moving beyond the slop is not a tap.
+    context.dispatch_pointer_event(PointerEvent::down(3, Offset::new(15.0, 15.0)));
+    context.dispatch_pointer_event(PointerEvent::moved(3, Offset::new(15.0, 45.0)));
+    context.dispatch_pointer_event(PointerEvent::up(3, Offset::new(15.0, 45.0)));
+
+    assert_eq!(*log.borrow(), vec!["cancel"]);
+    assert!(!context.gesture_binding.is_tracked(3));
+}
+
+#[test]
+fn test_double_tap() {
+    let log = Log::default();
+
+    let mut context = mount(
+        GestureDetector {
+            on_tap: log_unit(&log, "tap"),
+            on_double_tap: log_unit(&log, "double tap"),
+            behavior: HitTestBehavior::Opaque,
+            ..Default::default()
+        }
+        .into_view(),
+    );
+
+    tap(&mut context, 1, Offset::new(10.0, 10.0), 0);
+
+    // The tap waits until the double tap gives up.
+    assert!(log.borrow().is_empty());
+
+    tap(&mut context, 2, Offset::new(12.0, 10.0), 200);
+
+    assert_eq!(*log.borrow(), vec!["double tap"]);
+
+    log.borrow_mut().clear();
+
+    tap(&mut context, 3, Offset::new(10.0, 10.0), 1000);
+
+    context.advance_gestures(at(1200));
+
+    assert!(log.borrow().is_empty());
+
+    context.advance_gestures(at(1400));
+
+    assert_eq!(*log.borrow(), vec!["tap"]);
+}
+
+#[test]
+fn test_long_press() {
+    let log = Log::default();
+
+    let mut context = mount(
+        GestureDetector {
+            on_tap: log_unit(&log, "tap"),
+            on_long_press: log_callback(&log, "long press"),
+            on_long_press_up: log_unit(&log, "long press up"),
+            behavior: HitTestBehavior::Opaque,
+            ..Default::default()
+        }
+        .into_view(),
+    );
+
+    context.dispatch_pointer_event(PointerEvent::down(1, Offset::new(10.0, 10.0)));
+    context.advance_gestures(at(400));
+
+    assert!(log.borrow().is_empty());
+
+    context.advance_gestures(at(600));
+    context.dispatch_pointer_event(
+        PointerEvent::up(1, Offset::new(10.0, 10.0)).with_timestamp(at(700)),
+    );
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            format!(
+                "long press {:?}",
+                LongPressDetails {
+                    position: Offset::new(10.0, 10.0),
+                    local_position: Offset::new(10.0, 10.0),
+                }
+            ),
+            "long press up".to_owned(),
+        ]
+    );
+
+    log.borrow_mut().clear();
+
+    // Released before the deadline.
+    tap(&mut context, 2, Offset::new(10.0, 10.0), 1000);
+
+    assert_eq!(*log.borrow(), vec!["tap"]);
+}
+
+#[test]
+fn test_pan() {
+    let log = Log::default();
+
+    let mut context = mount(
+        GestureDetector {
+            on_tap: log_unit(&log, "tap"),
+            on_pan_start: log_callback(&log, "start"),
+            on_pan_update: Some(Callback::new({
+                let log = log.clone();
+
+                move |details: DragUpdateDetails| {
+                    log.borrow_mut()
+                        .push(format!("update {} {}", details.delta.dx, details.delta.dy));
+                }
+            })),
+            on_pan_end: Some(Callback::new({
+                let log = log.clone();
+
+                move |details: DragEndDetails| {
+                    log.borrow_mut().push(format!(
+                        "end {} {}",
+                        details.velocity.dx, details.velocity.dy
+                    ));
+                }
+            })),
+            behavior: HitTestBehavior::Opaque,
+            ..Default::default()
+        }
+        .into_view(),
+    );
+
+    context.dispatch_pointer_event(PointerEvent::down(1, Offset::new(10.0, 10.0)));
+    context.dispatch_pointer_event(
+        PointerEvent::moved(1, Offset::new(15.0, 10.0)).with_timestamp(at(100)),
+    );
+
+    assert!(log.borrow().is_empty());
+
+    context.dispatch_pointer_event(
+        PointerEvent::moved(1, Offset::new(40.0, 10.0)).with_timestamp(at(200)),
+    );
+    context.dispatch_pointer_event(
+        PointerEvent::moved(1, Offset::new(40.0, 30.0)).with_timestamp(at(300)),
+    );
+    context.dispatch_pointer_event(
+        PointerEvent::up(1, Offset::new(40.0, 30.0)).with_timestamp(at(300)),
+    );
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            format!(
+                "start {:?}",
+                DragStartDetails {
+                    position: Offset::new(10.0, 10.0),
+                    local_position: Offset::new(10.0, 10.0),
+                }
+            ),
+            "update 30 0".to_owned(),
+            "update 0 20".to_owned(),
+            "end 0 200".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn test_scale() {
+    let log = Log::default();
+
+    let mut context = mount(
+        GestureDetector {
+            on_scale_start: log_callback(&log, "start"),
+            on_scale_update: Some(Callback::new({
+                let log = log.clone();
+
+                move |details: ScaleUpdateDetails| {
+                    log.borrow_mut().push(format!(
+                        "update {} {} {}",
+                        details.focal_point.dx, details.focal_point.dy, details.scale
+                    ));
+                }
+            })),
+            on_scale_end: Some(Callback::new({
+                let log = log.clone();
+
+                move |_: ScaleEndDetails| log.borrow_mut().push("end".to_owned())
+            })),
+            behavior: HitTestBehavior::Opaque,
+            ..Default::default()
+        }
+        .into_view(),
+    );
+
+    context.dispatch_pointer_event(PointerEvent::down(1, Offset::new(40.0, 50.0)));
+    context.dispatch_pointer_event(PointerEvent::down(2, Offset::new(60.0, 50.0)));
+
+    // Alone in the arena, the scale starts right away.
+    assert_eq!(
+        *log.borrow(),
+        vec![format!(
+            "start {:?}",
+            ScaleStartDetails {
+                focal_point: Offset::new(40.0, 50.0),
+                pointer_count: 1,
+            }
+        )]
+    );
+
+    log.borrow_mut().clear();
+
+    context.dispatch_pointer_event(PointerEvent::moved(1, Offset::new(30.0, 50.0)));
+    context.dispatch_pointer_event(PointerEvent::moved(2, Offset::new(70.0, 50.0)));
+    context.dispatch_pointer_event(PointerEvent::up(1, Offset::new(30.0, 50.0)));
+    context.dispatch_pointer_event(PointerEvent::up(2, Offset::new(70.0, 50.0)));
+
+    assert_eq!(
+        *log.borrow(),
+        vec!["update 45 50 1.5", "update 50 50 2", "end"]
+    );
+}
+
+#[test]
+fn test_nested_detectors() {
+    let log = Log::default();
+
+    let mut context = mount(
+        GestureDetector {
+            on_tap: log_unit(&log, "outer"),
+            behavior: HitTestBehavior::Opaque,
+            child: positioned(
+                Offset::new(50.0, 50.0),
+                Size::new(20.0, 20.0),
+                GestureDetector {
+                    on_tap: log_unit(&log, "inner"),
+                    behavior: HitTestBehavior::Opaque,
+                    ..Default::default()
+                }
+                .into_view(),
+            ),
+            ..Default::default()
+        }
+        .into_view(),
+    );
+
+    // The deepest detector joins the arena first and wins the sweep.
+    tap(&mut context, 1, Offset::new(55.0, 55.0), 0);
+
+    assert_eq!(*log.borrow(), vec!["inner"]);
+
+    log.borrow_mut().clear();
+
+    tap(&mut context, 2, Offset::new(5.0, 5.0), 1000);
+
+    assert_eq!(*log.borrow(), vec!["outer"]);
+}
+
+#[test]
+fn test_listener() {
+    let log = Log::default();
+
+    let on_pointer = PointerHandler::new({
+        let log = log.clone();
+
+        move |event, _router| {
+            let local = event.local_position();
+
+            log.borrow_mut()
+                .push(format!("{:?} {} {}", event.kind, local.dx, local.dy));
+        }
+    });
+
+    let mut context = mount(positioned(
+        Offset::new(20.0, 20.0),
+        Size::new(50.0, 50.0),
+        Listener {
+            on_pointer,
+            behavior: HitTestBehavior::DeferToChild,
+            child: positioned(Offset::ZERO, Size::new(10.0, 10.0), View::Empty),
+        }
+        .into_view(),
+    ));
+
+    context.dispatch_pointer_event(PointerEvent::scroll(
+        1,
+        Offset::new(25.0, 25.0),
+        Offset::new(0.0, 3.0),
+    ));
+
+    // Outside of the listener.
+    context.dispatch_pointer_event(PointerEvent::scroll(
+        1,
+        Offset::new(80.0, 80.0),
+        Offset::new(0.0, 3.0),
+    ));
+
+    // Events of a pointer follow the path of its down.
+    context.dispatch_pointer_event(PointerEvent::down(2, Offset::new(22.0, 22.0)));
+    context.dispatch_pointer_event(PointerEvent::moved(2, Offset::new(60.0, 60.0)));
+    context.dispatch_pointer_event(PointerEvent::up(2, Offset::new(60.0, 60.0)));
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "Scroll(Offset { dx: 0.0, dy: 3.0 }) 5 5",
+            "Down 2 2",
+            "Move 40 40",
+            "Up 40 40",
+        ]
+    );
+}
+
+#[test]
+fn test_arena() {
+    #[derive(Debug, Default)]
+    struct Member;
+
+    impl GestureRecognizer for Member {
+        fn add_pointer(&mut self, _context: &mut GestureContext, _event: &PointerEvent) -> bool {
+            true
+        }
+
+        fn handle_event(&mut self, _context: &mut GestureContext, _event: &PointerEvent) {}
+
+        fn accept_gesture(&mut self, _context: &mut GestureContext, _pointer: PointerId) {}
+
+        fn reject_gesture(&mut self, _context: &mut GestureContext, _pointer: PointerId) {}
+    }
+
+    let first: GestureArenaMember = Rc::new(RefCell::new(Member));
+    let second: GestureArenaMember = Rc::new(RefCell::new(Member));
+
+    let outcomes = |outcomes: Vec<GestureArenaOutcome>| {
+        outcomes
+            .into_iter()
+            .map(|(member, _, disposition)| (Rc::ptr_eq(&member, &first), disposition))
+            .collect::<Vec<_>>()
+    };
+
+    let mut arena = GestureArenaManager::default();
+
+    // Accepting while open wins once closed.
+    arena.add(1, first.clone());
+    arena.add(1, second.clone());
+
+    assert!(arena
+        .resolve(1, &second, GestureDisposition::Accepted)
+        .is_empty());
+    assert_eq!(
+        outcomes(arena.close(1)),
+        vec![
+            (true, GestureDisposition::Rejected),
+            (false, GestureDisposition::Accepted)
+        ]
+    );
+    assert!(!arena.is_pending(1));
+
+    // Held arenas are swept when released.
+    arena.add(2, first.clone());
+    arena.add(2, second.clone());
+
+    assert!(arena.close(2).is_empty());
+
+    arena.hold(2);
+
+    assert!(arena.sweep(2).is_empty());
+    assert_eq!(
+        outcomes(arena.release(2)),
+        vec![
+            (true, GestureDisposition::Accepted),
+            (false, GestureDisposition::Rejected)
+        ]
+    );
+
+    // The last member of a closed arena wins.
+    arena.add(3, first.clone());
+    arena.add(3, second.clone());
+    arena.close(3);
+
+    assert_eq!(
+        outcomes(arena.resolve(3, &first, GestureDisposition::Rejected)),
+        vec![
+            (true, GestureDisposition::Rejected),
+            (false, GestureDisposition::Accepted)
+        ]
+    );
+}
@@ -0,0 +1,299 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Position {
+    Left,
+    Right,
+    None,
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    key: GlobalKey,
+    position: Rc<Cell<Position>>,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            key: self.key,
+            position: self.position.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RootState {
+    key: GlobalKey,
+    position: Rc<Cell<Position>>,
+}
+
+impl State for RootState {
+    fn framework_build(&self, _element: &StatefulElement) -> View {
+        let position = self.position.get();
+
+        Row {
+            children: vec![
+                Slot {
+                    key: (position == Position::Left).then_some(self.key),
+                }
+                .into_view(),
+                Slot {
+                    key: (position == Position::Right).then_some(self.key),
+                }
+                .into_view(),
+            ],
+        }
+        .into_view()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Row {
+    children: Vec<View>,
+}
+
+impl Row {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Slot {
+    key: Option<GlobalKey>,
+}
+
+impl Slot {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.key
+            .map(|key| vec![Keyed { key }.into_view()])
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Keyed {
+    #[compose(key)]
+    key: GlobalKey,
+}
+
+static STATES: AtomicUsize = AtomicUsize::new(0);
+
+impl Keyed {
+    fn create_state(&self) -> KeyedState {
+        KeyedState {
+            id: STATES.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct KeyedState {
+    id: usize,
+}
+
+impl State for KeyedState {
+    fn framework_build(&self, _element: &StatefulElement) -> View {
+        Slot { key: None }.into_view()
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+fn render_object_id(context: &FrameworkContext, id: ElementId) -> RenderObjectId {
+    let element = context.element_tree.borrow().get(id).unwrap().get().clone();
+
+    element
+        .first_render_object(context)
+        .unwrap()
+        .0
+        .borrow()
+        .id
+        .unwrap()
+}
+
+#[test]
+fn test_reparent_global_key() {
+    let mut context = FrameworkContext::default();
+
+    let key = GlobalKey::new();
+    let position = Rc::new(Cell::new(Position::Left));
+
+    let root = context
+        .mount_root(
+            Root {
+                key,
+                position: position.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let row = root
+        .search_first_render_object_element_id(&context)
+        .unwrap();
+
+    let slots = row
+        .children(&context.element_tree.borrow())
+        .collect::<Vec<_>>();
+
+    let keyed = context.element_of(key).unwrap().to_id().unwrap();
+    let state = context.with_state_of(key, |state: &KeyedState| state.id);
+
+    assert!(state.is_some());
+    assert_eq!(keyed.parent(&context.element_tree.borrow()), Some(slots[0]));
+
+    position.set(Position::Right);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(context.element_of(key).unwrap().to_id(), Some(keyed));
+    assert_eq!(
+        context.with_state_of(key, |state: &KeyedState| state.id),
+        state
+    );
+    assert_eq!(keyed.parent(&context.element_tree.borrow()), Some(slots[1]));
+
+    let keyed_render_object = context
+        .render_object_of(key)
+        .unwrap()
+        .0
+        .borrow()
+        .id
+        .unwrap();
+
+    assert_eq!(
+        keyed_render_object.parent(&context.render_tree.borrow()),
+        Some(render_object_id(&context, slots[1]))
+    );
+
+    assert!(render_object_id(&context, slots[0])
+        .children(&context.render_tree.borrow())
+        .next()
+        .is_none());
+
+    position.set(Position::None);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert!(context.element_of(key).is_none());
+    assert!(context.element_tree.borrow().get(keyed).is_none());
+    assert!(context
+        .render_tree
+        .borrow()
+        .get(keyed_render_object)
+        .is_none());
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Frame {
+    key: GlobalKey,
+    side: Position,
+    position: Rc<Cell<Position>>,
+}
+
+impl Frame {
+    fn build(&self, _element: &StatelessElement) -> impl IntoView {
+        if self.position.get() == self.side {
+            Keyed { key: self.key }.into_view()
+        } else {
+            Slot { key: None }.into_view()
+        }
+    }
+}
+
+#[test]
+fn test_reparent_keeps_render_order() {
+    let mut context = FrameworkContext::default();
+
+    let key = GlobalKey::new();
+    let position = Rc::new(Cell::new(Position::Left));
+
+    let frame = |side| {
+        Frame {
+            key,
+            side,
+            position: position.clone(),
+        }
+        .into_view()
+    };
+
+    let root = context
+        .mount_root(
+            Row {
+                children: vec![
+                    frame(Position::Left),
+                    Slot { key: None }.into_view(),
+                    frame(Position::Right),
+                ],
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let row = root.to_id().unwrap();
+
+    let frames = row
+        .children(&context.element_tree.borrow())
+        .collect::<Vec<_>>();
+
+    // Render objects of the row children in element order.
+    let expected = |context: &FrameworkContext| {
+        frames
+            .iter()
+            .map(|id| render_object_id(context, *id))
+            .collect::<Vec<_>>()
+    };
+
+    let render_children = |context: &FrameworkContext| {
+        render_object_id(context, row)
+            .children(&context.render_tree.borrow())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(render_children(&context), expected(&context));
+
+    // Only the frames rebuild, so their parent row doesn't reorder its render children.
+    for side in [Position::Right, Position::Left] {
+        position.set(side);
+
+        for id in [frames[0], frames[2]] {
+            let frame = context.element_tree.borrow()[id].get().clone();
+
+            frame.mark_needs_build();
+        }
+
+        context.flush_build();
+
+        assert_eq!(
+            context
+                .element_of(key)
+                .unwrap()
+                .to_id()
+                .unwrap()
+                .parent(&context.element_tree.borrow()),
+            Some(frames[if side == Position::Left { 0 } else { 2 }])
+        );
+        assert_eq!(render_children(&context), expected(&context));
+    }
+}
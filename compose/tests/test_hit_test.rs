@@ -0,0 +1,202 @@
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, PartialEq, Render)]
+struct Boxed {
+    name: &'static str,
+    /// Children are placed at these offsets with size 10x10.
+    offsets: Vec<Offset>,
+    scale: Option<f32>,
+    children: Vec<View>,
+}
+
+impl Boxed {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        BoxedRenderObject {
+            name: self.name,
+            offsets: self.offsets.clone(),
+            scale: self.scale,
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct BoxedRenderObject {
+    name: &'static str,
+    offsets: Vec<Offset>,
+    scale: Option<f32>,
+}
+
+impl RenderObjectLifecycle for BoxedRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        for (child, offset) in context.children().into_iter().zip(self.offsets.iter()) {
+            context.layout_child(child, BoxConstraints::tight(Size::new(10.0, 10.0)));
+            context.position_child(child, *offset);
+        }
+
+        constraints.biggest()
+    }
+
+    fn hit_test(&self, context: &mut HitTestContext, position: Offset) -> bool {
+        let Some(scale) = self.scale else {
+            if !Rect::from_offset_size(Offset::ZERO, context.size()).contains(position) {
+                return false;
+            }
+
+            let hit = context.hit_test_children(position) || self.hit_test_self(position);
+
+            if hit {
+                context.add_self();
+            }
+
+            return hit;
+        };
+
+        // Children are painted scaled around the origin, the render object itself is transparent.
+        let hit = context.children().into_iter().rev().any(|child| {
+            context.hit_test_child_with_transform(child, &Transform::scale(scale, scale), position)
+        });
+
+        if hit {
+            context.add_self();
+        }
+
+        hit
+    }
+
+    fn hit_test_self(&self, _position: Offset) -> bool {
+        self.name != "transparent"
+    }
+}
+
+fn boxed(name: &'static str, offsets: Vec<Offset>, children: Vec<View>) -> View {
+    Boxed {
+        name,
+        offsets,
+        scale: None,
+        children,
+    }
+    .into_view()
+}
+
+fn mount(view: View) -> FrameworkContext {
+    let mut context = FrameworkContext::default();
+
+    context.mount_root(view).unwrap();
+
+    context.flush_layout(BoxConstraints::tight(Size::new(100.0, 100.0)));
+
+    context
+}
+
+fn names(context: &FrameworkContext, result: &HitTestResult) -> Vec<&'static str> {
+    let render_tree = context.render_tree.borrow();
+
+    result
+        .render_object_ids()
+        .into_iter()
+        .map(|id| {
+            render_tree[id]
+                .get()
+                .0
+                .borrow()
+                .lifecycle
+                .downcast_ref::<BoxedRenderObject>()
+                .unwrap()
+                .name
+        })
+        .collect()
+}
+
+#[test]
+fn test_hit_test_reverse_paint_order() {
+    // root -> [first at (0, 0), second at (5, 0) painted over first]
+    let context = mount(boxed(
+        "root",
+        vec![Offset::new(0.0, 0.0), Offset::new(5.0, 0.0)],
+        vec![
+            boxed("first", vec![], vec![]),
+            boxed("second", vec![], vec![]),
+        ],
+    ));
+
+    let result = context.hit_test(Offset::new(7.0, 5.0));
+
+    assert_eq!(names(&context, &result), vec!["second", "root"]);
+    assert_eq!(
+        result.path[0].local_position(Offset::new(7.0, 5.0)),
+        Offset::new(2.0, 5.0)
+    );
+
+    let result = context.hit_test(Offset::new(2.0, 5.0));
+
+    assert_eq!(names(&context, &result), vec!["first", "root"]);
+
+    let result = context.hit_test(Offset::new(50.0, 50.0));
+
+    assert_eq!(names(&context, &result), vec!["root"]);
+
+    assert!(context.hit_test(Offset::new(150.0, 50.0)).is_empty());
+}
+
+#[test]
+fn test_hit_test_elements() {
+    let context = mount(boxed(
+        "transparent",
+        vec![Offset::new(20.0, 20.0)],
+        vec![boxed("child", vec![], vec![])],
+    ));
+
+    // Transparent render objects are only hit through their children.
+    assert!(context.hit_test(Offset::new(5.0, 5.0)).is_empty());
+
+    let result = context.hit_test(Offset::new(25.0, 25.0));
+
+    assert_eq!(names(&context, &result), vec!["child", "transparent"]);
+
+    let render_tree = context.render_tree.borrow();
+
+    let element_ids = result
+        .render_object_ids()
+        .into_iter()
+        .map(|id| render_tree[id].get().element_id().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(result.element_ids(), element_ids);
+
+    let element_tree = context.element_tree.borrow();
+
+    for (element_id, render_object_id) in element_ids.iter().zip(result.render_object_ids()) {
+        assert_eq!(
+            element_tree[*element_id].get().to_render_object_id(),
+            Some(render_object_id)
+        );
+    }
+}
+
+#[test]
+fn test_hit_test_transform() {
+    let context = mount(
+        Boxed {
+            name: "scaled",
+            offsets: vec![Offset::new(10.0, 10.0)],
+            scale: Some(2.0),
+            children: vec![boxed("child", vec![], vec![])],
+        }
+        .into_view(),
+    );
+
+    // Child covers (10, 10)..(20, 20) before scale, (20, 20)..(40, 40) on screen.
+    assert!(context.hit_test(Offset::new(15.0, 15.0)).is_empty());
+
+    let result = context.hit_test(Offset::new(30.0, 30.0));
+
+    assert_eq!(names(&context, &result), vec!["child", "scaled"]);
+    assert_eq!(
+        result.path[0].local_position(Offset::new(30.0, 30.0)),
+        Offset::new(5.0, 5.0)
+    );
+}
@@ -0,0 +1,297 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// Exposes a state handle created inside a build to the test.
+type Setter<T> = Rc<RefCell<Option<UseState<T>>>>;
+
+#[derive(Debug, PartialEq, Render)]
+#[render(update_render_object)]
+struct Label {
+    text: String,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject {
+            text: self.text.clone(),
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        render_object: &mut dyn RenderObjectLifecycle,
+    ) -> RenderObjectUpdate {
+        if let Some(render_object) = render_object.downcast_mut::<LabelRenderObject>() {
+            render_object.text = self.text.clone();
+        }
+
+        RenderObjectUpdate::Layout
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct LabelRenderObject {
+    text: String,
+}
+
+impl RenderObjectLifecycle for LabelRenderObject {}
+
+fn label_text(context: &FrameworkContext, root: &Element) -> String {
+    root.first_render_object(context)
+        .unwrap()
+        .0
+        .borrow()
+        .lifecycle
+        .downcast_ref::<LabelRenderObject>()
+        .unwrap()
+        .text
+        .clone()
+}
+
+#[component]
+fn Counter(setter: Setter<i32>, cx: &impl BuildContext) -> impl IntoView {
+    let count = cx.use_state(|| 0);
+    let builds = cx.use_ref(|| 0);
+
+    *builds.borrow_mut() += 1;
+    *setter.borrow_mut() = Some(count.clone());
+
+    Label {
+        text: format!("{} {}", count.get(), builds.borrow()),
+    }
+}
+
+#[test]
+fn test_use_state() {
+    let setter = Setter::default();
+
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(
+            Counter {
+                setter: setter.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(label_text(&context, &root), "0 1");
+
+    let count = setter.borrow().clone().unwrap();
+
+    count.set(5);
+    assert!(context.build_owner.has_dirty_elements());
+
+    context.flush_build();
+    assert_eq!(label_text(&context, &root), "5 2");
+
+    count.update(|count| *count += 1);
+    context.flush_build();
+    assert_eq!(label_text(&context, &root), "6 3");
+
+    // The handle of every build refers to the same state.
+    assert_eq!(setter.borrow().clone().unwrap(), count);
+}
+
+#[component]
+fn Memo(setter: Setter<u32>, log: Log, cx: &impl BuildContext) -> impl IntoView {
+    let deps = cx.use_state(|| 1);
+
+    *setter.borrow_mut() = Some(deps.clone());
+
+    let doubled = cx.use_memo(deps.get(), || {
+        log.borrow_mut().push(format!("compute {}", deps.get()));
+
+        deps.get() * 2
+    });
+
+    Label {
+        text: doubled.to_string(),
+    }
+}
+
+#[test]
+fn test_use_memo() {
+    let setter = Setter::default();
+    let log = Log::default();
+
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(
+            Memo {
+                setter: setter.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let deps = setter.borrow().clone().unwrap();
+
+    deps.set(1);
+    context.flush_build();
+    assert_eq!(label_text(&context, &root), "2");
+
+    deps.set(3);
+    context.flush_build();
+    assert_eq!(label_text(&context, &root), "6");
+
+    assert_eq!(*log.borrow(), vec!["compute 1", "compute 3"]);
+}
+
+#[component]
+fn Effects(setter: Setter<u32>, log: Log, cx: &impl BuildContext) -> impl IntoView {
+    let deps = cx.use_state(|| 1);
+
+    *setter.borrow_mut() = Some(deps.clone());
+
+    let value = deps.get();
+
+    cx.use_effect(value, {
+        let log = log.clone();
+
+        move || {
+            log.borrow_mut().push(format!("effect {}", value));
+
+            move || log.borrow_mut().push(format!("cleanup {}", value))
+        }
+    });
+
+    cx.use_effect((), {
+        let log = log.clone();
+
+        move || log.borrow_mut().push("mounted".to_owned())
+    });
+
+    log.borrow_mut().push(format!("build {}", value));
+
+    Label {
+        text: value.to_string(),
+    }
+}
+
+#[test]
+fn test_use_effect() {
+    let setter = Setter::default();
+    let log = Log::default();
+
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(
+            Effects {
+                setter: setter.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    // Effects run after the first build, without waiting for the next build scope.
+    assert_eq!(
+        std::mem::take(&mut *log.borrow_mut()),
+        vec!["build 1", "effect 1", "mounted"]
+    );
+
+    context.flush_build();
+    assert!(log.borrow().is_empty());
+
+    let deps = setter.borrow().clone().unwrap();
+
+    deps.set(1);
+    context.flush_build();
+    assert_eq!(std::mem::take(&mut *log.borrow_mut()), vec!["build 1"]);
+
+    deps.set(2);
+    context.flush_build();
+    assert_eq!(
+        std::mem::take(&mut *log.borrow_mut()),
+        vec!["build 2", "cleanup 1", "effect 2"]
+    );
+
+    context.unmount_subtree(root.to_id().unwrap());
+    assert_eq!(*log.borrow(), vec!["cleanup 2"]);
+}
+
+#[component]
+fn Conditional(setter: Setter<bool>, cx: &impl BuildContext) -> impl IntoView {
+    let flag = cx.use_state(|| false);
+
+    *setter.borrow_mut() = Some(flag.clone());
+
+    if flag.get() {
+        cx.use_memo((), || 1);
+    }
+
+    cx.use_ref(|| 0);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(
+    expected = "Hook order changed: hook #1 is use_memo::<core::option::Option<((), i32)>>, it was use_ref::<i32>"
+)]
+fn test_hook_order_mismatch() {
+    let setter = Setter::default();
+
+    let mut context = FrameworkContext::default();
+
+    let _root = context
+        .mount_root(
+            Conditional {
+                setter: setter.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let flag = setter.borrow().clone().unwrap();
+
+    flag.set(true);
+    context.flush_build();
+}
+
+#[component]
+fn Shrinking(setter: Setter<bool>, cx: &impl BuildContext) -> impl IntoView {
+    let flag = cx.use_state(|| true);
+
+    *setter.borrow_mut() = Some(flag.clone());
+
+    if flag.get() {
+        cx.use_ref(|| 0);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(
+    expected = "Fewer hooks than in the previous build: 1 called, 2 in the previous build"
+)]
+fn test_hook_count_mismatch() {
+    let setter = Setter::default();
+
+    let mut context = FrameworkContext::default();
+
+    let _root = context
+        .mount_root(
+            Shrinking {
+                setter: setter.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let flag = setter.borrow().clone().unwrap();
+
+    flag.set(false);
+    context.flush_build();
+}
@@ -0,0 +1,224 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    color: Rc<Cell<u32>>,
+    log: Log,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            color: self.color.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+struct RootState {
+    color: Rc<Cell<u32>>,
+    log: Log,
+}
+
+impl RootState {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Theme {
+            color: self.color.get(),
+            child: Holder {
+                children: vec![
+                    Swatch {
+                        log: self.log.clone(),
+                    }
+                    .into_view(),
+                    Plain {
+                        log: self.log.clone(),
+                    }
+                    .into_view(),
+                ],
+            }
+            .into_view(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Inherited)]
+struct Theme {
+    color: u32,
+    child: View,
+}
+
+impl Theme {
+    fn child(&self) -> View {
+        self.child.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Swatch {
+    log: Log,
+}
+
+impl Swatch {
+    fn build(&self, context: &impl BuildContext) -> impl IntoView {
+        let color = context.depend_on_inherited(|theme: &Theme| theme.color);
+
+        self.log.borrow_mut().push(format!("swatch {:?}", color));
+
+        Holder { children: vec![] }
+    }
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Plain {
+    log: Log,
+}
+
+impl Plain {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        self.log.borrow_mut().push("plain".to_owned());
+
+        Holder { children: vec![] }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Holder {
+    children: Vec<View>,
+}
+
+impl Holder {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+#[test]
+fn test_inherited_dependents() {
+    let mut context = FrameworkContext::default();
+
+    let color = Rc::new(Cell::new(1));
+    let log = Log::default();
+
+    let root = context
+        .mount_root(
+            Root {
+                color: color.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(*log.borrow(), vec!["swatch Some(1)", "plain"]);
+
+    log.borrow_mut().clear();
+    color.set(2);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    // Only the dependent element is rebuilt.
+    assert_eq!(*log.borrow(), vec!["swatch Some(2)"]);
+
+    log.borrow_mut().clear();
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert!(log.borrow().is_empty());
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Toggle {
+    depend: bool,
+    log: Log,
+}
+
+impl Toggle {
+    fn build(&self, context: &impl BuildContext) -> impl IntoView {
+        let color = if self.depend {
+            context.depend_on_inherited(|theme: &Theme| theme.color)
+        } else {
+            None
+        };
+
+        self.log.borrow_mut().push(format!("toggle {:?}", color));
+
+        Holder { children: vec![] }
+    }
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct ToggleRoot {
+    color: Rc<Cell<u32>>,
+    depend: Rc<Cell<bool>>,
+    log: Log,
+}
+
+impl ToggleRoot {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Theme {
+            color: self.color.get(),
+            child: Toggle {
+                depend: self.depend.get(),
+                log: self.log.clone(),
+            }
+            .into_view(),
+        }
+    }
+}
+
+#[test]
+fn test_rebuild_clears_dependencies() {
+    let mut context = FrameworkContext::default();
+
+    let color = Rc::new(Cell::new(1));
+    let depend = Rc::new(Cell::new(true));
+    let log = Log::default();
+
+    let root = context
+        .mount_root(
+            ToggleRoot {
+                color: color.clone(),
+                depend: depend.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(*log.borrow(), vec!["toggle Some(1)"]);
+
+    depend.set(false);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(*log.borrow(), vec!["toggle Some(1)", "toggle None"]);
+
+    log.borrow_mut().clear();
+    color.set(2);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    // The last build didn't depend on the theme.
+    assert!(log.borrow().is_empty());
+}
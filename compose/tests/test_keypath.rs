@@ -0,0 +1,276 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    ids: Rc<RefCell<Vec<u64>>>,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            ids: self.ids.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RootState {
+    ids: Rc<RefCell<Vec<u64>>>,
+}
+
+impl State for RootState {
+    fn framework_build(&self, _element: &StatefulElement) -> View {
+        List {
+            ids: self.ids.borrow().clone(),
+        }
+        .into_view()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct List {
+    ids: Vec<u64>,
+}
+
+impl List {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.ids
+            .iter()
+            .map(|id| Item { id: *id }.into_view())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Item {
+    #[compose(key)]
+    id: u64,
+}
+
+impl Item {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+#[test]
+fn test_key_field() {
+    let item = |id| Item { id }.into_view();
+
+    let a = item(1);
+    let b = item(2);
+
+    assert_eq!(
+        a.to_keypath().unwrap().to_caller(),
+        b.to_keypath().unwrap().to_caller()
+    );
+
+    assert_ne!(a.to_keypath(), b.to_keypath());
+
+    assert_eq!(
+        a.to_keypath(),
+        Some(&KeyPath::from(a.to_keypath().unwrap().to_caller().unwrap()).join(1u64))
+    );
+}
+
+#[test]
+fn test_into_views() {
+    let views = (0..3).map(|_| List { ids: vec![] }).into_views();
+
+    let caller = views[0].to_keypath().unwrap().to_caller();
+
+    for (index, view) in views.iter().enumerate() {
+        assert_eq!(view.to_keypath().unwrap().to_caller(), caller);
+        assert_eq!(
+            view.to_keypath(),
+            Some(&KeyPath::from(caller.unwrap()).join(KeyPath::Index(index)))
+        );
+    }
+
+    // User keys are kept instead of the index.
+    let views = (0..3).rev().map(|id| Item { id }).into_views();
+
+    let caller = views[0].to_keypath().unwrap().to_caller();
+
+    for (view, id) in views.iter().zip((0..3u64).rev()) {
+        assert_eq!(
+            view.to_keypath(),
+            Some(&KeyPath::from(caller.unwrap()).join(id))
+        );
+    }
+
+    let view = Item { id: 0 }.into_view().with_key("first");
+
+    assert_eq!(
+        view.to_keypath(),
+        Some(
+            &KeyPath::from(view.to_keypath().unwrap().to_caller().unwrap())
+                .join(KeyPath::Integer(0))
+                .join("first")
+        )
+    );
+
+    assert_eq!(KeyPath::hash_of("hello"), KeyPath::hash_of("hello"));
+    assert_ne!(KeyPath::hash_of("hello"), KeyPath::hash_of("world"));
+}
+
+#[test]
+fn test_keyed_reorder() {
+    let mut context = FrameworkContext::default();
+
+    let ids = Rc::new(RefCell::new(vec![1, 2, 3]));
+
+    let view = Root { ids: ids.clone() }.into_view();
+
+    let root = context.mount_root(view).unwrap();
+
+    let list = root
+        .search_first_render_object_element_id(&context)
+        .unwrap();
+
+    let old = list
+        .children(&context.element_tree.borrow())
+        .collect::<Vec<_>>();
+
+    *ids.borrow_mut() = vec![3, 1, 4, 2];
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    let new = list
+        .children(&context.element_tree.borrow())
+        .collect::<Vec<_>>();
+
+    assert_eq!(new.len(), 4);
+    assert_eq!(new[0], old[2]);
+    assert_eq!(new[1], old[0]);
+    assert_eq!(new[3], old[1]);
+    assert!(!old.contains(&new[2]));
+}
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[derive(Debug, PartialEq, Stateless)]
+struct TrackedRoot {
+    ids: Rc<RefCell<Vec<u64>>>,
+    log: Log,
+}
+
+impl TrackedRoot {
+    fn build(&self, _element: &StatelessElement) -> impl IntoView {
+        TrackedList {
+            ids: self.ids.borrow().clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct TrackedList {
+    ids: Vec<u64>,
+    log: Log,
+}
+
+impl TrackedList {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.ids
+            .iter()
+            .map(|id| Tracked {
+                id: *id,
+                log: self.log.clone(),
+            })
+            .into_views()
+    }
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Tracked {
+    #[compose(key)]
+    id: u64,
+    log: Log,
+}
+
+impl Tracked {
+    fn create_state(&self) -> TrackedState {
+        TrackedState {
+            id: self.id,
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+#[state(init_state)]
+struct TrackedState {
+    id: u64,
+    log: Log,
+}
+
+impl TrackedState {
+    fn init_state(&mut self, _element: &StatefulElement) {
+        self.log.borrow_mut().push(format!("init {}", self.id));
+    }
+
+    fn build(&self, _element: &StatefulElement) {}
+}
+
+#[test]
+fn test_into_views_keyed_reorder() {
+    let mut context = FrameworkContext::default();
+
+    let ids = Rc::new(RefCell::new(vec![1, 2, 3]));
+    let log = Log::default();
+
+    let root = context
+        .mount_root(
+            TrackedRoot {
+                ids: ids.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(*log.borrow(), vec!["init 1", "init 2", "init 3"]);
+
+    let list = root
+        .search_first_render_object_element_id(&context)
+        .unwrap();
+
+    let old = list
+        .children(&context.element_tree.borrow())
+        .collect::<Vec<_>>();
+
+    *ids.borrow_mut() = vec![3, 1, 4, 2];
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    let new = list
+        .children(&context.element_tree.borrow())
+        .collect::<Vec<_>>();
+
+    // Moved items keep their elements and states, only the new item is initialized.
+    assert_eq!(new, vec![old[2], old[0], new[2], old[1]]);
+    assert!(!old.contains(&new[2]));
+    assert_eq!(*log.borrow(), vec!["init 1", "init 2", "init 3", "init 4"]);
+}
@@ -0,0 +1,302 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+#[derive(Debug, PartialEq, Render)]
+struct Tracked {
+    name: &'static str,
+    repaint_boundary: bool,
+    log: Log,
+    children: Vec<View>,
+}
+
+impl Tracked {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        TrackedRenderObject {
+            name: self.name,
+            repaint_boundary: self.repaint_boundary,
+            log: self.log.clone(),
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct TrackedRenderObject {
+    name: &'static str,
+    repaint_boundary: bool,
+    log: Log,
+}
+
+impl RenderObjectLifecycle for TrackedRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        for (index, child) in context.children().into_iter().enumerate() {
+            context.layout_child(child, BoxConstraints::tight(Size::new(10.0, 10.0)));
+            context.position_child(child, Offset::new(index as f32 * 10.0, 0.0));
+        }
+
+        constraints.biggest()
+    }
+
+    fn is_repaint_boundary(&self) -> bool {
+        self.repaint_boundary
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        self.log.borrow_mut().push(self.name);
+
+        let rect = Rect::from_offset_size(offset, context.size());
+
+        context.canvas().draw_rect(rect, &Paint::default());
+        context.paint_children(offset);
+    }
+}
+
+fn tracked(name: &'static str, repaint_boundary: bool, log: &Log, children: Vec<View>) -> View {
+    Tracked {
+        name,
+        repaint_boundary,
+        log: log.clone(),
+        children,
+    }
+    .into_view()
+}
+
+fn mount(view: View) -> FrameworkContext {
+    let mut context = FrameworkContext::default();
+
+    context.mount_root(view).unwrap();
+
+    context.flush_layout(BoxConstraints::tight(Size::new(100.0, 100.0)));
+
+    context
+}
+
+/// Returns the layers of the first render tree root.
+fn root_layers(layer: &Layer) -> Vec<Layer> {
+    let Layer::Retained(root) = &layer.children()[0] else {
+        panic!("expect retained root layer");
+    };
+
+    root.borrow().children().to_vec()
+}
+
+fn render_object(context: &FrameworkContext, depth: usize) -> RenderObject {
+    let render_tree = context.render_tree.borrow();
+
+    let id = context.render_roots()[0]
+        .descendants(&render_tree)
+        .nth(depth)
+        .unwrap();
+
+    render_tree[id].get().clone()
+}
+
+#[test]
+fn test_repaint_boundary_cache() {
+    let log = Log::default();
+
+    // root -> [boundary -> inner, sibling]
+    let mut context = mount(tracked(
+        "root",
+        false,
+        &log,
+        vec![
+            tracked(
+                "boundary",
+                true,
+                &log,
+                vec![tracked("inner", false, &log, vec![])],
+            ),
+            tracked("sibling", false, &log, vec![]),
+        ],
+    ));
+
+    let layer = context.flush_layers();
+
+    // Dirty boundaries are painted deepest first.
+    assert_eq!(*log.borrow(), vec!["boundary", "inner", "root", "sibling"]);
+
+    let roots = root_layers(&layer);
+
+    assert!(matches!(roots[0], Layer::Picture(_)));
+    assert!(matches!(&roots[1], Layer::Offset(offset, _) if *offset == Offset::ZERO));
+    assert!(matches!(roots[2], Layer::Picture(_)));
+
+    let pictures = layer.pictures();
+
+    assert_eq!(pictures.len(), 3);
+
+    // The sibling is outside of the boundary, the boundary layer is reused.
+    log.borrow_mut().clear();
+    render_object(&context, 3).mark_needs_paint();
+
+    let second = context.flush_layers().pictures();
+
+    assert_eq!(*log.borrow(), vec!["root", "sibling"]);
+    assert!(Rc::ptr_eq(&pictures[1], &second[1]));
+    assert!(!Rc::ptr_eq(&pictures[2], &second[2]));
+
+    // Dirty descendant repaints its boundary only, the root layer retains the new boundary layer.
+    log.borrow_mut().clear();
+    render_object(&context, 2).mark_needs_paint();
+
+    let third = context.flush_layers().pictures();
+
+    assert_eq!(*log.borrow(), vec!["boundary", "inner"]);
+    assert!(Rc::ptr_eq(&second[0], &third[0]));
+    assert!(!Rc::ptr_eq(&second[1], &third[1]));
+
+    // Nothing dirty, nothing painted.
+    log.borrow_mut().clear();
+    context.flush_layers();
+
+    assert!(log.borrow().is_empty());
+}
+
+#[derive(Debug, Default)]
+struct LogCanvas {
+    log: Vec<String>,
+}
+
+impl Canvas for LogCanvas {
+    fn save(&mut self) {
+        self.log.push("save".to_owned());
+    }
+
+    fn save_layer(&mut self, alpha: u8) {
+        self.log.push(format!("save_layer {}", alpha));
+    }
+
+    fn restore(&mut self) {
+        self.log.push("restore".to_owned());
+    }
+
+    fn transform(&mut self, transform: &Transform) {
+        self.log
+            .push(format!("transform {} {}", transform.e, transform.f));
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.log
+            .push(format!("clip {} {}", rect.right, rect.bottom));
+    }
+
+    fn clip_path(&mut self, _path: &Path) {}
+
+    fn draw_path(&mut self, _path: &Path, _paint: &Paint) {}
+
+    fn draw_rect(&mut self, rect: Rect, _paint: &Paint) {
+        self.log.push(format!("rect {} {}", rect.left, rect.top));
+    }
+
+    fn draw_text(&mut self, _run: &TextRun, _offset: Offset, _paint: &Paint) {}
+
+    fn draw_image(&mut self, _image: &Image, _rect: Rect, _paint: &Paint) {}
+}
+
+#[test]
+fn test_composite() {
+    let mut recorder = PictureRecorder::default();
+
+    recorder.draw_rect(Rect::from_ltwh(1.0, 2.0, 3.0, 4.0), &Paint::default());
+
+    let picture = Rc::new(recorder.finish());
+
+    assert!(recorder.is_empty());
+
+    let layer = Layer::Container(vec![
+        Layer::Opacity(
+            128,
+            vec![Layer::Clip(
+                Clip::Rect(Rect::from_ltwh(0.0, 0.0, 5.0, 6.0)),
+                vec![Layer::Picture(picture.clone())],
+            )],
+        ),
+        Layer::Offset(Offset::new(7.0, 8.0), vec![Layer::Picture(picture)]),
+    ]);
+
+    let mut canvas = LogCanvas::default();
+
+    layer.composite(&mut canvas);
+
+    assert_eq!(
+        canvas.log,
+        vec![
+            "save_layer 128",
+            "save",
+            "clip 5 6",
+            "rect 1 2",
+            "restore",
+            "restore",
+            "save",
+            "transform 7 8",
+            "rect 1 2",
+            "restore",
+        ]
+    );
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Faded {
+    child: View,
+}
+
+impl Faded {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        FadedRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![self.child.clone()]
+    }
+}
+
+#[derive(Debug)]
+struct FadedRenderObject {}
+
+impl RenderObjectLifecycle for FadedRenderObject {
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        context.canvas().draw_rect(Rect::ZERO, &Paint::default());
+
+        context.push_opacity(64, |context| {
+            context.push_transform(Transform::scale(2.0, 2.0), |context| {
+                context.paint_children(offset);
+            });
+        });
+    }
+}
+
+#[test]
+fn test_push_layers() {
+    let log = Log::default();
+
+    let mut context = mount(
+        Faded {
+            child: tracked("child", false, &log, vec![]),
+        }
+        .into_view(),
+    );
+
+    let roots = root_layers(&context.flush_layers());
+
+    assert_eq!(roots.len(), 2);
+    assert!(matches!(roots[0], Layer::Picture(_)));
+
+    let Layer::Opacity(64, children) = &roots[1] else {
+        panic!("expect opacity layer");
+    };
+
+    let Layer::Transform(transform, children) = &children[0] else {
+        panic!("expect transform layer");
+    };
+
+    assert_eq!(*transform, Transform::scale(2.0, 2.0));
+    assert!(matches!(children[0], Layer::Picture(_)));
+    assert_eq!(*log.borrow(), vec!["child"]);
+}
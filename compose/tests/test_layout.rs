@@ -0,0 +1,224 @@
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, PartialEq, Render)]
+struct Column {
+    children: Vec<View>,
+}
+
+impl Column {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        ColumnRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct ColumnRenderObject {}
+
+impl RenderObjectLifecycle for ColumnRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        let child_constraints = BoxConstraints::new(0.0, constraints.max_width, 0.0, f32::INFINITY);
+
+        let mut y = 0.0;
+
+        for child in context.children() {
+            let size = context.layout_child(child, child_constraints);
+
+            context.position_child(child, Offset::new(0.0, y));
+
+            y += size.height;
+        }
+
+        constraints.constrain(Size::new(constraints.max_width, y))
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Padding {
+    padding: f32,
+    child: View,
+}
+
+impl Padding {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        PaddingRenderObject {
+            padding: self.padding,
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![self.child.clone()]
+    }
+}
+
+#[derive(Debug)]
+struct PaddingRenderObject {
+    padding: f32,
+}
+
+impl RenderObjectLifecycle for PaddingRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        let padding = self.padding;
+        let mut size = Size::new(padding * 2.0, padding * 2.0);
+
+        for child in context.children() {
+            let child_size =
+                context.layout_child(child, constraints.deflate(padding * 2.0, padding * 2.0));
+
+            context.position_child(child, Offset::new(padding, padding));
+
+            size.width += child_size.width;
+            size.height += child_size.height;
+        }
+
+        size
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct SizedBox {
+    width: f32,
+    height: f32,
+}
+
+impl SizedBox {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        SizedBoxRenderObject {
+            size: Size::new(self.width, self.height),
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct SizedBoxRenderObject {
+    size: Size,
+}
+
+impl RenderObjectLifecycle for SizedBoxRenderObject {
+    fn perform_layout(
+        &mut self,
+        _context: &mut LayoutContext,
+        constraints: BoxConstraints,
+    ) -> Size {
+        constraints.constrain(self.size)
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct SelfLayout {}
+
+impl SelfLayout {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        SelfLayoutRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct SelfLayoutRenderObject {}
+
+impl RenderObjectLifecycle for SelfLayoutRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        let id = context.to_id();
+
+        context.layout_child(id, constraints)
+    }
+}
+
+#[test]
+fn test_box_constraints() {
+    let constraints = BoxConstraints::new(10.0, 100.0, 20.0, 50.0);
+
+    assert_eq!(
+        constraints.constrain(Size::new(0.0, 80.0)),
+        Size::new(10.0, 50.0)
+    );
+    assert_eq!(constraints.loosen().smallest(), Size::ZERO);
+    assert_eq!(constraints.biggest(), Size::new(100.0, 50.0));
+    assert_eq!(
+        constraints.deflate(20.0, 40.0),
+        BoxConstraints::new(0.0, 80.0, 0.0, 10.0)
+    );
+    assert!(BoxConstraints::tight(Size::new(1.0, 2.0)).is_tight());
+    assert_eq!(BoxConstraints::default().biggest(), Size::ZERO);
+}
+
+#[test]
+fn test_layout() {
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(
+            Column {
+                children: vec![
+                    SizedBox {
+                        width: 500.0,
+                        height: 30.0,
+                    }
+                    .into_view(),
+                    Padding {
+                        padding: 10.0,
+                        child: SizedBox {
+                            width: 40.0,
+                            height: 20.0,
+                        }
+                        .into_view(),
+                    }
+                    .into_view(),
+                ],
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    context.flush_layout(BoxConstraints::loose(Size::new(200.0, 300.0)));
+
+    let render_tree = context.render_tree.borrow();
+
+    let column_id = root.to_render_object_id().unwrap();
+    let column = render_tree.get(column_id).unwrap().get();
+
+    assert_eq!(column.size(), Some(Size::new(200.0, 70.0)));
+    assert!(!column.needs_layout());
+
+    let children = column_id
+        .children(&render_tree)
+        .map(|id| render_tree.get(id).unwrap().get().clone())
+        .collect::<Vec<_>>();
+
+    // The first box is clamped to the column width.
+    assert_eq!(children[0].size(), Some(Size::new(200.0, 30.0)));
+    assert_eq!(children[0].offset(), Offset::ZERO);
+
+    assert_eq!(children[1].size(), Some(Size::new(60.0, 40.0)));
+    assert_eq!(children[1].offset(), Offset::new(0.0, 30.0));
+
+    let padded_id = children[1].to_id().unwrap();
+    let padded = render_tree
+        .get(padded_id.children(&render_tree).next().unwrap())
+        .unwrap()
+        .get();
+
+    assert_eq!(padded.size(), Some(Size::new(40.0, 20.0)));
+    assert_eq!(padded.offset(), Offset::new(10.0, 10.0));
+}
+
+#[test]
+#[should_panic(expected = "is not a child of the render object being laid out")]
+fn test_layout_non_child() {
+    let mut context = FrameworkContext::default();
+
+    context.mount_root(SelfLayout {}.into_view()).unwrap();
+
+    context.flush_layout(BoxConstraints::loose(Size::new(200.0, 300.0)));
+}
@@ -0,0 +1,237 @@
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+/// Canvas recording the draw calls as text.
+#[derive(Debug, Default)]
+struct LogCanvas {
+    log: Vec<String>,
+}
+
+impl Canvas for LogCanvas {
+    fn save(&mut self) {
+        self.log.push("save".to_owned());
+    }
+
+    fn restore(&mut self) {
+        self.log.push("restore".to_owned());
+    }
+
+    fn transform(&mut self, transform: &Transform) {
+        self.log
+            .push(format!("transform {} {}", transform.e, transform.f));
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.log.push(format!(
+            "clip {} {} {} {}",
+            rect.left, rect.top, rect.right, rect.bottom
+        ));
+    }
+
+    fn clip_path(&mut self, _path: &Path) {
+        self.log.push("clip path".to_owned());
+    }
+
+    fn draw_rect(&mut self, rect: Rect, paint: &Paint) {
+        self.log.push(format!(
+            "rect {} {} {} {} {:?}",
+            rect.left, rect.top, rect.right, rect.bottom, paint.color
+        ));
+    }
+
+    fn draw_path(&mut self, _path: &Path, _paint: &Paint) {
+        self.log.push("path".to_owned());
+    }
+
+    fn draw_text(&mut self, run: &TextRun, offset: Offset, _paint: &Paint) {
+        self.log
+            .push(format!("text {} {} {}", run.text, offset.dx, offset.dy));
+    }
+
+    fn draw_image(&mut self, _image: &Image, _rect: Rect, _paint: &Paint) {
+        self.log.push("image".to_owned());
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Boxed {
+    color: Color,
+    size: f32,
+    children: Vec<View>,
+}
+
+impl Boxed {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        BoxedRenderObject {
+            color: self.color,
+            size: self.size,
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct BoxedRenderObject {
+    color: Color,
+    size: f32,
+}
+
+impl RenderObjectLifecycle for BoxedRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        let mut x = 0.0;
+
+        for child in context.children() {
+            let size = context.layout_child(child, constraints.loosen());
+
+            context.position_child(child, Offset::new(x, 5.0));
+
+            x += size.width;
+        }
+
+        constraints.constrain(Size::new(self.size, self.size))
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        let rect = Rect::from_offset_size(offset, context.size());
+
+        context.canvas().draw_rect(rect, &Paint::fill(self.color));
+
+        context.canvas().save();
+        context.canvas().clip_rect(rect);
+        context.paint_children(offset);
+        context.canvas().restore();
+    }
+}
+
+#[test]
+fn test_paint_order() {
+    let mut context = FrameworkContext::default();
+
+    let red = Color::rgb(255, 0, 0);
+    let green = Color::rgb(0, 255, 0);
+    let blue = Color::rgb(0, 0, 255);
+
+    let root = context
+        .mount_root(
+            Boxed {
+                color: red,
+                size: 100.0,
+                children: vec![
+                    Boxed {
+                        color: green,
+                        size: 10.0,
+                        children: vec![],
+                    }
+                    .into_view(),
+                    Boxed {
+                        color: blue,
+                        size: 20.0,
+                        children: vec![],
+                    }
+                    .into_view(),
+                ],
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    context.flush_layout(BoxConstraints::loose(Size::new(200.0, 200.0)));
+
+    let mut canvas = LogCanvas::default();
+
+    context.flush_paint(&mut canvas);
+
+    assert_eq!(
+        canvas.log,
+        vec![
+            format!("rect 0 0 100 100 {:?}", red),
+            "save".to_owned(),
+            "clip 0 0 100 100".to_owned(),
+            format!("rect 0 5 10 15 {:?}", green),
+            "save".to_owned(),
+            "clip 0 5 10 15".to_owned(),
+            "restore".to_owned(),
+            format!("rect 10 5 30 25 {:?}", blue),
+            "save".to_owned(),
+            "clip 10 5 30 25".to_owned(),
+            "restore".to_owned(),
+            "restore".to_owned(),
+        ]
+    );
+
+    assert!(!root.first_render_object(&context).unwrap().needs_paint());
+}
+
+#[test]
+fn test_canvas_defaults() {
+    let mut canvas = LogCanvas::default();
+
+    canvas.translate(Offset::new(3.0, 4.0));
+    canvas.draw_rrect(
+        RRect::from_rect_radius(Rect::from_ltwh(0.0, 0.0, 10.0, 10.0), 2.0),
+        &Paint::default(),
+    );
+    canvas.clip_rrect(RRect::default());
+
+    assert_eq!(canvas.log, vec!["transform 3 4", "path", "clip path"]);
+}
+
+#[test]
+fn test_transform() {
+    let transform = Transform::scale(2.0, 3.0).then(&Transform::translation(10.0, 20.0));
+
+    assert_eq!(
+        transform.map_point(Offset::new(1.0, 1.0)),
+        Offset::new(12.0, 23.0)
+    );
+
+    let inverse = transform.invert().unwrap();
+
+    assert_eq!(
+        inverse.map_point(Offset::new(12.0, 23.0)),
+        Offset::new(1.0, 1.0)
+    );
+
+    assert!(Transform::scale(0.0, 1.0).invert().is_none());
+
+    // Determinant 1e-8 is below f32::EPSILON, still invertible.
+    let small = Transform::scale(1e-4, 1e-4).invert().unwrap();
+    let point = small.map_point(Offset::new(1e-4, 2e-4));
+
+    assert!((point.dx - 1.0).abs() < 1e-4);
+    assert!((point.dy - 2.0).abs() < 1e-4);
+
+    let rotated = Transform::rotation(std::f32::consts::FRAC_PI_2)
+        .map_rect(&Rect::from_ltwh(0.0, 0.0, 10.0, 20.0));
+
+    assert!((rotated.left + 20.0).abs() < 1e-4);
+    assert!((rotated.bottom - 10.0).abs() < 1e-4);
+}
+
+#[test]
+fn test_path_bounds() {
+    let rect = Rect::from_ltwh(5.0, 5.0, 40.0, 20.0);
+
+    assert_eq!(
+        Path::from_rrect(RRect::from_rect_radius(rect, 100.0)).bounds(),
+        Some(rect)
+    );
+    assert_eq!(Path::from_oval(rect).bounds(), Some(rect));
+    assert_eq!(Path::new().bounds(), None);
+}
+
+#[test]
+fn test_image() {
+    let image = Image::new(2, 1, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+    assert_eq!(image.pixel(1, 0), Color::rgba(5, 6, 7, 8));
+}
+
+#[test]
+#[should_panic(expected = "Image pixels length mismatch")]
+fn test_image_size_overflow() {
+    // `65536 * 65536 * 4` overflows u32, it must not wrap to an empty image.
+    Image::new(65536, 65536, vec![]);
+}
@@ -0,0 +1,208 @@
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, PartialEq, Render)]
+struct Square {
+    color: Color,
+    children: Vec<View>,
+}
+
+impl Square {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        SquareRenderObject { color: self.color }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct SquareRenderObject {
+    color: Color,
+}
+
+impl RenderObjectLifecycle for SquareRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        for child in context.children() {
+            context.layout_child(child, BoxConstraints::tight(Size::new(10.0, 10.0)));
+            context.position_child(child, Offset::new(5.0, 5.0));
+        }
+
+        constraints.biggest()
+    }
+
+    fn is_repaint_boundary(&self) -> bool {
+        true
+    }
+
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        let rect = Rect::from_offset_size(offset, context.size());
+
+        context.canvas().draw_rect(rect, &Paint::fill(self.color));
+        context.paint_children(offset);
+    }
+}
+
+fn mount(view: View) -> FrameworkContext {
+    let mut context = FrameworkContext::default();
+
+    context.mount_root(view).unwrap();
+
+    context.flush_layout(BoxConstraints::tight(Size::new(100.0, 100.0)));
+
+    context
+}
+
+fn record(color: Color) -> Picture {
+    let mut recorder = PictureRecorder::default();
+
+    recorder.draw_rect(
+        Rect::from_ltwh(0.0, 0.0, 10.0, 10.0),
+        &Paint::fill(Color::WHITE),
+    );
+    recorder.save();
+    recorder.translate(Offset::new(5.0, 5.0));
+    recorder.draw_rect(Rect::from_ltwh(0.0, 0.0, 10.0, 10.0), &Paint::fill(color));
+    recorder.restore();
+
+    recorder.finish()
+}
+
+#[test]
+fn test_record_frame() {
+    let mut context = mount(
+        Square {
+            color: Color::WHITE,
+            children: vec![Square {
+                color: Color::BLACK,
+                children: vec![],
+            }
+            .into_view()],
+        }
+        .into_view(),
+    );
+
+    let picture = context.record_frame();
+
+    assert_eq!(
+        picture.commands,
+        vec![
+            DrawCommand::DrawRect(
+                Rect::from_ltwh(0.0, 0.0, 100.0, 100.0),
+                Paint::fill(Color::WHITE)
+            ),
+            DrawCommand::Save,
+            DrawCommand::Transform(Transform::translation(5.0, 5.0)),
+            DrawCommand::DrawRect(
+                Rect::from_ltwh(0.0, 0.0, 10.0, 10.0),
+                Paint::fill(Color::BLACK)
+            ),
+            DrawCommand::Restore,
+        ]
+    );
+
+    // Replaying the picture draws the same as compositing the layer tree.
+    let mut replayed = PictureRecorder::default();
+
+    picture.playback(&mut replayed);
+
+    assert_eq!(replayed.finish(), context.flush_layers().to_picture());
+}
+
+#[test]
+fn test_picture_json() {
+    let mut recorder = PictureRecorder::default();
+
+    recorder.save_layer(128);
+    recorder.clip_rrect(RRect::from_rect_radius(
+        Rect::from_ltwh(0.0, 0.0, 20.0, 20.0),
+        4.0,
+    ));
+    recorder.draw_path(
+        &Path::from_oval(Rect::from_ltwh(0.0, 0.0, 20.0, 20.0)),
+        &Paint::stroke(Color::rgb(255, 0, 0), 2.0),
+    );
+    recorder.draw_image(
+        &Image::new(1, 1, vec![1, 2, 3, 4]),
+        Rect::from_ltwh(0.0, 0.0, 1.0, 1.0),
+        &Paint::default(),
+    );
+    recorder.restore();
+
+    let picture = recorder.finish();
+    let json = picture.to_json().unwrap();
+
+    assert_eq!(Picture::from_json(&json).unwrap(), picture);
+    assert!(Picture::from_json("{\"commands\": [\"Unknown\"]}").is_err());
+
+    // Pixels must match the image size.
+    let corrupted = json.replace("\"pixels\": [\n", "\"pixels\": [\n 9,\n");
+
+    assert_ne!(corrupted, json);
+    assert!(Picture::from_json(&corrupted)
+        .unwrap_err()
+        .to_string()
+        .contains("Image pixels length mismatch"));
+}
+
+#[test]
+fn test_picture_diff() {
+    let old = record(Color::BLACK);
+
+    assert!(old.diff(&old).is_empty());
+
+    let new = record(Color::WHITE);
+
+    assert_eq!(
+        old.diff(&new),
+        vec![PictureChange::Replace(
+            3,
+            old.commands[3].clone(),
+            new.commands[3].clone()
+        )]
+    );
+
+    let mut longer = new.clone();
+
+    longer.commands.insert(1, DrawCommand::SaveLayer(255));
+    longer.commands.insert(5, DrawCommand::Restore);
+
+    assert_eq!(
+        new.diff(&longer),
+        vec![
+            PictureChange::Insert(1, DrawCommand::SaveLayer(255)),
+            PictureChange::Insert(5, DrawCommand::Restore),
+        ]
+    );
+
+    assert_eq!(
+        longer.diff(&new),
+        vec![
+            PictureChange::Remove(1, DrawCommand::SaveLayer(255)),
+            PictureChange::Remove(5, DrawCommand::Restore),
+        ]
+    );
+}
+
+#[test]
+fn test_picture_diff_large() {
+    let picture = |dx: f32| Picture {
+        commands: (0..1100)
+            .map(|i| DrawCommand::ClipRect(Rect::from_ltwh(i as f32 + dx, 0.0, 1.0, 1.0)))
+            .collect(),
+    };
+
+    let (old, new) = (picture(0.0), picture(0.5));
+
+    // Too large to match, every command is replaced instead of allocating the whole table.
+    let changes = old.diff(&new);
+
+    assert_eq!(changes.len(), 1100);
+    assert_eq!(
+        changes[7],
+        PictureChange::Replace(7, old.commands[7].clone(), new.commands[7].clone())
+    );
+    assert!(changes
+        .iter()
+        .all(|change| matches!(change, PictureChange::Replace(..))));
+}
@@ -0,0 +1,222 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+#[derive(Debug, PartialEq, Render)]
+struct Tracked {
+    name: &'static str,
+    /// Lay out children with tight constraints of this size.
+    tight: Option<f32>,
+    boundary: bool,
+    log: Log,
+    children: Vec<View>,
+}
+
+impl Tracked {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        TrackedRenderObject {
+            name: self.name,
+            tight: self.tight,
+            boundary: self.boundary,
+            log: self.log.clone(),
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct TrackedRenderObject {
+    name: &'static str,
+    tight: Option<f32>,
+    boundary: bool,
+    log: Log,
+}
+
+impl RenderObjectLifecycle for TrackedRenderObject {
+    fn perform_layout(&mut self, context: &mut LayoutContext, constraints: BoxConstraints) -> Size {
+        self.log.borrow_mut().push(self.name);
+
+        let child_constraints = match self.tight {
+            Some(size) => BoxConstraints::tight(Size::new(size, size)),
+            None => constraints.loosen(),
+        };
+
+        for child in context.children() {
+            context.layout_child(child, child_constraints);
+        }
+
+        constraints.biggest()
+    }
+
+    fn is_relayout_boundary(&self) -> bool {
+        self.boundary
+    }
+}
+
+#[derive(Debug, Default)]
+struct NullCanvas {}
+
+impl Canvas for NullCanvas {
+    fn save(&mut self) {}
+    fn restore(&mut self) {}
+    fn transform(&mut self, _transform: &Transform) {}
+    fn clip_path(&mut self, _path: &Path) {}
+    fn draw_path(&mut self, _path: &Path, _paint: &Paint) {}
+    fn draw_text(&mut self, _run: &TextRun, _offset: Offset, _paint: &Paint) {}
+    fn draw_image(&mut self, _image: &Image, _rect: Rect, _paint: &Paint) {}
+}
+
+fn tracked(
+    name: &'static str,
+    tight: Option<f32>,
+    boundary: bool,
+    log: &Log,
+    children: Vec<View>,
+) -> View {
+    Tracked {
+        name,
+        tight,
+        boundary,
+        log: log.clone(),
+        children,
+    }
+    .into_view()
+}
+
+fn render_object(context: &FrameworkContext, depth: usize) -> RenderObject {
+    let render_tree = context.render_tree.borrow();
+
+    let id = context.render_roots()[0]
+        .descendants(&render_tree)
+        .nth(depth)
+        .unwrap();
+
+    render_tree[id].get().clone()
+}
+
+#[test]
+fn test_relayout_boundary() {
+    let log = Log::default();
+
+    // root -> panel (tight constraints, boundary) -> leaf -> tip
+    let mut context = FrameworkContext::default();
+
+    context
+        .mount_root(tracked(
+            "root",
+            Some(50.0),
+            false,
+            &log,
+            vec![tracked(
+                "panel",
+                None,
+                false,
+                &log,
+                vec![tracked(
+                    "leaf",
+                    None,
+                    false,
+                    &log,
+                    vec![tracked("tip", None, false, &log, vec![])],
+                )],
+            )],
+        ))
+        .unwrap();
+
+    let constraints = BoxConstraints::loose(Size::new(100.0, 100.0));
+
+    assert!(context.pipeline_owner.has_dirty_layout());
+
+    context.flush_layout(constraints);
+
+    assert_eq!(*log.borrow(), vec!["root", "panel", "leaf", "tip"]);
+    assert!(!context.pipeline_owner.has_dirty_layout());
+    assert!(render_object(&context, 1).is_relayout_boundary());
+    assert!(!render_object(&context, 2).is_relayout_boundary());
+
+    // Nothing is dirty.
+    log.borrow_mut().clear();
+    context.flush_layout(constraints);
+
+    assert!(log.borrow().is_empty());
+
+    // Relayout stops at the panel boundary, the clean tip keeps its layout.
+    render_object(&context, 2).mark_needs_layout();
+    context.flush_layout(constraints);
+
+    assert_eq!(*log.borrow(), vec!["panel", "leaf"]);
+
+    // New root constraints don't change the tight panel constraints.
+    log.borrow_mut().clear();
+    context.flush_layout(BoxConstraints::loose(Size::new(80.0, 80.0)));
+
+    assert_eq!(*log.borrow(), vec!["root"]);
+}
+
+#[test]
+fn test_explicit_relayout_boundary() {
+    let log = Log::default();
+
+    let mut context = FrameworkContext::default();
+
+    context
+        .mount_root(tracked(
+            "root",
+            None,
+            false,
+            &log,
+            vec![tracked(
+                "boundary",
+                None,
+                true,
+                &log,
+                vec![tracked("leaf", None, false, &log, vec![])],
+            )],
+        ))
+        .unwrap();
+
+    let constraints = BoxConstraints::loose(Size::new(100.0, 100.0));
+
+    context.flush_layout(constraints);
+    log.borrow_mut().clear();
+
+    render_object(&context, 2).mark_needs_layout();
+    context.flush_layout(constraints);
+
+    assert_eq!(*log.borrow(), vec!["boundary", "leaf"]);
+
+    // Root relayout skips the clean boundary laid out with the same constraints.
+    log.borrow_mut().clear();
+    render_object(&context, 0).mark_needs_layout();
+    context.flush_layout(constraints);
+
+    assert_eq!(*log.borrow(), vec!["root"]);
+}
+
+#[test]
+fn test_draw_frame() {
+    let log = Log::default();
+
+    let mut context = FrameworkContext::default();
+
+    context
+        .mount_root(tracked("root", None, false, &log, vec![]))
+        .unwrap();
+
+    let constraints = BoxConstraints::loose(Size::new(100.0, 100.0));
+    let mut canvas = NullCanvas::default();
+
+    assert!(context.draw_frame(constraints, &mut canvas));
+    assert!(!context.draw_frame(constraints, &mut canvas));
+
+    render_object(&context, 0).mark_needs_paint();
+
+    assert!(!context.pipeline_owner.has_dirty_layout());
+    assert!(context.draw_frame(constraints, &mut canvas));
+    assert_eq!(*log.borrow(), vec!["root"]);
+}
@@ -0,0 +1,160 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    show: Rc<Cell<bool>>,
+    value: Rc<Cell<u32>>,
+    log: Log,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            show: self.show.clone(),
+            value: self.value.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+struct RootState {
+    show: Rc<Cell<bool>>,
+    value: Rc<Cell<u32>>,
+    log: Log,
+}
+
+impl RootState {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        let mut children = vec![];
+
+        if self.show.get() {
+            children.push(
+                Tracked {
+                    value: self.value.get(),
+                    log: self.log.clone(),
+                }
+                .into_view(),
+            );
+        }
+
+        Holder { children }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Holder {
+    children: Vec<View>,
+}
+
+impl Holder {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Tracked {
+    value: u32,
+    log: Log,
+}
+
+impl Tracked {
+    fn create_state(&self) -> TrackedState {
+        TrackedState {
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+#[state(init_state, did_update_config, deactivate, dispose)]
+struct TrackedState {
+    log: Log,
+}
+
+impl TrackedState {
+    fn init_state(&mut self, _element: &StatefulElement) {
+        self.log.borrow_mut().push("init_state".to_owned());
+    }
+
+    fn did_update_config(
+        &mut self,
+        _element: &StatefulElement,
+        old: &Configuration<dyn StatefulConfiguration>,
+    ) {
+        let value = old.downcast(|old: &Tracked| old.value).unwrap();
+
+        self.log
+            .borrow_mut()
+            .push(format!("did_update_config {}", value));
+    }
+
+    fn deactivate(&mut self, _element: &StatefulElement) {
+        self.log.borrow_mut().push("deactivate".to_owned());
+    }
+
+    fn dispose(&mut self, _element: &StatefulElement) {
+        self.log.borrow_mut().push("dispose".to_owned());
+    }
+
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        self.log.borrow_mut().push("build".to_owned());
+
+        Holder { children: vec![] }
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+#[test]
+fn test_state_lifecycle() {
+    let mut context = FrameworkContext::default();
+
+    let show = Rc::new(Cell::new(true));
+    let value = Rc::new(Cell::new(1));
+    let log = Log::default();
+
+    let root = context
+        .mount_root(
+            Root {
+                show: show.clone(),
+                value: value.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(*log.borrow(), vec!["init_state", "build"]);
+
+    log.borrow_mut().clear();
+    value.set(2);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(log.borrow()[0], "did_update_config 1");
+
+    log.borrow_mut().clear();
+    show.set(false);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(*log.borrow(), vec!["deactivate", "dispose"]);
+}
@@ -0,0 +1,359 @@
+use std::io::ErrorKind;
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+const FONT: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fonts/DejaVuSansMono.ttf"
+);
+
+fn fonts() -> FontCollection {
+    let fonts = FontCollection::new();
+
+    fonts.load_file(FONT).unwrap();
+
+    fonts
+}
+
+fn styled(text: &str, style: ParagraphStyle) -> Paragraph {
+    Paragraph::new(
+        text,
+        ParagraphStyle {
+            text_style: TextStyle {
+                font_size: 10.0,
+                ..Default::default()
+            },
+            ..style
+        },
+        &fonts(),
+    )
+}
+
+fn laid_out(text: &str, width: f32) -> Paragraph {
+    let mut paragraph = styled(text, Default::default());
+
+    paragraph.layout(width);
+
+    paragraph
+}
+
+/// Advance of every character of the monospace font at the test font size.
+fn advance() -> f32 {
+    styled("x", Default::default()).max_intrinsic_width()
+}
+
+fn assert_close(a: f32, b: f32) {
+    assert!((a - b).abs() < 1e-3, "{} != {}", a, b);
+}
+
+fn lines(paragraph: &Paragraph) -> Vec<&str> {
+    paragraph
+        .line_metrics()
+        .iter()
+        .map(|line| &paragraph.text()[line.start..line.end])
+        .collect()
+}
+
+#[test]
+fn test_font_loading() {
+    let fonts = fonts();
+
+    let font = fonts.resolve(Some("dejavu sans mono")).unwrap();
+    assert_eq!(font.family(), "DejaVu Sans Mono");
+
+    // Unknown families fall back to the first font.
+    assert_eq!(fonts.resolve(Some("Missing")), Some(font.clone()));
+    assert_eq!(fonts.resolve(None), Some(font.clone()));
+    assert_eq!(FontCollection::new().resolve(None), None);
+
+    let metrics = font.metrics(10.0);
+    assert!(metrics.ascent > 0.0 && metrics.descent > 0.0);
+
+    assert_eq!(
+        Font::from_bytes(vec![0; 16], 0).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(
+        fonts.load_file("missing.ttf").unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn test_line_breaking() {
+    let w = advance();
+
+    let paragraph = laid_out("hello world foo", 11.0 * w);
+    assert_eq!(lines(&paragraph), vec!["hello world ", "foo"]);
+
+    // Trailing whitespace hangs past the width.
+    let paragraph = laid_out("hello world", 5.0 * w);
+    assert_eq!(lines(&paragraph), vec!["hello ", "world"]);
+    assert_close(paragraph.line_metrics()[0].width, 5.0 * w);
+    assert_close(paragraph.longest_line(), 5.0 * w);
+
+    // Words wider than the width are split.
+    let paragraph = laid_out("abcdefgh ij", 3.0 * w);
+    assert_eq!(lines(&paragraph), vec!["abc", "def", "gh ", "ij"]);
+
+    let metrics = paragraph.line_metrics();
+    let line_height = metrics[0].height;
+
+    assert_close(paragraph.height(), 4.0 * line_height);
+    assert_close(metrics[1].top, line_height);
+    assert_close(paragraph.alphabetic_baseline(), metrics[0].baseline);
+    assert!(metrics[0].baseline >= metrics[0].ascent);
+}
+
+#[test]
+fn test_hard_breaks() {
+    let paragraph = laid_out("a\n\nbc\n", 100.0);
+
+    let metrics = paragraph.line_metrics();
+
+    assert_eq!(lines(&paragraph), vec!["a", "", "bc", ""]);
+    assert_eq!(
+        metrics
+            .iter()
+            .map(|line| line.hard_break)
+            .collect::<Vec<_>>(),
+        vec![true, true, true, false]
+    );
+    assert_eq!((metrics[3].start, metrics[3].end), (6, 6));
+
+    assert_close(paragraph.max_intrinsic_width(), 2.0 * advance());
+
+    let paragraph = laid_out("", 100.0);
+    assert_eq!(paragraph.line_metrics().len(), 1);
+    assert!(paragraph.height() > 0.0);
+}
+
+#[test]
+fn test_alignment() {
+    let w = advance();
+
+    let aligned = |align: TextAlign| {
+        let mut paragraph = styled(
+            "abc",
+            ParagraphStyle {
+                align,
+                ..Default::default()
+            },
+        );
+
+        paragraph.layout(10.0 * w);
+
+        paragraph.line_metrics()[0].left
+    };
+
+    assert_close(aligned(TextAlign::Left), 0.0);
+    assert_close(aligned(TextAlign::Right), 7.0 * w);
+    assert_close(aligned(TextAlign::Center), 3.5 * w);
+
+    let mut paragraph = styled(
+        "aa bb cc dd",
+        ParagraphStyle {
+            align: TextAlign::Justify,
+            ..Default::default()
+        },
+    );
+
+    paragraph.layout(7.0 * w);
+
+    // The last line is not justified.
+    let metrics = paragraph.line_metrics();
+    assert_eq!(lines(&paragraph), vec!["aa bb ", "cc dd"]);
+    assert_close(metrics[0].width, 7.0 * w);
+    assert_close(metrics[1].width, 5.0 * w);
+
+    assert_close(paragraph.caret_rect(3).left, 5.0 * w);
+    assert_close(paragraph.caret_rect(9).left, 3.0 * w);
+}
+
+#[test]
+fn test_max_lines() {
+    let w = advance();
+
+    let style = |ellipsis: Option<&str>| ParagraphStyle {
+        max_lines: Some(2),
+        ellipsis: ellipsis.map(str::to_owned),
+        ..Default::default()
+    };
+
+    let mut paragraph = styled("aaaa bbbb cccc", style(Some("…")));
+
+    assert_close(
+        paragraph.intrinsic_height(4.0 * w),
+        2.0 * paragraph.intrinsic_height(100.0),
+    );
+
+    paragraph.layout(4.0 * w);
+
+    assert!(paragraph.did_exceed_max_lines());
+    assert_eq!(lines(&paragraph), vec!["aaaa ", "bbb"]);
+    assert_close(paragraph.line_metrics()[1].width, 4.0 * w);
+
+    let mut picture = PictureRecorder::default();
+    paragraph.paint(&mut picture, Offset::ZERO);
+
+    let texts = picture
+        .finish()
+        .commands
+        .into_iter()
+        .filter_map(|command| match command {
+            DrawCommand::DrawText(run, _, _) => Some((run.text, run.glyphs.len())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(texts, vec![("aaaa ".to_owned(), 5), ("bbb…".to_owned(), 4)]);
+
+    // Without ellipsis the last line is kept whole.
+    let mut paragraph = styled("aaaa bbbb cccc", style(None));
+    paragraph.layout(4.0 * w);
+
+    assert!(paragraph.did_exceed_max_lines());
+    assert_eq!(lines(&paragraph), vec!["aaaa ", "bbbb "]);
+
+    paragraph.layout(100.0);
+    assert!(!paragraph.did_exceed_max_lines());
+}
+
+#[test]
+fn test_intrinsics() {
+    let w = advance();
+
+    let paragraph = laid_out("hello wide world ", 100.0);
+    assert_close(paragraph.min_intrinsic_width(), 5.0 * w);
+    assert_close(paragraph.max_intrinsic_width(), 16.0 * w);
+
+    let line_height = paragraph.line_metrics()[0].height;
+    assert_close(paragraph.intrinsic_height(f32::INFINITY), line_height);
+    assert_close(paragraph.intrinsic_height(5.0 * w), 3.0 * line_height);
+
+    let paragraph = laid_out("ab\nabcd", 100.0);
+    assert_close(paragraph.min_intrinsic_width(), 4.0 * w);
+    assert_close(paragraph.max_intrinsic_width(), 4.0 * w);
+
+    // Text at its max intrinsic width doesn't wrap.
+    let paragraph = laid_out("hello wide world", 16.0 * w);
+    assert_eq!(paragraph.line_metrics().len(), 1);
+}
+
+#[test]
+fn test_caret_and_selection() {
+    let w = advance();
+
+    let paragraph = laid_out("hello world", 6.0 * w);
+    let line_height = paragraph.line_metrics()[0].height;
+
+    let caret = paragraph.caret_rect(5);
+    assert_close(caret.left, 5.0 * w);
+    assert_close(caret.top, 0.0);
+    assert_close(caret.height(), line_height);
+    assert_close(caret.width(), 0.0);
+
+    // Soft wrap positions are at the start of the next line.
+    let caret = paragraph.caret_rect(6);
+    assert_close(caret.left, 0.0);
+    assert_close(caret.top, line_height);
+
+    assert_close(paragraph.caret_rect(11).left, 5.0 * w);
+    assert_close(paragraph.caret_rect(100).left, 5.0 * w);
+
+    assert_eq!(paragraph.index_for_position(Offset::new(2.4 * w, 1.0)), 2);
+    assert_eq!(paragraph.index_for_position(Offset::new(2.6 * w, 1.0)), 3);
+    assert_eq!(
+        paragraph.index_for_position(Offset::new(-10.0, line_height * 1.5)),
+        6
+    );
+    assert_eq!(paragraph.index_for_position(Offset::new(100.0, 100.0)), 11);
+
+    let rects = paragraph.selection_rects(3..8);
+
+    assert_eq!(rects.len(), 2);
+    assert_close(rects[0].left, 3.0 * w);
+    assert_close(rects[0].right, 6.0 * w);
+    assert_close(rects[1].left, 0.0);
+    assert_close(rects[1].right, 2.0 * w);
+    assert_close(rects[1].top, line_height);
+
+    assert!(paragraph.selection_rects(4..4).is_empty());
+}
+
+#[test]
+fn test_render_paragraph() {
+    let w = advance();
+
+    let mut context = FrameworkContext::default();
+
+    let root = context
+        .mount_root(
+            DefaultTextStyle {
+                style: TextStyle {
+                    font_size: 10.0,
+                    color: Color::rgb(255, 0, 0),
+                    ..Default::default()
+                },
+                fonts: fonts(),
+                child: Text::new("hello world").into_view(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    context.flush_layout(BoxConstraints::loose(Size::new(6.5 * w, 100.0)));
+
+    let render_object = root.first_render_object(&context).unwrap();
+
+    let line_height = {
+        let node = render_object.0.borrow();
+        let paragraph = node.lifecycle.downcast_ref::<RenderParagraph>().unwrap();
+
+        assert_eq!(paragraph.line_metrics().len(), 2);
+        assert_close(paragraph.compute_max_intrinsic_width(), 11.0 * w);
+
+        paragraph.line_metrics()[0].height
+    };
+
+    let size = render_object.size().unwrap();
+    assert_close(size.width, 6.5 * w);
+    assert_close(size.height, 2.0 * line_height);
+
+    let runs = context
+        .record_frame()
+        .commands
+        .into_iter()
+        .filter_map(|command| match command {
+            DrawCommand::DrawText(run, offset, paint) => Some((run, offset, paint)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(runs.len(), 2);
+
+    let (run, offset, paint) = &runs[1];
+
+    assert_eq!(run.text, "world");
+    assert_eq!(run.font_family, "DejaVu Sans Mono");
+    assert_eq!(paint.color, Color::rgb(255, 0, 0));
+    assert_close(offset.dy, line_height + runs[0].1.dy);
+    assert_close(run.glyphs[1].offset.dx, w);
+    assert!(run.glyphs.iter().all(|glyph| !glyph.outline.is_empty()));
+}
+
+#[test]
+fn test_text_without_fonts() {
+    let mut paragraph = Paragraph::new("hello world", Default::default(), &FontCollection::new());
+
+    paragraph.layout(100.0);
+
+    assert_close(paragraph.max_intrinsic_width(), 0.0);
+    assert_eq!(paragraph.line_metrics().len(), 1);
+    assert!(paragraph.height() > 0.0);
+
+    let mut picture = PictureRecorder::default();
+    paragraph.paint(&mut picture, Offset::ZERO);
+
+    assert!(picture.is_empty());
+}
@@ -0,0 +1,173 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+use indextree::Arena;
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    show: Rc<Cell<bool>>,
+    log: Log,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            show: self.show.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+struct RootState {
+    show: Rc<Cell<bool>>,
+    log: Log,
+}
+
+impl RootState {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        let mut children = vec![];
+
+        if self.show.get() {
+            children.push(
+                Panel {
+                    log: self.log.clone(),
+                }
+                .into_view(),
+            );
+        }
+
+        Holder { children }
+    }
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Panel {
+    log: Log,
+}
+
+impl Panel {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Holder {
+            children: vec![
+                Tracked {
+                    name: "first",
+                    log: self.log.clone(),
+                }
+                .into_view(),
+                Tracked {
+                    name: "second",
+                    log: self.log.clone(),
+                }
+                .into_view(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Holder {
+    children: Vec<View>,
+}
+
+impl Holder {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Tracked {
+    name: &'static str,
+    log: Log,
+}
+
+impl Tracked {
+    fn create_state(&self) -> TrackedState {
+        TrackedState {
+            name: self.name,
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+#[state(dispose)]
+struct TrackedState {
+    name: &'static str,
+    log: Log,
+}
+
+impl TrackedState {
+    fn dispose(&mut self, _element: &StatefulElement) {
+        self.log.borrow_mut().push(format!("dispose {}", self.name));
+    }
+
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Holder {
+            children: vec![Holder { children: vec![] }.into_view()],
+        }
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+fn live_count<T>(arena: &Arena<T>) -> usize {
+    arena.iter().filter(|node| !node.is_removed()).count()
+}
+
+#[test]
+fn test_unmount_subtree() {
+    let mut context = FrameworkContext::default();
+
+    let show = Rc::new(Cell::new(true));
+    let log = Log::default();
+
+    let root = context
+        .mount_root(
+            Root {
+                show: show.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    // root, holder, panel, holder, 2 * (tracked, holder, holder)
+    assert_eq!(live_count(&context.element_tree.borrow()), 10);
+    assert_eq!(live_count(&context.render_tree.borrow()), 6);
+
+    show.set(false);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(live_count(&context.element_tree.borrow()), 2);
+    assert_eq!(live_count(&context.render_tree.borrow()), 1);
+
+    let render_root = root.first_render_object(&context).unwrap();
+    let render_root = render_root.0.borrow().id.unwrap();
+
+    assert!(render_root
+        .children(&context.render_tree.borrow())
+        .next()
+        .is_none());
+
+    let mut log = log.borrow().clone();
+
+    log.sort();
+
+    assert_eq!(log, vec!["dispose first", "dispose second"]);
+}
@@ -0,0 +1,255 @@
+use std::{cell::RefCell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    A(usize),
+    B,
+    C,
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    kinds: Rc<RefCell<Vec<Kind>>>,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            kinds: self.kinds.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RootState {
+    kinds: Rc<RefCell<Vec<Kind>>>,
+}
+
+impl State for RootState {
+    fn framework_build(&self, _element: &StatefulElement) -> View {
+        Column {
+            kinds: self.kinds.borrow().clone(),
+        }
+        .into_view()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Column {
+    kinds: Vec<Kind>,
+}
+
+fn a(value: usize) -> View {
+    Item { value }.into_view()
+}
+
+fn b() -> View {
+    same_call(Item { value: 0 })
+}
+
+fn c() -> View {
+    same_call(Other {})
+}
+
+/// Views created here share the key path, only their types differ.
+fn same_call<V: IntoView>(view: V) -> View {
+    view.into_view()
+}
+
+impl Column {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        ItemRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.kinds
+            .iter()
+            .map(|kind| match kind {
+                Kind::A(value) => a(*value),
+                Kind::B => b(),
+                Kind::C => c(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Item {
+    value: usize,
+}
+
+impl Item {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        ItemRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Other {}
+
+impl Other {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        ItemRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct ItemRenderObject {}
+
+impl RenderObjectLifecycle for ItemRenderObject {}
+
+fn children(context: &FrameworkContext, root: &Element) -> Vec<ElementId> {
+    let column = root.search_first_render_object_element_id(context).unwrap();
+
+    column
+        .children(&context.element_tree.borrow())
+        .collect::<Vec<_>>()
+}
+
+fn render_children(context: &FrameworkContext, root: &Element) -> Vec<RenderObjectId> {
+    let column = root.first_render_object(context).unwrap();
+
+    let id = column.0.borrow().id.unwrap();
+
+    id.children(&context.render_tree.borrow()).collect()
+}
+
+fn render_object_ids(context: &FrameworkContext, children: &[ElementId]) -> Vec<RenderObjectId> {
+    children
+        .iter()
+        .map(|id| {
+            context
+                .element_tree
+                .borrow()
+                .get(*id)
+                .unwrap()
+                .get()
+                .to_render_object_id()
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_reorder_reuses_elements() {
+    let mut context = FrameworkContext::default();
+
+    let kinds = Rc::new(RefCell::new(vec![Kind::A(1), Kind::A(2), Kind::B]));
+
+    let root = context
+        .mount_root(
+            Root {
+                kinds: kinds.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let old = children(&context, &root);
+
+    assert_eq!(old.len(), 3);
+    assert_eq!(
+        render_children(&context, &root),
+        render_object_ids(&context, &old)
+    );
+
+    *kinds.borrow_mut() = vec![Kind::B, Kind::A(1), Kind::A(2)];
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    let new = children(&context, &root);
+
+    assert_eq!(new, vec![old[2], old[0], old[1]]);
+    assert_eq!(
+        render_children(&context, &root),
+        render_object_ids(&context, &new)
+    );
+}
+
+#[test]
+fn test_insert_and_remove() {
+    let mut context = FrameworkContext::default();
+
+    let kinds = Rc::new(RefCell::new(vec![Kind::A(1), Kind::B]));
+
+    let root = context
+        .mount_root(
+            Root {
+                kinds: kinds.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let old = children(&context, &root);
+
+    *kinds.borrow_mut() = vec![Kind::A(1), Kind::A(3), Kind::B, Kind::A(4)];
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    let new = children(&context, &root);
+
+    assert_eq!(new.len(), 4);
+    assert_eq!(new[0], old[0]);
+    assert_eq!(new[2], old[1]);
+    assert_eq!(
+        render_children(&context, &root),
+        render_object_ids(&context, &new)
+    );
+
+    *kinds.borrow_mut() = vec![Kind::B];
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(children(&context, &root), vec![old[1]]);
+}
+
+#[test]
+fn test_type_change_replaces_element() {
+    let mut context = FrameworkContext::default();
+
+    let kinds = Rc::new(RefCell::new(vec![Kind::A(1), Kind::B]));
+
+    let root = context
+        .mount_root(
+            Root {
+                kinds: kinds.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let old = children(&context, &root);
+
+    assert!(b().can_update(&b()));
+    assert!(!b().can_update(&c()));
+
+    // Same variant and key path, but another configuration type.
+    *kinds.borrow_mut() = vec![Kind::A(1), Kind::C];
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    let new = children(&context, &root);
+
+    assert_eq!(new[0], old[0]);
+    assert_ne!(new[1], old[1]);
+    assert!(context.element_tree.borrow().get(old[1]).is_none());
+    assert_eq!(
+        render_children(&context, &root),
+        render_object_ids(&context, &new)
+    );
+}
@@ -0,0 +1,187 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use agoraui_compose::{element::Element, framework::FrameworkContext, prelude::*};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    value: Rc<Cell<u32>>,
+    log: Log,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            value: self.value.clone(),
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+struct RootState {
+    value: Rc<Cell<u32>>,
+    log: Log,
+}
+
+impl RootState {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Row {
+            children: vec![
+                Caption {
+                    value: self.value.get(),
+                }
+                .into_view(),
+                Counter {
+                    value: self.value.get(),
+                    log: self.log.clone(),
+                }
+                .into_view(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Stateless)]
+struct Caption {
+    value: u32,
+}
+
+impl Caption {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Label { value: self.value }
+    }
+}
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Counter {
+    value: u32,
+    log: Log,
+}
+
+impl Counter {
+    fn create_state(&self) -> CounterState {
+        CounterState {
+            log: self.log.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+#[state(did_update_config)]
+struct CounterState {
+    log: Log,
+}
+
+impl CounterState {
+    fn did_update_config(
+        &mut self,
+        _element: &StatefulElement,
+        old: &Configuration<dyn StatefulConfiguration>,
+    ) {
+        let value = old.downcast(|old: &Counter| old.value).unwrap();
+
+        self.log
+            .borrow_mut()
+            .push(format!("did_update_config {}", value));
+    }
+
+    fn build(&self, context: &impl BuildContext) -> impl IntoView {
+        let value = match context.to_configuration() {
+            View::Stateful(config) => config.downcast(|config: &Counter| config.value).unwrap(),
+            _ => unreachable!(),
+        };
+
+        self.log.borrow_mut().push(format!("build {}", value));
+
+        Label { value }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Row {
+    children: Vec<View>,
+}
+
+impl Row {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct Label {
+    value: u32,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        EmptyRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct EmptyRenderObject {}
+
+impl RenderObjectLifecycle for EmptyRenderObject {}
+
+fn label_values(context: &FrameworkContext) -> Vec<u32> {
+    let arena = context.element_tree.borrow();
+
+    let elements = arena
+        .iter()
+        .filter(|node| !node.is_removed())
+        .map(|node| node.get().clone())
+        .collect::<Vec<Element>>();
+
+    elements
+        .into_iter()
+        .filter_map(|element| match element.to_configuration() {
+            View::RenderObject(config) => config.downcast(|label: &Label| label.value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_update_rebuilds_children() {
+    let mut context = FrameworkContext::default();
+
+    let value = Rc::new(Cell::new(1));
+    let log = Log::default();
+
+    let root = context
+        .mount_root(
+            Root {
+                value: value.clone(),
+                log: log.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    assert_eq!(label_values(&context), vec![1, 1]);
+    assert_eq!(*log.borrow(), vec!["build 1"]);
+
+    log.borrow_mut().clear();
+    value.set(2);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert_eq!(label_values(&context), vec![2, 2]);
+    assert_eq!(*log.borrow(), vec!["did_update_config 1", "build 2"]);
+    assert!(!context.build_owner.has_dirty_elements());
+}
@@ -0,0 +1,167 @@
+use std::{cell::Cell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, PartialEq, Stateful)]
+struct Root {
+    value: Rc<Cell<u32>>,
+    color: Rc<Cell<u32>>,
+    tag: Rc<Cell<u32>>,
+}
+
+impl Root {
+    fn create_state(&self) -> RootState {
+        RootState {
+            value: self.value.clone(),
+            color: self.color.clone(),
+            tag: self.tag.clone(),
+        }
+    }
+}
+
+#[derive(Debug, State)]
+struct RootState {
+    value: Rc<Cell<u32>>,
+    color: Rc<Cell<u32>>,
+    tag: Rc<Cell<u32>>,
+}
+
+impl RootState {
+    fn build(&self, _context: &impl BuildContext) -> impl IntoView {
+        Label {
+            value: self.value.get(),
+            color: self.color.get(),
+            tag: self.tag.get(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Render)]
+#[render(update_render_object)]
+struct Label {
+    value: u32,
+    color: u32,
+    /// Not used by layout or paint.
+    tag: u32,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject {
+            value: self.value,
+            color: self.color,
+            tag: self.tag,
+        }
+    }
+
+    fn update_render_object(
+        &self,
+        render_object: &mut dyn RenderObjectLifecycle,
+    ) -> RenderObjectUpdate {
+        let Some(render_object) = render_object.downcast_mut::<LabelRenderObject>() else {
+            return RenderObjectUpdate::None;
+        };
+
+        render_object.tag = self.tag;
+
+        if render_object.value != self.value {
+            render_object.value = self.value;
+            render_object.color = self.color;
+
+            RenderObjectUpdate::Layout
+        } else if render_object.color != self.color {
+            render_object.color = self.color;
+
+            RenderObjectUpdate::Paint
+        } else {
+            RenderObjectUpdate::None
+        }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct LabelRenderObject {
+    value: u32,
+    color: u32,
+    tag: u32,
+}
+
+impl RenderObjectLifecycle for LabelRenderObject {}
+
+fn label_value(render_object: &RenderObject) -> u32 {
+    render_object
+        .0
+        .borrow()
+        .lifecycle
+        .downcast_ref::<LabelRenderObject>()
+        .unwrap()
+        .value
+}
+
+fn clean(render_object: &RenderObject) {
+    let mut node = render_object.0.borrow_mut();
+
+    node.needs_layout = false;
+    node.needs_paint = false;
+}
+
+#[test]
+fn test_update_render_object() {
+    let mut context = FrameworkContext::default();
+
+    let value = Rc::new(Cell::new(1));
+    let color = Rc::new(Cell::new(0));
+    let tag = Rc::new(Cell::new(0));
+
+    let root = context
+        .mount_root(
+            Root {
+                value: value.clone(),
+                color: color.clone(),
+                tag: tag.clone(),
+            }
+            .into_view(),
+        )
+        .unwrap();
+
+    let render_object = root.first_render_object(&context).unwrap();
+
+    assert_eq!(label_value(&render_object), 1);
+
+    clean(&render_object);
+
+    value.set(2);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    // The existing render object is updated in place instead of recreated.
+    assert_eq!(root.first_render_object(&context).unwrap(), render_object);
+    assert_eq!(label_value(&render_object), 2);
+    assert!(render_object.needs_layout());
+    assert!(render_object.needs_paint());
+    assert_eq!(context.render_tree.borrow().len(), 1);
+
+    // Only the render object knows what its properties affect.
+    clean(&render_object);
+    color.set(1);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert!(!render_object.needs_layout());
+    assert!(render_object.needs_paint());
+
+    clean(&render_object);
+    tag.set(1);
+
+    root.mark_needs_build();
+    context.flush_build();
+
+    assert!(!render_object.needs_layout());
+    assert!(!render_object.needs_paint());
+}
@@ -0,0 +1,239 @@
+use std::{cell::Cell, rc::Rc};
+
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+
+#[derive(Debug, Default, PartialEq, Render)]
+struct Label {
+    text: String,
+}
+
+impl Label {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        LabelRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct LabelRenderObject {}
+
+impl RenderObjectLifecycle for LabelRenderObject {}
+
+#[derive(Debug, Default, PartialEq, Render)]
+struct Column {
+    spacing: f32,
+    children: Vec<View>,
+}
+
+impl Column {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        ColumnRenderObject {}
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        self.children.clone()
+    }
+}
+
+#[derive(Debug)]
+struct ColumnRenderObject {}
+
+impl RenderObjectLifecycle for ColumnRenderObject {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    View,
+    Edit(u32),
+}
+
+/// Text of a `Label` or `Text` view.
+fn text(view: &View) -> String {
+    match view {
+        View::RenderObject(config) => config.downcast(|label: &Label| label.text.clone()),
+        View::Stateless(config) => config.downcast(|text: &Text| text.text.clone()),
+        _ => None,
+    }
+    .unwrap_or_else(|| format!("{:?}", view))
+}
+
+fn children(view: &View) -> Vec<View> {
+    match view {
+        View::RenderObject(config) => config
+            .downcast(|column: &Column| column.children.clone())
+            .unwrap(),
+        _ => panic!("expected a column: {:?}", view),
+    }
+}
+
+fn screen(title: &str, flag: bool, items: &[u32], mode: Mode) -> View {
+    view! {
+        Column(spacing: 4.0) [
+            "header"
+            Label(text: title.to_owned())
+            if flag {
+                Label(text: "flag".to_owned())
+            } else if items.is_empty() {
+                Label(text: "empty".to_owned())
+            }
+            for item in items {
+                Label(text: item.to_string())
+            }
+            match mode {
+                Mode::View => {}
+                Mode::Edit(id) if id > 0 => Label(text: format!("edit {}", id)),
+                Mode::Edit(_) => {
+                    "new"
+                    "draft"
+                }
+            }
+            { Label { text: "footer".to_owned() } }
+        ]
+    }
+}
+
+#[test]
+fn test_view_macro_children() {
+    let texts = |view: View| children(&view).iter().map(text).collect::<Vec<_>>();
+
+    assert_eq!(
+        texts(screen("title", true, &[1, 2], Mode::View)),
+        vec!["header", "title", "flag", "1", "2", "footer"]
+    );
+
+    assert_eq!(
+        texts(screen("title", false, &[], Mode::Edit(3))),
+        vec!["header", "title", "empty", "edit 3", "footer"]
+    );
+
+    assert_eq!(
+        texts(screen("title", false, &[7], Mode::Edit(0))),
+        vec!["header", "title", "7", "new", "draft", "footer"]
+    );
+
+    let spacing = match screen("title", false, &[], Mode::View) {
+        View::RenderObject(config) => config.downcast(|column: &Column| column.spacing),
+        _ => None,
+    };
+
+    assert_eq!(spacing, Some(4.0));
+
+    let list = view! {
+        [
+            "a"
+            if false {
+                "b"
+            }
+            "c"
+        ]
+    };
+
+    assert_eq!(list.iter().map(text).collect::<Vec<_>>(), vec!["a", "c"]);
+}
+
+#[test]
+fn test_view_macro_keys() {
+    let views = children(&screen("title", false, &[1, 2], Mode::View));
+
+    // Siblings of one `view!` are told apart by their location.
+    assert_ne!(views[0].to_keypath(), views[1].to_keypath());
+    assert_ne!(views[1].to_keypath(), views[2].to_keypath());
+
+    // Loop views share the location and are keyed by the iteration index.
+    assert_eq!(
+        views[2].to_keypath().unwrap().to_caller(),
+        views[3].to_keypath().unwrap().to_caller()
+    );
+    assert_ne!(views[2].to_keypath(), views[3].to_keypath());
+
+    // Key paths are stable across builds.
+    assert_eq!(
+        views,
+        children(&screen("title", false, &[1, 2], Mode::View))
+    );
+
+    let keyed = |ids: &[u32]| {
+        view! {
+            Column(..) [
+                for id in ids {
+                    Label(key: *id, text: id.to_string())
+                    for index in 0..2 {
+                        Label(text: format!("{}.{}", id, index))
+                    }
+                }
+            ]
+        }
+    };
+
+    let forward = children(&keyed(&[1, 2]));
+    let backward = children(&keyed(&[2, 1]));
+
+    assert_eq!(forward.len(), 6);
+
+    // Explicit keys follow the item, index keys follow the position.
+    assert_eq!(forward[0].to_keypath(), backward[3].to_keypath());
+    assert_eq!(forward[1].to_keypath(), backward[1].to_keypath());
+
+    // Nested loops layer the indices of the enclosing loops.
+    assert_ne!(forward[1].to_keypath(), forward[4].to_keypath());
+    assert_ne!(forward[1].to_keypath(), forward[2].to_keypath());
+}
+
+#[test]
+fn test_view_macro_handler() {
+    let taps = Rc::new(Cell::new(0));
+    let on_tap_taps = taps.clone();
+
+    let view = view! {
+        GestureDetector(
+            on_tap: move |_| on_tap_taps.set(on_tap_taps.get() + 1),
+            on_pan_update: |details| assert!(details.delta.dx >= 0.0),
+            behavior: HitTestBehavior::Opaque,
+            ..
+        ) {
+            "tap"
+        }
+    };
+
+    let detector = match &view {
+        View::Stateful(config) => config.downcast(|detector: &GestureDetector| detector.clone()),
+        _ => None,
+    }
+    .unwrap();
+
+    assert_eq!(detector.behavior, HitTestBehavior::Opaque);
+    assert!(detector.on_pan_update.is_some());
+    assert_eq!(text(&detector.child), "tap");
+
+    let on_tap = detector.on_tap.unwrap();
+
+    on_tap.call(());
+    on_tap.call(());
+    assert_eq!(taps.get(), 2);
+}
+
+#[component]
+fn List(count: u32) -> impl IntoView {
+    view! {
+        Column(..) [
+            for index in 0..count {
+                Label(text: index.to_string())
+            }
+        ]
+    }
+}
+
+#[test]
+fn test_view_macro_mount() {
+    let mut context = FrameworkContext::default();
+
+    let root = context.mount_root(view! { List(count: 3) }).unwrap();
+
+    let column = root.first_render_object(&context).unwrap();
+
+    let id = column.0.borrow().id.unwrap();
+
+    assert_eq!(id.children(&context.render_tree.borrow()).count(), 3);
+}
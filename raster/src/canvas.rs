@@ -0,0 +1,242 @@
+use std::rc::Rc;
+
+use agoraui_compose::{
+    geometry::{Offset, Rect, Transform},
+    painting::{Canvas, Color, Image, Paint, PaintStyle, Path, TextRun},
+};
+
+use crate::{
+    pixmap::Pixmap,
+    rasterizer::{fill_mask, stroke_mask, Mask, PixelRect},
+};
+
+#[derive(Debug, Clone)]
+struct CanvasState {
+    transform: Transform,
+    /// `None` if nothing is clipped.
+    clip: Option<Rc<Mask>>,
+}
+
+/// Drawing below a [`Canvas::save_layer`], composited with `alpha` on restore.
+#[derive(Debug)]
+struct Layer {
+    below: Pixmap,
+    below_rect: PixelRect,
+    alpha: u8,
+}
+
+/// [`Canvas`] rasterizing into a [`Pixmap`] on the CPU.
+#[derive(Debug)]
+pub struct SoftwareCanvas {
+    pixmap: Pixmap,
+    /// Device pixels covered by `pixmap`, a layer only covers the clip it was saved with.
+    rect: PixelRect,
+    state: CanvasState,
+    stack: Vec<(CanvasState, Option<Layer>)>,
+}
+
+impl SoftwareCanvas {
+    /// Create a canvas over a transparent pixmap.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::from_pixmap(Pixmap::new(width, height))
+    }
+
+    pub fn from_pixmap(pixmap: Pixmap) -> Self {
+        Self {
+            rect: PixelRect::from_size(pixmap.width() as usize, pixmap.height() as usize),
+            pixmap,
+            state: CanvasState {
+                transform: Transform::IDENTITY,
+                clip: None,
+            },
+            stack: vec![],
+        }
+    }
+
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    pub fn into_pixmap(self) -> Pixmap {
+        self.pixmap
+    }
+
+    /// Pixels which can be drawn, the pixmap limited to the bounds of the current clip.
+    fn bounds(&self) -> PixelRect {
+        match &self.state.clip {
+            Some(clip) => self.rect.intersect(&clip.bounds),
+            None => self.rect,
+        }
+    }
+
+    fn mask(&self, path: &Path, paint: &Paint) -> Mask {
+        match paint.style {
+            PaintStyle::Fill => {
+                fill_mask(path, &self.state.transform, self.bounds(), paint.anti_alias)
+            }
+            PaintStyle::Stroke => stroke_mask(
+                path,
+                &self.state.transform,
+                paint.stroke_width,
+                self.bounds(),
+                paint.anti_alias,
+            ),
+        }
+    }
+
+    /// Blend `color_at(x, y)` over every covered pixel, clipped by the current clip.
+    fn composite<F: FnMut(usize, usize) -> Option<Color>>(&mut self, mask: &Mask, mut color_at: F) {
+        let rect = self.rect;
+        let bounds = mask.bounds;
+
+        for y in bounds.top..bounds.bottom {
+            for x in bounds.left..bounds.right {
+                let coverage = mask.get(x, y);
+
+                let coverage = match &self.state.clip {
+                    Some(clip) => coverage * clip.get(x, y),
+                    None => coverage,
+                };
+
+                if coverage <= 0.0 {
+                    continue;
+                }
+
+                if let Some(color) = color_at(x, y) {
+                    let index = (y - rect.top) * rect.width() + x - rect.left;
+
+                    self.pixmap.blend(index, color, coverage);
+                }
+            }
+        }
+    }
+}
+
+impl Canvas for SoftwareCanvas {
+    fn save(&mut self) {
+        self.stack.push((self.state.clone(), None));
+    }
+
+    fn save_layer(&mut self, alpha: u8) {
+        // Nothing is drawn outside of the current clip until the layer is restored.
+        let rect = self.bounds();
+
+        let layer = Pixmap::new(rect.width() as u32, rect.height() as u32);
+        let below = std::mem::replace(&mut self.pixmap, layer);
+        let below_rect = std::mem::replace(&mut self.rect, rect);
+
+        let layer = Layer {
+            below,
+            below_rect,
+            alpha,
+        };
+
+        self.stack.push((self.state.clone(), Some(layer)));
+    }
+
+    fn restore(&mut self) {
+        let Some((state, layer)) = self.stack.pop() else {
+            return;
+        };
+
+        self.state = state;
+
+        if let Some(Layer {
+            below,
+            below_rect,
+            alpha,
+        }) = layer
+        {
+            let layer = std::mem::replace(&mut self.pixmap, below);
+            let rect = std::mem::replace(&mut self.rect, below_rect);
+
+            self.pixmap.draw_pixmap(
+                &layer,
+                rect.left - below_rect.left,
+                rect.top - below_rect.top,
+                alpha as f32 / 255.0,
+            );
+        }
+    }
+
+    fn transform(&mut self, transform: &Transform) {
+        self.state.transform = transform.then(&self.state.transform);
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        let mut mask = fill_mask(path, &self.state.transform, self.bounds(), true);
+
+        if let Some(clip) = &self.state.clip {
+            let bounds = mask.bounds;
+
+            for y in bounds.top..bounds.bottom {
+                for x in bounds.left..bounds.right {
+                    mask.coverage[(y - bounds.top) * bounds.width() + x - bounds.left] *=
+                        clip.get(x, y);
+                }
+            }
+        }
+
+        self.state.clip = Some(Rc::new(mask));
+    }
+
+    fn draw_path(&mut self, path: &Path, paint: &Paint) {
+        let mask = self.mask(path, paint);
+        let color = paint.color;
+
+        self.composite(&mask, |_, _| Some(color));
+    }
+
+    fn draw_text(&mut self, run: &TextRun, offset: Offset, paint: &Paint) {
+        let fill = Paint {
+            style: PaintStyle::Fill,
+            ..paint.clone()
+        };
+
+        for glyph in &run.glyphs {
+            let origin = offset + glyph.offset;
+
+            self.draw_path(
+                &glyph
+                    .outline
+                    .transform(&Transform::translation(origin.dx, origin.dy)),
+                &fill,
+            );
+        }
+    }
+
+    fn draw_image(&mut self, image: &Image, rect: Rect, paint: &Paint) {
+        let Some(inverse) = self.state.transform.invert() else {
+            return;
+        };
+
+        if rect.is_empty() || image.width == 0 || image.height == 0 {
+            return;
+        }
+
+        let mask = self.mask(
+            &Path::from_rect(rect),
+            &Paint {
+                style: PaintStyle::Fill,
+                ..paint.clone()
+            },
+        );
+
+        let opacity = paint.color.a as f32 / 255.0;
+
+        self.composite(&mask, |x, y| {
+            // Sample the nearest image pixel of the pixel center.
+            let local = inverse.map_point(Offset::new(x as f32 + 0.5, y as f32 + 0.5));
+
+            let u = ((local.dx - rect.left) / rect.width() * image.width as f32).floor();
+            let v = ((local.dy - rect.top) / rect.height() * image.height as f32).floor();
+
+            let color = image.pixel(
+                (u.max(0.0) as u32).min(image.width - 1),
+                (v.max(0.0) as u32).min(image.height - 1),
+            );
+
+            Some(color.with_alpha((color.a as f32 * opacity).round() as u8))
+        });
+    }
+}
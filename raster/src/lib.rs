@@ -0,0 +1,27 @@
+//! Software rasterizer backend, paints the render tree into an in-memory RGBA framebuffer.
+
+mod canvas;
+mod pixmap;
+mod rasterizer;
+
+pub use canvas::*;
+pub use pixmap::*;
+
+use agoraui_compose::{
+    framework::FrameworkContext,
+    geometry::{BoxConstraints, Size},
+};
+
+/// Lay out the render tree of `context` to fill `width` x `height` pixels and paint it.
+pub fn render(context: &mut FrameworkContext, width: u32, height: u32) -> Pixmap {
+    context.flush_layout(BoxConstraints::tight(Size::new(
+        width as f32,
+        height as f32,
+    )));
+
+    let mut canvas = SoftwareCanvas::new(width, height);
+
+    context.flush_paint(&mut canvas);
+
+    canvas.into_pixmap()
+}
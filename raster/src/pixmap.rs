@@ -0,0 +1,159 @@
+use std::{fs::File, io::BufWriter, io::Write, path::Path};
+
+use agoraui_compose::painting::Color;
+
+/// In-memory framebuffer of non premultiplied RGBA8 pixels, row by row.
+///
+/// Pixels are converted to premultiplied `f32` only while blending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pixmap {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Pixmap {
+    /// Create a transparent pixmap.
+    pub fn new(width: u32, height: u32) -> Self {
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|len| len.checked_mul(4))
+            .expect("Pixmap size overflows usize");
+
+        Self {
+            width,
+            height,
+            data: vec![0; len],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Replace every pixel with `color`.
+    pub fn fill(&mut self, color: Color) {
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    /// Non premultiplied RGBA8 pixels, row by row.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the color of pixel at `(x, y)`.
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let pixel = &self.data[index..index + 4];
+
+        Color::rgba(pixel[0], pixel[1], pixel[2], pixel[3])
+    }
+
+    /// Blend `color` over pixel at `index` with `coverage`, source over.
+    pub(crate) fn blend(&mut self, index: usize, color: Color, coverage: f32) {
+        if coverage <= 0.0 {
+            return;
+        }
+
+        self.blend_premultiplied(index, premultiply(color, coverage));
+    }
+
+    /// Blend the pixels of `source` placed at `(left, top)` over this pixmap with `opacity`,
+    /// source over. `source` must lie inside this pixmap.
+    pub(crate) fn draw_pixmap(&mut self, source: &Pixmap, left: usize, top: usize, opacity: f32) {
+        let (width, source_width) = (self.width as usize, source.width as usize);
+
+        if source_width == 0 {
+            return;
+        }
+
+        for (row, source_row) in source.data.chunks_exact(source_width * 4).enumerate() {
+            let start = (top + row) * width + left;
+
+            for (column, pixel) in source_row.chunks_exact(4).enumerate() {
+                if pixel[3] == 0 {
+                    continue;
+                }
+
+                let color = Color::rgba(pixel[0], pixel[1], pixel[2], pixel[3]);
+
+                self.blend_premultiplied(start + column, premultiply(color, opacity));
+            }
+        }
+    }
+
+    fn blend_premultiplied(&mut self, index: usize, source: [f32; 4]) {
+        let pixel = &mut self.data[index * 4..index * 4 + 4];
+
+        let below = premultiply(Color::rgba(pixel[0], pixel[1], pixel[2], pixel[3]), 1.0);
+        let inverse = 1.0 - source[3];
+
+        let mut blended = [0.0; 4];
+
+        for channel in 0..4 {
+            blended[channel] = source[channel] + below[channel] * inverse;
+        }
+
+        let color = unpremultiply(blended);
+
+        pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+    }
+
+    /// Returns a copy of the pixels, see [`Pixmap::data`] to borrow them.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    /// Encode as PNG into `writer`.
+    pub fn write_png<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+
+        writer.write_image_data(&self.data)?;
+
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Encode as PNG file at `path`.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        self.write_png(BufWriter::new(File::create(path)?))
+    }
+}
+
+fn premultiply(color: Color, coverage: f32) -> [f32; 4] {
+    let alpha = color.a as f32 / 255.0 * coverage;
+
+    [
+        color.r as f32 / 255.0 * alpha,
+        color.g as f32 / 255.0 * alpha,
+        color.b as f32 / 255.0 * alpha,
+        alpha,
+    ]
+}
+
+fn unpremultiply([r, g, b, a]: [f32; 4]) -> Color {
+    if a <= 0.0 {
+        return Color::TRANSPARENT;
+    }
+
+    let channel = |value: f32| ((value / a).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    Color::rgba(
+        channel(r),
+        channel(g),
+        channel(b),
+        (a.clamp(0.0, 1.0) * 255.0).round() as u8,
+    )
+}
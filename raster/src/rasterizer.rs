@@ -0,0 +1,503 @@
+use agoraui_compose::{
+    geometry::{Offset, Transform},
+    painting::{Path, PathCommand},
+};
+
+/// Pixel rectangle, `right` and `bottom` are excluded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PixelRect {
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+}
+
+impl PixelRect {
+    /// Rectangle at the origin covering `width` x `height` pixels.
+    pub fn from_size(width: usize, height: usize) -> Self {
+        Self {
+            left: 0,
+            top: 0,
+            right: width,
+            bottom: height,
+        }
+    }
+
+    /// Pixels touched by `contours`, `None` if there is no point.
+    pub fn bounding(contours: &[Contour]) -> Option<Self> {
+        let mut points = contours.iter().flat_map(|contour| contour.points.iter());
+
+        let first = points.next()?;
+
+        let (mut left, mut top, mut right, mut bottom) = (first.dx, first.dy, first.dx, first.dy);
+
+        for point in points {
+            left = left.min(point.dx);
+            top = top.min(point.dy);
+            right = right.max(point.dx);
+            bottom = bottom.max(point.dy);
+        }
+
+        // Negative and NaN coordinates saturate to 0.
+        Some(Self {
+            left: left.floor() as usize,
+            top: top.floor() as usize,
+            right: right.ceil() as usize,
+            bottom: bottom.ceil() as usize,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.right.saturating_sub(self.left)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bottom.saturating_sub(self.top)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+
+    pub fn intersect(&self, other: &PixelRect) -> Self {
+        Self {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+}
+
+/// Coverage in `0.0..=1.0` of each pixel of `bounds`, row by row,
+/// pixels outside of `bounds` are not covered.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mask {
+    pub bounds: PixelRect,
+    pub coverage: Vec<f32>,
+}
+
+impl Mask {
+    /// Returns the coverage of pixel at `(x, y)` in device pixels.
+    pub fn get(&self, x: usize, y: usize) -> f32 {
+        let bounds = &self.bounds;
+
+        if x < bounds.left || x >= bounds.right || y < bounds.top || y >= bounds.bottom {
+            return 0.0;
+        }
+
+        self.coverage[(y - bounds.top) * bounds.width() + x - bounds.left]
+    }
+}
+
+/// Polyline approximating a path contour, implicitly closed when filled.
+#[derive(Debug, Clone, Default)]
+pub struct Contour {
+    pub points: Vec<Offset>,
+    pub closed: bool,
+}
+
+/// Maximum number of line segments a bezier curve is flattened into.
+const MAX_CURVE_SEGMENTS: usize = 64;
+
+fn curve_segments(points: &[Offset]) -> usize {
+    let length = points
+        .windows(2)
+        .map(|pair| distance(pair[0], pair[1]))
+        .sum::<f32>();
+
+    ((length.sqrt() * 1.5).ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+}
+
+fn distance(a: Offset, b: Offset) -> f32 {
+    ((b.dx - a.dx).powi(2) + (b.dy - a.dy).powi(2)).sqrt()
+}
+
+fn lerp(a: Offset, b: Offset, t: f32) -> Offset {
+    Offset::new(a.dx + (b.dx - a.dx) * t, a.dy + (b.dy - a.dy) * t)
+}
+
+/// Flatten `path` mapped by `transform` into polylines in device pixels.
+pub fn flatten(path: &Path, transform: &Transform) -> Vec<Contour> {
+    let mut contours = vec![];
+    let mut current = Contour::default();
+    let mut last = Offset::ZERO;
+
+    let mut finish = |current: &mut Contour, closed: bool| {
+        let mut contour = std::mem::take(current);
+
+        if contour.points.len() > 1 {
+            contour.closed = closed;
+            contours.push(contour);
+        }
+    };
+
+    for command in &path.commands {
+        match *command {
+            PathCommand::MoveTo(p) => {
+                finish(&mut current, false);
+
+                last = transform.map_point(p);
+                current.points.push(last);
+            }
+            PathCommand::LineTo(p) => {
+                if current.points.is_empty() {
+                    current.points.push(last);
+                }
+
+                last = transform.map_point(p);
+                current.points.push(last);
+            }
+            PathCommand::QuadTo(c, p) => {
+                if current.points.is_empty() {
+                    current.points.push(last);
+                }
+
+                let (c, p) = (transform.map_point(c), transform.map_point(p));
+                let segments = curve_segments(&[last, c, p]);
+
+                for i in 1..=segments {
+                    let t = i as f32 / segments as f32;
+
+                    current
+                        .points
+                        .push(lerp(lerp(last, c, t), lerp(c, p, t), t));
+                }
+
+                last = p;
+            }
+            PathCommand::CubicTo(c1, c2, p) => {
+                if current.points.is_empty() {
+                    current.points.push(last);
+                }
+
+                let (c1, c2, p) = (
+                    transform.map_point(c1),
+                    transform.map_point(c2),
+                    transform.map_point(p),
+                );
+                let segments = curve_segments(&[last, c1, c2, p]);
+
+                for i in 1..=segments {
+                    let t = i as f32 / segments as f32;
+                    let (a, b, c) = (lerp(last, c1, t), lerp(c1, c2, t), lerp(c2, p, t));
+
+                    current.points.push(lerp(lerp(a, b, t), lerp(b, c, t), t));
+                }
+
+                last = p;
+            }
+            PathCommand::Close => {
+                if let Some(first) = current.points.first().copied() {
+                    finish(&mut current, true);
+
+                    last = first;
+                }
+            }
+        }
+    }
+
+    finish(&mut current, false);
+
+    contours
+}
+
+/// Number of points of the polygon approximating stroke joins and caps.
+const ROUND_SEGMENTS: usize = 16;
+
+/// Convert polylines into polygons covering a stroke of `width`, with round joins and caps.
+///
+/// Every polygon is counter clockwise, so overlapping pieces never cancel each other.
+pub fn stroke(contours: &[Contour], width: f32) -> Vec<Contour> {
+    let radius = width / 2.0;
+    let mut polygons = vec![];
+
+    let push = |polygons: &mut Vec<Contour>, mut points: Vec<Offset>| {
+        if signed_area(&points) < 0.0 {
+            points.reverse();
+        }
+
+        polygons.push(Contour {
+            points,
+            closed: true,
+        });
+    };
+
+    for contour in contours {
+        let mut segments = contour
+            .points
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<_>>();
+
+        if contour.closed {
+            segments.push((*contour.points.last().unwrap(), contour.points[0]));
+        }
+
+        for (p, q) in segments {
+            let length = distance(p, q);
+
+            if length <= f32::EPSILON {
+                continue;
+            }
+
+            let normal = Offset::new(
+                -(q.dy - p.dy) / length * radius,
+                (q.dx - p.dx) / length * radius,
+            );
+
+            push(
+                &mut polygons,
+                vec![p + normal, q + normal, q - normal, p - normal],
+            );
+        }
+
+        for point in &contour.points {
+            push(
+                &mut polygons,
+                (0..ROUND_SEGMENTS)
+                    .map(|i| {
+                        let angle = i as f32 / ROUND_SEGMENTS as f32 * std::f32::consts::TAU;
+
+                        Offset::new(
+                            point.dx + radius * angle.cos(),
+                            point.dy + radius * angle.sin(),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    polygons
+}
+
+fn signed_area(points: &[Offset]) -> f32 {
+    let mut area = 0.0;
+
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+
+        area += p.dx * q.dy - q.dx * p.dy;
+    }
+
+    area / 2.0
+}
+
+/// Anti-aliased scanline rasterizer accumulating signed area coverage per row.
+///
+/// Only the pixels of `bounds` are rasterized, geometry outside of them is clipped.
+pub struct Rasterizer {
+    bounds: PixelRect,
+    width: usize,
+    height: usize,
+    /// Two extra cells per row receive coverage right of the last pixel.
+    accumulation: Vec<f32>,
+}
+
+impl Rasterizer {
+    pub fn new(bounds: PixelRect) -> Self {
+        let (width, height) = (bounds.width(), bounds.height());
+
+        Self {
+            bounds,
+            width,
+            height,
+            accumulation: vec![0.0; (width + 2) * height],
+        }
+    }
+
+    /// Add filled polygons, overlapping polygons with the same winding are unioned.
+    pub fn fill(&mut self, contours: &[Contour]) {
+        let origin = Offset::new(self.bounds.left as f32, self.bounds.top as f32);
+
+        for contour in contours {
+            for (i, p) in contour.points.iter().enumerate() {
+                let q = contour.points[(i + 1) % contour.points.len()];
+
+                self.line(*p - origin, q - origin);
+            }
+        }
+    }
+
+    /// Resolve the accumulated area into pixel coverage, non-zero winding.
+    pub fn into_mask(self, anti_alias: bool) -> Mask {
+        let stride = self.width + 2;
+        let mut mask = vec![0.0; self.width * self.height];
+
+        for y in 0..self.height {
+            let mut sum = 0.0;
+
+            for x in 0..self.width {
+                sum += self.accumulation[y * stride + x];
+
+                let coverage = sum.abs().min(1.0);
+
+                mask[y * self.width + x] = if anti_alias {
+                    coverage
+                } else if coverage >= 0.5 {
+                    1.0
+                } else {
+                    0.0
+                };
+            }
+        }
+
+        Mask {
+            bounds: self.bounds,
+            coverage: mask,
+        }
+    }
+
+    /// Split the line at the left and right edges, so every piece lies inside the columns.
+    fn line(&mut self, p0: Offset, p1: Offset) {
+        let right = self.width as f32;
+        let mut pieces = vec![p0, p1];
+
+        for edge in [0.0, right] {
+            let mut i = 0;
+
+            while i + 1 < pieces.len() {
+                let (a, b) = (pieces[i], pieces[i + 1]);
+
+                if (a.dx < edge && b.dx > edge) || (a.dx > edge && b.dx < edge) {
+                    pieces.insert(i + 1, lerp(a, b, (edge - a.dx) / (b.dx - a.dx)));
+                }
+
+                i += 1;
+            }
+        }
+
+        for pair in pieces.windows(2) {
+            // Coverage left of the bounds accumulates at the first column,
+            // coverage right of the bounds is dropped into the extra cells.
+            let clamp = |p: Offset| Offset::new(p.dx.clamp(0.0, right), p.dy);
+
+            self.clipped_line(clamp(pair[0]), clamp(pair[1]));
+        }
+    }
+
+    fn clipped_line(&mut self, p0: Offset, p1: Offset) {
+        if p0.dy == p1.dy {
+            return;
+        }
+
+        let (direction, p0, p1) = if p0.dy < p1.dy {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+
+        let height = self.height as f32;
+
+        if p1.dy <= 0.0 || p0.dy >= height {
+            return;
+        }
+
+        let stride = self.width + 2;
+        let dxdy = (p1.dx - p0.dx) / (p1.dy - p0.dy);
+
+        let mut x = p0.dx;
+
+        if p0.dy < 0.0 {
+            x -= p0.dy * dxdy;
+        }
+
+        let y_start = p0.dy.max(0.0) as usize;
+        let y_end = (p1.dy.min(height).ceil() as usize).min(self.height);
+
+        for y in y_start..y_end {
+            let line_start = y * stride;
+            let dy = ((y + 1) as f32).min(p1.dy) - (y as f32).max(p0.dy);
+            let x_next = x + dxdy * dy;
+            let d = dy * direction;
+
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+
+            let x0_floor = x0.floor();
+            let x0i = x0_floor as usize;
+            let x1_ceil = x1.ceil();
+            let x1i = x1_ceil as usize;
+
+            let acc = &mut self.accumulation;
+
+            if x1i <= x0i + 1 {
+                let xmf = 0.5 * (x + x_next) - x0_floor;
+
+                acc[line_start + x0i] += d - d * xmf;
+                acc[line_start + x0i + 1] += d * xmf;
+            } else {
+                let s = (x1 - x0).recip();
+                let x0f = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                let x1f = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1f * x1f;
+
+                acc[line_start + x0i] += d * a0;
+
+                if x1i == x0i + 2 {
+                    acc[line_start + x0i + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - x0f);
+
+                    acc[line_start + x0i + 1] += d * (a1 - a0);
+
+                    for xi in x0i + 2..x1i - 1 {
+                        acc[line_start + xi] += d * s;
+                    }
+
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+
+                    acc[line_start + x1i - 1] += d * (1.0 - a2 - am);
+                }
+
+                acc[line_start + x1i] += d * am;
+            }
+
+            x = x_next;
+        }
+    }
+}
+
+/// Rasterize `contours` into a coverage mask limited to `bounds` and the pixels they touch.
+fn rasterize(contours: &[Contour], bounds: PixelRect, anti_alias: bool) -> Mask {
+    let Some(touched) = PixelRect::bounding(contours) else {
+        return Mask::default();
+    };
+
+    let bounds = bounds.intersect(&touched);
+
+    if bounds.is_empty() {
+        return Mask::default();
+    }
+
+    let mut rasterizer = Rasterizer::new(bounds);
+
+    rasterizer.fill(contours);
+
+    rasterizer.into_mask(anti_alias)
+}
+
+/// Rasterize `path` mapped by `transform` into a coverage mask, filled with the non-zero rule.
+///
+/// The mask covers the pixels of `bounds` touched by the path.
+pub fn fill_mask(path: &Path, transform: &Transform, bounds: PixelRect, anti_alias: bool) -> Mask {
+    rasterize(&flatten(path, transform), bounds, anti_alias)
+}
+
+/// Rasterize the stroke of `path` mapped by `transform` into a coverage mask.
+///
+/// The stroke width is scaled by the transform, using the average scale of both axes.
+pub fn stroke_mask(
+    path: &Path,
+    transform: &Transform,
+    stroke_width: f32,
+    bounds: PixelRect,
+    anti_alias: bool,
+) -> Mask {
+    let scale = ((transform.a * transform.d - transform.b * transform.c).abs()).sqrt();
+
+    let polygons = stroke(&flatten(path, transform), (stroke_width * scale).max(0.0));
+
+    rasterize(&polygons, bounds, anti_alias)
+}
@@ -0,0 +1,263 @@
+use agoraui_compose::{framework::FrameworkContext, prelude::*};
+use agoraui_raster::{render, SoftwareCanvas};
+
+const RED: Color = Color::rgb(255, 0, 0);
+const BLUE: Color = Color::rgb(0, 0, 255);
+
+fn coverage(canvas: &SoftwareCanvas) -> f32 {
+    let pixmap = canvas.pixmap();
+    let mut total = 0.0;
+
+    for y in 0..pixmap.height() {
+        for x in 0..pixmap.width() {
+            total += pixmap.pixel(x, y).a as f32 / 255.0;
+        }
+    }
+
+    total
+}
+
+#[test]
+fn test_fill_rect() {
+    let mut canvas = SoftwareCanvas::new(10, 10);
+
+    canvas.draw_rect(Rect::from_ltwh(2.0, 2.0, 4.5, 4.0), &Paint::fill(RED));
+
+    let pixmap = canvas.pixmap();
+
+    assert_eq!(pixmap.pixel(3, 3), RED);
+    assert_eq!(pixmap.pixel(0, 0), Color::TRANSPARENT);
+    assert_eq!(pixmap.pixel(7, 3), Color::TRANSPARENT);
+
+    // Half covered column is anti-aliased.
+    assert_eq!(pixmap.pixel(6, 3), RED.with_alpha(128));
+
+    assert!((coverage(&canvas) - 18.0).abs() < 0.1);
+}
+
+#[test]
+fn test_fill_outside_canvas() {
+    let mut canvas = SoftwareCanvas::new(10, 10);
+
+    // Only the part inside the canvas is rasterized, the winding left of it still counts.
+    canvas.draw_rect(Rect::from_ltwh(-5.0, -5.0, 8.0, 20.0), &Paint::fill(RED));
+    canvas.draw_rect(Rect::from_ltwh(8.0, 20.0, 4.0, 4.0), &Paint::fill(BLUE));
+
+    let pixmap = canvas.pixmap();
+
+    assert_eq!(pixmap.pixel(0, 0), RED);
+    assert_eq!(pixmap.pixel(2, 9), RED);
+    assert_eq!(pixmap.pixel(3, 0), Color::TRANSPARENT);
+
+    assert!((coverage(&canvas) - 30.0).abs() < 0.1);
+}
+
+#[test]
+fn test_fill_circle_area() {
+    let mut canvas = SoftwareCanvas::new(40, 40);
+
+    canvas.draw_path(
+        &Path::from_oval(Rect::from_ltwh(5.0, 5.0, 30.0, 30.0)),
+        &Paint::fill(RED),
+    );
+
+    let expected = std::f32::consts::PI * 15.0 * 15.0;
+
+    assert!((coverage(&canvas) - expected).abs() / expected < 0.01);
+}
+
+#[test]
+fn test_stroke() {
+    let mut canvas = SoftwareCanvas::new(20, 20);
+
+    let mut path = Path::new();
+
+    path.move_to(2.0, 10.0).line_to(18.0, 10.0);
+
+    canvas.draw_path(&path, &Paint::stroke(RED, 2.0));
+
+    let pixmap = canvas.pixmap();
+
+    assert_eq!(pixmap.pixel(10, 9), RED);
+    assert_eq!(pixmap.pixel(10, 10), RED);
+    assert_eq!(pixmap.pixel(10, 12), Color::TRANSPARENT);
+}
+
+#[test]
+fn test_alpha_blend() {
+    let mut canvas = SoftwareCanvas::new(4, 4);
+
+    canvas.draw_rect(Rect::from_ltwh(0.0, 0.0, 4.0, 4.0), &Paint::fill(RED));
+    canvas.draw_rect(
+        Rect::from_ltwh(0.0, 0.0, 4.0, 4.0),
+        &Paint::fill(BLUE.with_alpha(128)),
+    );
+
+    assert_eq!(canvas.pixmap().pixel(1, 1), Color::rgb(127, 0, 128));
+}
+
+#[test]
+fn test_clip_and_transform() {
+    let mut canvas = SoftwareCanvas::new(20, 20);
+
+    canvas.save();
+    canvas.translate(Offset::new(5.0, 5.0));
+    canvas.clip_rect(Rect::from_ltwh(0.0, 0.0, 5.0, 5.0));
+    canvas.draw_rect(Rect::from_ltwh(0.0, 0.0, 100.0, 100.0), &Paint::fill(RED));
+    canvas.restore();
+
+    canvas.draw_rect(Rect::from_ltwh(0.0, 0.0, 2.0, 2.0), &Paint::fill(BLUE));
+
+    let pixmap = canvas.pixmap();
+
+    assert_eq!(pixmap.pixel(5, 5), RED);
+    assert_eq!(pixmap.pixel(9, 9), RED);
+    assert_eq!(pixmap.pixel(10, 10), Color::TRANSPARENT);
+    assert_eq!(pixmap.pixel(4, 4), Color::TRANSPARENT);
+    assert_eq!(pixmap.pixel(1, 1), BLUE);
+}
+
+#[test]
+fn test_draw_image() {
+    let image = Image::new(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+
+    let mut canvas = SoftwareCanvas::new(8, 4);
+
+    canvas.draw_image(
+        &image,
+        Rect::from_ltwh(0.0, 0.0, 8.0, 4.0),
+        &Paint::default(),
+    );
+
+    let pixmap = canvas.pixmap();
+
+    assert_eq!(pixmap.pixel(1, 1), RED);
+    assert_eq!(pixmap.pixel(6, 2), BLUE);
+}
+
+#[test]
+fn test_png() {
+    let mut canvas = SoftwareCanvas::new(3, 2);
+
+    canvas.draw_rect(Rect::from_ltwh(0.0, 0.0, 1.0, 2.0), &Paint::fill(RED));
+
+    let mut bytes = vec![];
+
+    canvas.pixmap().write_png(&mut bytes).unwrap();
+
+    let decoder = png::Decoder::new(bytes.as_slice());
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).unwrap();
+
+    assert_eq!((info.width, info.height), (3, 2));
+    assert_eq!(
+        &buf[..info.buffer_size()],
+        canvas.pixmap().to_rgba8().as_slice()
+    );
+    assert_eq!(&buf[0..8], &[255, 0, 0, 255, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_data() {
+    let mut canvas = SoftwareCanvas::new(2, 1);
+
+    canvas.draw_rect(
+        Rect::from_ltwh(1.0, 0.0, 1.0, 1.0),
+        &Paint::fill(BLUE.with_alpha(128)),
+    );
+
+    assert_eq!(canvas.pixmap().data(), &[0, 0, 0, 0, 0, 0, 255, 128]);
+}
+
+#[derive(Debug, PartialEq, Render)]
+struct ColoredBox {
+    color: Color,
+}
+
+impl ColoredBox {
+    fn create_render_object(&self) -> impl RenderObjectLifecycle {
+        ColoredBoxRenderObject { color: self.color }
+    }
+
+    fn render_object_children(&self) -> Vec<View> {
+        vec![]
+    }
+}
+
+#[derive(Debug)]
+struct ColoredBoxRenderObject {
+    color: Color,
+}
+
+impl RenderObjectLifecycle for ColoredBoxRenderObject {
+    fn paint(&self, context: &mut PaintContext, offset: Offset) {
+        let rect = Rect::from_offset_size(offset, context.size());
+
+        context.canvas().draw_rect(rect, &Paint::fill(self.color));
+    }
+}
+
+#[test]
+fn test_render_tree() {
+    let mut context = FrameworkContext::default();
+
+    let element_id = ColoredBox { color: BLUE }
+        .into_view()
+        .into_element(&mut context.element_tree.borrow_mut())
+        .unwrap();
+
+    let root = context
+        .element_tree
+        .borrow()
+        .get(element_id)
+        .unwrap()
+        .get()
+        .clone();
+
+    root.mount(&mut context, None);
+
+    let pixmap = render(&mut context, 4, 3);
+
+    assert_eq!(pixmap.pixel(0, 0), BLUE);
+    assert_eq!(pixmap.pixel(3, 2), BLUE);
+}
+
+#[test]
+fn test_save_layer() {
+    let mut canvas = SoftwareCanvas::new(4, 1);
+
+    canvas.save_layer(128);
+    canvas.draw_rect(Rect::from_ltwh(0.0, 0.0, 3.0, 1.0), &Paint::fill(RED));
+    canvas.draw_rect(Rect::from_ltwh(1.0, 0.0, 3.0, 1.0), &Paint::fill(RED));
+    canvas.restore();
+
+    let pixmap = canvas.pixmap();
+
+    // Overlapping drawing is blended as a group.
+    assert_eq!(pixmap.pixel(0, 0), RED.with_alpha(128));
+    assert_eq!(pixmap.pixel(1, 0), RED.with_alpha(128));
+    assert_eq!(pixmap.pixel(3, 0), RED.with_alpha(128));
+}
+
+#[test]
+fn test_save_layer_clipped() {
+    let mut canvas = SoftwareCanvas::new(4, 2);
+
+    canvas.clip_rect(Rect::from_ltwh(1.0, 1.0, 2.0, 1.0));
+    canvas.save_layer(128);
+
+    // The layer only covers the clip.
+    assert_eq!((canvas.pixmap().width(), canvas.pixmap().height()), (2, 1));
+
+    canvas.draw_rect(Rect::from_ltwh(0.0, 0.0, 4.0, 2.0), &Paint::fill(RED));
+    canvas.restore();
+
+    let pixmap = canvas.pixmap();
+
+    assert_eq!(pixmap.pixel(0, 1), Color::TRANSPARENT);
+    assert_eq!(pixmap.pixel(1, 1), RED.with_alpha(128));
+    assert_eq!(pixmap.pixel(2, 1), RED.with_alpha(128));
+    assert_eq!(pixmap.pixel(3, 1), Color::TRANSPARENT);
+    assert_eq!(pixmap.pixel(1, 0), Color::TRANSPARENT);
+}
@@ -1 +1,2 @@
 pub use agoraui_compose as compose;
+pub use agoraui_raster as raster;